@@ -0,0 +1,221 @@
+//! Anomaly scoring with kernel density estimation.
+//!
+//! A kernel density estimation of a reference sample, for example a baseline
+//! window of measurements, scores new values by how improbable they are under
+//! the reference distribution. Smaller scores are more anomalous. A value is
+//! flagged as an anomaly when its score is below a threshold calibrated on
+//! the reference sample for a chosen false alarm rate.
+//!
+//! Scoring the reference samples against an estimation containing them
+//! overstates how probable they are, most of all for isolated samples. The
+//! threshold is instead calibrated on leave-one-out scores, each reference
+//! sample scored against the estimation of the other samples, which behave
+//! like scores of new values from the reference distribution.
+
+use density::Density;
+use kde::{KernelDensityEstimation, Kernel};
+use std::f64;
+
+/// Anomaly score of a value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    /// Log density of the estimation at the value. This flags values in
+    /// regions of low density anywhere, including gaps between modes.
+    Density,
+    /// Probability of the estimation below the value, F(x). This flags
+    /// unusually small values.
+    LowerTail,
+    /// Probability of the estimation above the value, 1 - F(x). This flags
+    /// unusually large values, such as high latencies.
+    UpperTail,
+    /// Twice the smaller of the lower and upper tail probabilities. This
+    /// flags unusually small or large values.
+    TwoTailed,
+}
+
+/// Anomaly detector for a reference sample, bandwidth, kernel and score.
+pub struct AnomalyDetector<K: Kernel> {
+    estimation: KernelDensityEstimation<K>,
+    method: Method,
+    false_alarm_rate: f64,
+    threshold: f64,
+}
+
+impl<K: Kernel> AnomalyDetector<K> {
+    /// Construct a new anomaly detector for a given reference sample,
+    /// bandwidth, kernel, score and false alarm rate.
+    ///
+    /// The threshold is the leave-one-out score of rank floor(rate * n) + 1
+    /// among the n reference samples, so at most a proportion rate of the
+    /// leave-one-out scores are below it. For the density score, calibration
+    /// costs O(n^2) kernel evaluations for kernels with unbounded support and
+    /// O(n * k) for compact kernels with k samples in the kernel support.
+    ///
+    /// # Panics
+    ///
+    /// Bandwidth must be greater than zero, the reference sample must have
+    /// more than one element and the false alarm rate must satisfy 0 < rate
+    /// < 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::anomaly::{AnomalyDetector, Method};
+    /// use kernel_density::kde::NormalKernel;
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let detector = AnomalyDetector::new(&samples, 1.0, NormalKernel, Method::UpperTail, 0.1);
+    ///
+    /// assert!(detector.is_anomaly(15.0));
+    /// assert!(!detector.is_anomaly(5.0));
+    /// ```
+    pub fn new(samples: &[f64],
+               bandwidth: f64,
+               kernel: K,
+               method: Method,
+               false_alarm_rate: f64)
+               -> AnomalyDetector<K> {
+        assert!(samples.len() > 1);
+        assert!(0.0 < false_alarm_rate && false_alarm_rate < 1.0);
+
+        let estimation = KernelDensityEstimation::new(samples, bandwidth, kernel);
+
+        let mut scores = leave_one_out_scores(&estimation, method);
+        scores.sort_by(|score_1, score_2| score_1.partial_cmp(score_2).unwrap());
+
+        let length = scores.len();
+        let rank = ((false_alarm_rate * length as f64).floor() as usize).min(length - 1);
+        let threshold = scores[rank];
+
+        AnomalyDetector {
+            estimation,
+            method,
+            false_alarm_rate,
+            threshold,
+        }
+    }
+
+    /// Return the kernel density estimation of the reference sample.
+    pub fn estimation(&self) -> &KernelDensityEstimation<K> {
+        &self.estimation
+    }
+
+    /// Return the anomaly score method.
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    /// Return the false alarm rate. The proportion of reference samples
+    /// whose leave-one-out scores fall below the threshold is at most this
+    /// rate.
+    pub fn false_alarm_rate(&self) -> f64 {
+        self.false_alarm_rate
+    }
+
+    /// Return the threshold calibrated for the false alarm rate. Values with
+    /// scores below the threshold are anomalies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::anomaly::{AnomalyDetector, Method};
+    /// use kernel_density::kde::NormalKernel;
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let detector = AnomalyDetector::new(&samples, 1.0, NormalKernel, Method::UpperTail, 0.1);
+    ///
+    /// assert!(detector.score(15.0) < detector.threshold());
+    /// ```
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Calculate the anomaly score of a given value. Smaller scores are more
+    /// anomalous.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::anomaly::{AnomalyDetector, Method};
+    /// use kernel_density::kde::UniformKernel;
+    ///
+    /// let samples = vec!(0.0, 1.0, 2.0, 3.0);
+    /// let detector = AnomalyDetector::new(&samples, 1.0, UniformKernel, Method::LowerTail, 0.1);
+    ///
+    /// assert_eq!(detector.score(1.5), 0.5);
+    /// ```
+    pub fn score(&self, x: f64) -> f64 {
+        match self.method {
+            Method::Density => self.estimation.log_density(x),
+            _ => tail_score(self.method, self.estimation.cdf(x)),
+        }
+    }
+
+    /// Calculate the anomaly scores of a slice of values.
+    pub fn scores(&self, xs: &[f64]) -> Vec<f64> {
+        match self.method {
+            Method::Density => xs.iter().map(|&x| self.estimation.log_density(x)).collect(),
+            _ => {
+                self.estimation
+                    .cdfs(xs)
+                    .into_iter()
+                    .map(|cdf| tail_score(self.method, cdf))
+                    .collect()
+            }
+        }
+    }
+
+    /// Test whether a given value is an anomaly, with score below the
+    /// threshold.
+    pub fn is_anomaly(&self, x: f64) -> bool {
+        self.score(x) < self.threshold
+    }
+}
+
+/// Convert a cumulative density to a tail probability score.
+fn tail_score(method: Method, cdf: f64) -> f64 {
+    match method {
+        Method::LowerTail => cdf,
+        Method::UpperTail => 1.0 - cdf,
+        _ => 2.0 * cdf.min(1.0 - cdf),
+    }
+}
+
+/// Calculate the score of each sample against the estimation of the other
+/// samples, in sorted sample order.
+fn leave_one_out_scores<K: Kernel>(estimation: &KernelDensityEstimation<K>,
+                                   method: Method)
+                                   -> Vec<f64> {
+    let samples = estimation.samples();
+    let weights = estimation.weights();
+    let kernel = estimation.kernel();
+    let total_weight: f64 = weights.iter().sum();
+
+    if method != Method::Density {
+        // Remove the sample's own kernel, which has mass k_cdf(0) below the
+        // sample.
+        let own = kernel.cdf(0.0);
+
+        return estimation.cdfs(samples)
+            .into_iter()
+            .zip(weights)
+            .map(|(cdf, weight)| {
+                let cdf = (total_weight * cdf - weight * own) / (total_weight - weight);
+                tail_score(method, cdf.clamp(0.0, 1.0))
+            })
+            .collect();
+    }
+
+    // Log densities are summed directly rather than subtracting the sample's
+    // own kernel from the density, which cancels badly for isolated samples.
+    samples.iter()
+        .enumerate()
+        .map(|(i, &x)| estimation.log_density_excluding(x, Some(i)))
+        .collect()
+}
@@ -0,0 +1,75 @@
+extern crate kernel_density;
+
+use kernel_density::anomaly::{AnomalyDetector, Method};
+use kernel_density::kde::{bandwidth, NormalKernel};
+
+use std::env;
+use std::io::{BufReader, BufRead};
+use std::fs::File;
+use std::path::Path;
+
+fn parse_float(s: &str) -> f64 {
+    s.parse::<f64>().expect("Not a floating point number.")
+}
+
+fn read_samples(path: &str) -> Vec<f64> {
+    let file = BufReader::new(File::open(Path::new(path)).unwrap());
+    file.lines().map(|line| parse_float(&line.unwrap())).collect()
+}
+
+/// Score measurements for anomalies against a baseline using Normal kernel
+/// density estimation.
+///
+/// Input files must be single-column headerless data files.
+///
+/// # Examples
+///
+/// ```bash
+/// cargo run --bin anomaly_scores <rate> <method> <baseline> <file> [<bandwidth>]
+/// ```
+///
+/// This will fit a Normal KDE to the baseline file, calibrate an anomaly
+/// threshold with false alarm rate `<rate>` on leave-one-out scores of the
+/// baseline, and print the score of each line of the file and whether it is
+/// an anomaly, 1, or not, 0. See `anomaly::AnomalyDetector`.
+///
+/// `<method>` must be `density`, `lower_tail`, `upper_tail` or
+/// `two_tailed`. Density scores are log densities and the others are tail
+/// probabilities. `<rate>` must be a floating point number strictly between
+/// zero and one. `<bandwidth>` defaults to Silverman's rule of thumb for the
+/// baseline and must otherwise be a floating point number strictly greater
+/// than zero.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let rate = parse_float(&args[1]);
+    assert!(0.0 < rate && rate < 1.0);
+
+    let method = match args[2].as_str() {
+        "density" => Method::Density,
+        "lower_tail" => Method::LowerTail,
+        "upper_tail" => Method::UpperTail,
+        "two_tailed" => Method::TwoTailed,
+        _ => panic!("Method must be density, lower_tail, upper_tail or two_tailed."),
+    };
+
+    let baseline = read_samples(&args[3]);
+    let xs = read_samples(&args[4]);
+
+    let bandwidth = if args.len() > 5 {
+        parse_float(&args[5])
+    } else {
+        bandwidth::silverman(&baseline)
+    };
+
+    assert!(bandwidth > 0.0);
+
+    let detector = AnomalyDetector::new(&baseline, bandwidth, NormalKernel, method, rate);
+    let scores = detector.scores(&xs);
+
+    println!("x\tscore\tanomaly");
+    for (x, score) in xs.iter().zip(&scores) {
+        let anomaly = if *score < detector.threshold() { 1 } else { 0 };
+        println!("{}\t{}\t{}", x, score, anomaly);
+    }
+}
@@ -0,0 +1,61 @@
+extern crate kernel_density;
+extern crate rand;
+
+use kernel_density::multimodality::dip;
+use rand::{Rng, SeedableRng, StdRng};
+
+/// Seed of the random number generator, so the table is reproducible.
+const SEED: [usize; 4] = [1, 2, 3, 4];
+
+/// Tabulated sample sizes and the number of simulated dips for each.
+const SIZES: [(usize, usize); 18] = [(4, 200000), (5, 200000), (6, 200000), (7, 200000),
+                                     (8, 200000), (9, 200000), (10, 200000), (15, 200000),
+                                     (20, 200000), (30, 200000), (50, 200000), (100, 200000),
+                                     (200, 200000), (500, 200000), (1000, 200000),
+                                     (2000, 100000), (5000, 40000), (10000, 40000)];
+
+/// Cumulative probabilities of the tabulated quantiles.
+const PROBABILITIES: [f64; 19] = [0.0, 0.01, 0.02, 0.05, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8,
+                                  0.9, 0.95, 0.98, 0.99, 0.995, 0.998, 0.999];
+
+/// Simulate reference quantiles of the dip of uniform samples.
+///
+/// # Examples
+///
+/// ```bash
+/// cargo run --release --bin dip_quantiles
+/// ```
+///
+/// This will print the quantiles of sqrt(n) * dip for the sample sizes and
+/// probabilities tabulated in `multimodality::tables`, as rows of the
+/// `QUANTILES` table. The quantile at probability p is the smallest simulated
+/// value with at least a proportion p of the simulated values at or below it.
+/// Sizes are simulated in increasing order from a single generator with a
+/// fixed seed, so the output is the same on every run.
+fn main() {
+    let mut rng: StdRng = SeedableRng::from_seed(&SEED[..]);
+
+    for &(size, replicates) in SIZES.iter() {
+        let mut sample = vec![0.0; size];
+        let mut dips: Vec<f64> = (0..replicates)
+            .map(|_| {
+                for value in &mut sample {
+                    *value = rng.next_f64();
+                }
+
+                (size as f64).sqrt() * dip(&sample)
+            })
+            .collect();
+
+        dips.sort_by(|x_1, x_2| x_1.partial_cmp(x_2).unwrap());
+
+        let quantiles: Vec<String> = PROBABILITIES.iter()
+            .map(|&p| {
+                let rank = ((p * replicates as f64).ceil() as usize).max(1);
+                format!("{:.4}", dips[rank - 1])
+            })
+            .collect();
+
+        println!("({}, [{}]),", size, quantiles.join(", "));
+    }
+}
@@ -0,0 +1,84 @@
+extern crate kernel_density;
+
+use kernel_density::kde::NormalKernel;
+use kernel_density::regression::{KernelRegression, Method};
+
+use std::env;
+use std::io::{BufReader, BufRead};
+use std::fs::File;
+use std::path::Path;
+
+fn parse_float(s: &str) -> f64 {
+    s.parse::<f64>().expect("Not a floating point number.")
+}
+
+/// Calculate Normal kernel regression values.
+///
+/// Input files must be two-column headerless tab-separated data files of x
+/// and y values.
+///
+/// # Examples
+///
+/// ```bash
+/// cargo run --bin kernel_regression <min> <max> <bandwidth> <method> <file>
+/// ```
+///
+/// This will print the fitted values and pointwise standard errors of the
+/// Normal kernel regression of y on x for values between min and max using
+/// 0.01 as step size.
+///
+/// `<method>` must be `nadaraya_watson` or `local_linear`. `<bandwidth>` must
+/// be a floating point number strictly greater than zero. `<min>` and `<max>`
+/// must be floating point numbers with `<min>` less than `<max>`.
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let min: f64 = parse_float(&args[1]);
+    let max: f64 = parse_float(&args[2]);
+    let bandwidth: f64 = parse_float(&args[3]);
+
+    assert!(bandwidth > 0.0);
+    assert!(min <= max);
+
+    let method = match args[4].as_str() {
+        "nadaraya_watson" => Method::NadarayaWatson,
+        "local_linear" => Method::LocalLinear,
+        _ => panic!("Method must be nadaraya_watson or local_linear."),
+    };
+
+    let path = Path::new(&args[5]);
+    let file = BufReader::new(File::open(path).unwrap());
+
+    let (xs, ys): (Vec<f64>, Vec<f64>) = file.lines()
+        .map(|line| {
+            let line = line.unwrap();
+            let mut columns = line.split('\t');
+            let x = parse_float(columns.next().expect("Missing x column."));
+            let y = parse_float(columns.next().expect("Missing y column."));
+            (x, y)
+        })
+        .unzip();
+
+    let regression = KernelRegression::new(&xs, &ys, bandwidth, NormalKernel, method);
+
+    // Iterate using fixed point arithmetic over a 0.01 grid resolution.
+    let mut xs = vec![min];
+    let mut x_fixed: i64 = (min * 100.0).floor() as i64 + 1;
+    let mut x_f64: f64 = x_fixed as f64 / 100.0;
+
+    while x_f64 < max {
+        xs.push(x_f64);
+
+        x_fixed += 1;
+        x_f64 = x_fixed as f64 / 100.0;
+    }
+
+    xs.push(max);
+
+    let fits = regression.fits(&xs);
+
+    println!("x\tfit\tse");
+    for (x, fit) in xs.iter().zip(&fits) {
+        println!("{}\t{}\t{}", x, fit.value, fit.standard_error);
+    }
+}
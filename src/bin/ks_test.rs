@@ -29,8 +29,8 @@ fn main() {
     let path1 = Path::new(&args[1]);
     let path2 = Path::new(&args[2]);
 
-    let file1 = BufReader::new(File::open(&path1).unwrap());
-    let file2 = BufReader::new(File::open(&path2).unwrap());
+    let file1 = BufReader::new(File::open(path1).unwrap());
+    let file2 = BufReader::new(File::open(path2).unwrap());
 
     let lines1 = file1.lines().map(|line| line.unwrap());
     let lines2 = file2.lines().map(|line| line.unwrap());
@@ -1,16 +1,59 @@
 extern crate kernel_density;
+extern crate rand;
 
 use kernel_density::kde;
+use kernel_density::kde::{confidence, grid, KernelDensityEstimation, NormalKernel};
+use kernel_density::kde::confidence::Band;
 
 use std::env;
 use std::io::{BufReader, BufRead};
 use std::fs::File;
 use std::path::Path;
 
+/// Bootstrap replicates for simultaneous confidence bands.
+const REPLICATES: usize = 1000;
+
 fn parse_float(s: String) -> f64 {
     s.parse::<f64>().expect("Not a floating point number.")
 }
 
+/// Calculate the requested confidence band, if any, over given values.
+fn band(xs: &[f64], bandwidth: f64, values: &[f64], band: Option<(bool, f64)>) -> Option<Band> {
+    band.map(|(simultaneous, level)| {
+        let kde = KernelDensityEstimation::new(xs, bandwidth, NormalKernel);
+
+        if simultaneous {
+            confidence::simultaneous(&kde, values, level, REPLICATES, &mut rand::thread_rng())
+        } else {
+            confidence::pointwise(&kde, values, level)
+        }
+    })
+}
+
+/// Print the values, using the exact band estimate as the density where a
+/// band is given so that the density always lies between its limits.
+fn print(xs: &[f64], densities: &[f64], cdfs: &[f64], band: Option<Band>) {
+    match band {
+        Some(band) => {
+            println!("x\tkde\tcdf\tlower\tupper");
+            for i in 0..xs.len() {
+                println!("{}\t{}\t{}\t{}\t{}",
+                         xs[i],
+                         band.estimate[i],
+                         cdfs[i],
+                         band.lower[i],
+                         band.upper[i]);
+            }
+        }
+        None => {
+            println!("x\tkde\tcdf");
+            for i in 0..xs.len() {
+                println!("{}\t{}\t{}", xs[i], densities[i], cdfs[i]);
+            }
+        }
+    }
+}
+
 /// Calculate Normal Kernel Density Estimation values.
 ///
 /// Input files must be single-column headerless data files.
@@ -24,40 +67,80 @@ fn parse_float(s: String) -> f64 {
 /// This will print the values of the Normal KDE for values between min and
 /// max using 0.01 as step size.
 ///
+/// ```bash
+/// cargo run --bin normal_kde <min> <max> <bandwidth> <file> <points>
+/// ```
+///
+/// With `<points>`, the values are instead approximated on an equally spaced
+/// grid of that many points between min and max using linear binning and the
+/// fast Fourier transform. This is much faster for large sample sets. See
+/// `kde::grid` for the approximation error.
+///
+/// Either form accepts `--pointwise <level>` or `--simultaneous <level>` to
+/// add `lower` and `upper` columns with a confidence band for the density at
+/// the given confidence level, for example 0.95. Pointwise bands are
+/// asymptotic and simultaneous bands use 1000 bootstrap replicates. See
+/// `kde::confidence`. Bands are evaluated exactly, also on a grid, and the
+/// `kde` column is then the exact density rather than the grid
+/// approximation.
+///
 /// `<bandwidth>` must be a floating point number strictly greater than zero.
 /// `<min>` and `<max>` must be floating point numbers with `<min>` less than
 /// `<max>`.
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    let mut confidence_band = None;
+    if let Some(i) = args.iter().position(|arg| arg == "--pointwise" || arg == "--simultaneous") {
+        confidence_band = Some((args[i] == "--simultaneous", parse_float(args[i + 1].clone())));
+        args.drain(i..(i + 2));
+    }
 
     let min: f64 = parse_float(args[1].clone());
     let max: f64 = parse_float(args[2].clone());
     let bandwidth: f64 = parse_float(args[3].clone());
 
     assert!(bandwidth > 0.0);
-    assert!(min <= max);
+    assert!(min < max);
 
     let path = Path::new(&args[4]);
-    let file = BufReader::new(File::open(&path).unwrap());
+    let file = BufReader::new(File::open(path).unwrap());
     let lines = file.lines().map(|line| line.unwrap());
 
     let xs: Vec<f64> = lines.map(parse_float).collect();
 
-    let kde = kde::normal(&xs, bandwidth);
+    if args.len() > 5 {
+        let points = args[5].parse::<usize>().expect("Not an integer.");
+
+        let kde = KernelDensityEstimation::new(&xs, bandwidth, NormalKernel);
+        let grid = grid::evaluate(&kde, min, max, points);
 
-    println!("x\tkde\tcdf");
-    println!("{}\t{}\t{}", min, kde.density(min), kde.cdf(min));
+        let band = band(&xs, bandwidth, &grid.xs, confidence_band);
+        print(&grid.xs, &grid.density, &grid.cdf, band);
+
+        return;
+    }
+
+    let samples = xs;
+    let kde = kde::normal(&samples, bandwidth);
 
     // Iterate using fixed point arithmetic over a 0.01 grid resolution.
+    let mut xs = vec![min];
     let mut x_fixed: i64 = (min * 100.0).floor() as i64 + 1;
     let mut x_f64: f64 = x_fixed as f64 / 100.0;
 
     while x_f64 < max {
-        println!("{}\t{}\t{}", x_f64, kde.density(x_f64), kde.cdf(x_f64));
+        xs.push(x_f64);
 
         x_fixed += 1;
         x_f64 = x_fixed as f64 / 100.0;
     }
 
-    println!("{}\t{}\t{}", max, kde.density(max), kde.cdf(max));
+    xs.push(max);
+
+    let densities = kde.densities(&xs);
+    let cdfs = kde.cdfs(&xs);
+
+    let band = band(&samples, bandwidth, &xs, confidence_band);
+    print(&xs, &densities, &cdfs, band);
 }
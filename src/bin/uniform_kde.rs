@@ -1,6 +1,7 @@
 extern crate kernel_density;
 
 use kernel_density::kde;
+use kernel_density::kde::{grid, KernelDensityEstimation, UniformKernel};
 
 use std::env;
 use std::io::{BufReader, BufRead};
@@ -24,6 +25,15 @@ fn parse_float(s: String) -> f64 {
 /// This will print the values of the Uniform KDE for values between min and
 /// max using 0.01 as step size.
 ///
+/// ```bash
+/// cargo run --bin uniform_kde <min> <max> <bandwidth> <file> <points>
+/// ```
+///
+/// With `<points>`, the values are instead approximated on an equally spaced
+/// grid of that many points between min and max using linear binning and the
+/// fast Fourier transform. This is much faster for large sample sets. See
+/// `kde::grid` for the approximation error.
+///
 /// `<bandwidth>` must be a floating point number strictly greater than zero.
 /// `<min>` and `<max>` must be floating point numbers with `<min>` less than
 /// `<max>`.
@@ -38,26 +48,46 @@ fn main() {
     assert!(min <= max);
 
     let path = Path::new(&args[4]);
-    let file = BufReader::new(File::open(&path).unwrap());
+    let file = BufReader::new(File::open(path).unwrap());
     let lines = file.lines().map(|line| line.unwrap());
 
     let xs: Vec<f64> = lines.map(parse_float).collect();
 
-    let kde = kde::uniform(&xs, bandwidth);
+    if args.len() > 5 {
+        let points = args[5].parse::<usize>().expect("Not an integer.");
 
-    println!("x\tkde\tcdf");
-    println!("{}\t{}\t{}", min, kde.density(min), kde.cdf(min));
+        let kde = KernelDensityEstimation::new(&xs, bandwidth, UniformKernel);
+        let grid = grid::evaluate(&kde, min, max, points);
+
+        println!("x\tkde\tcdf");
+        for i in 0..points {
+            println!("{}\t{}\t{}", grid.xs[i], grid.density[i], grid.cdf[i]);
+        }
+
+        return;
+    }
+
+    let kde = kde::uniform(&xs, bandwidth);
 
     // Iterate using fixed point arithmetic over a 0.01 grid resolution.
+    let mut xs = vec![min];
     let mut x_fixed: i64 = (min * 100.0).floor() as i64 + 1;
     let mut x_f64: f64 = x_fixed as f64 / 100.0;
 
     while x_f64 < max {
-        println!("{}\t{}\t{}", x_f64, kde.density(x_f64), kde.cdf(x_f64));
+        xs.push(x_f64);
 
         x_fixed += 1;
         x_f64 = x_fixed as f64 / 100.0;
     }
 
-    println!("{}\t{}\t{}", max, kde.density(max), kde.cdf(max));
+    xs.push(max);
+
+    let densities = kde.densities(&xs);
+    let cdfs = kde.cdfs(&xs);
+
+    println!("x\tkde\tcdf");
+    for i in 0..xs.len() {
+        println!("{}\t{}\t{}", xs[i], densities[i], cdfs[i]);
+    }
 }
@@ -345,6 +345,8 @@ pub fn percentile(samples: &[f64], percentile: f64) -> f64 {
 /// let rank = kernel_density::density::rank(&samples, 5);
 /// assert_eq!(rank, 4.0);
 /// ```
+// The second partition shadows `bottom`, which is not a swap.
+#[allow(clippy::almost_swapped)]
 pub fn rank(samples: &[f64], rank: usize) -> f64 {
     let length = samples.len();
     assert!(length > 0);
@@ -359,7 +361,7 @@ pub fn rank(samples: &[f64], rank: usize) -> f64 {
     loop {
         assert!(low < high);
 
-        let pivot = samples[low].clone();
+        let pivot = samples[low];
 
         if low >= high - 1 {
             return pivot;
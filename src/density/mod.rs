@@ -1,8 +1,266 @@
 //! Density function definitions and examples.
 
+extern crate rand;
+
+use self::rand::Rng;
+use std::f64;
+
 pub trait Density {
     fn cdf(&self, x: f64) -> f64;
     fn density(&self, x: f64) -> f64;
+
+    /// Calculate the natural logarithm of the density function for a given
+    /// value.
+    ///
+    /// The default implementation takes the logarithm of the density, which
+    /// is negative infinity where the density underflows. Implementations
+    /// should override this where the logarithm can be computed directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// let normal = kernel_density::density::normal(0.0, 1.0);
+    ///
+    /// assert_eq!(normal.log_density(0.0), normal.density(0.0).ln());
+    /// ```
+    fn log_density(&self, x: f64) -> f64 {
+        self.density(x).ln()
+    }
+
+    /// Calculate the cumulative density function for a slice of values.
+    ///
+    /// Implementations may evaluate nondecreasing values faster than
+    /// arbitrary values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// let density = kernel_density::density::normal(0.0, 1.0);
+    /// let cdfs = density.cdfs(&[-1.0, 0.0, 1.0]);
+    ///
+    /// assert_eq!(cdfs[1], 0.5);
+    /// ```
+    fn cdfs(&self, xs: &[f64]) -> Vec<f64> {
+        xs.iter().map(|&x| self.cdf(x)).collect()
+    }
+
+    /// Calculate the density function for a slice of values.
+    ///
+    /// Implementations may evaluate nondecreasing values faster than
+    /// arbitrary values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density;
+    ///
+    /// let normal = density::normal(0.0, 1.0);
+    /// let xs = density::linspace(-4.0, 4.0, 801);
+    /// let densities = normal.densities(&xs);
+    ///
+    /// assert_eq!(densities.len(), 801);
+    /// ```
+    fn densities(&self, xs: &[f64]) -> Vec<f64> {
+        xs.iter().map(|&x| self.density(x)).collect()
+    }
+
+    /// Calculate the quantile function, the inverse of the cumulative density
+    /// function, for a given probability. This is the smallest x with
+    /// cdf(x) >= p.
+    ///
+    /// The default implementation brackets the quantile by doubling an
+    /// interval about zero and then inverts the cumulative density function
+    /// with `invert_cdf`.
+    ///
+    /// # Panics
+    ///
+    /// The probability must satisfy 0 < p < 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// let normal = kernel_density::density::normal(0.0, 1.0);
+    ///
+    /// assert_eq!(normal.quantile(0.5), 0.0);
+    /// ```
+    fn quantile(&self, p: f64) -> f64 {
+        assert!(0.0 < p && p < 1.0);
+
+        let mut lower = -1.0;
+        while self.cdf(lower) >= p && lower > f64::MIN / 2.0 {
+            lower *= 2.0;
+        }
+
+        let mut upper = 1.0;
+        while self.cdf(upper) < p && upper < f64::MAX / 2.0 {
+            upper *= 2.0;
+        }
+
+        invert_cdf(self, p, lower, upper)
+    }
+
+    /// Draw a random value from this density using a given random number
+    /// generator. Seeded generators give reproducible draws.
+    ///
+    /// The default implementation inverts the cumulative density function
+    /// using `quantile` on a Uniform(0, 1) value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    /// extern crate rand;
+    ///
+    /// use rand::{SeedableRng, StdRng};
+    ///
+    /// let normal = kernel_density::density::normal(0.0, 1.0);
+    ///
+    /// let seed: &[_] = &[1, 2, 3, 4];
+    /// let mut rng: StdRng = SeedableRng::from_seed(seed);
+    /// let x = normal.sample(&mut rng);
+    /// ```
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let mut u = rng.next_f64();
+        while u == 0.0 {
+            u = rng.next_f64();
+        }
+
+        self.quantile(u)
+    }
+}
+
+/// A density function over d-dimensional points.
+pub trait MultivariateDensity {
+    /// Return the dimension d of the points.
+    fn dimension(&self) -> usize;
+
+    /// Calculate the value of the density function at a given point.
+    fn density(&self, x: &[f64]) -> f64;
+}
+
+/// Maximum number of iterations when inverting a cumulative density function.
+/// Bisection alone reaches adjacent floating point numbers from any finite
+/// bracket well within this.
+const MAX_INVERSION_ITERATIONS: usize = 2200;
+
+/// Find the smallest x in a bracket [lower, upper] with cdf(x) >= p.
+///
+/// The bracket must satisfy cdf(lower) < p <= cdf(upper). Newton's method
+/// using the density is safeguarded by bisection, so that every iteration
+/// shrinks the bracket, and iteration stops when the bracket is a few
+/// floating point numbers wide. Flat regions of the cumulative density
+/// function, such as gaps between compactly supported kernels, converge to
+/// their left end.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::density;
+///
+/// let normal = density::normal(0.0, 1.0);
+/// let x = density::invert_cdf(&*normal, 0.975, -10.0, 10.0);
+///
+/// assert!((x - 1.959963984540054).abs() < 1e-12);
+/// ```
+pub fn invert_cdf<D: Density + ?Sized>(density: &D, p: f64, lower: f64, upper: f64) -> f64 {
+    let mut lower = lower;
+    let mut upper = upper;
+    let mut x = 0.5 * lower + 0.5 * upper;
+
+    for _ in 0..MAX_INVERSION_ITERATIONS {
+        let tolerance = f64::EPSILON * (lower.abs() + upper.abs());
+        if upper - lower <= tolerance {
+            break;
+        }
+
+        let residual = density.cdf(x) - p;
+        if residual < 0.0 {
+            lower = x;
+        } else {
+            upper = x;
+        }
+
+        let mut next = x - residual / density.density(x);
+
+        if !(lower < next && next < upper) {
+            next = 0.5 * lower + 0.5 * upper;
+        } else if (next - x).abs() < tolerance {
+            // Step past the root so the far side of the bracket moves too.
+            next = if residual < 0.0 { x + tolerance } else { x - tolerance };
+            next = next.max(lower).min(upper);
+        }
+
+        if next == lower || next == upper {
+            next = 0.5 * lower + 0.5 * upper;
+            if next == lower || next == upper {
+                break;
+            }
+        }
+
+        x = next;
+    }
+
+    upper
+}
+
+/// Calculate the log-likelihood of a sample under a density, the sum of the
+/// log densities of the sample values.
+///
+/// The log-likelihood is negative infinity if any value has zero density,
+/// for example outside the support of a compact kernel density estimation.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::density;
+///
+/// let normal = density::normal(0.0, 1.0);
+/// let samples = vec!(-1.0, 0.0, 1.0);
+///
+/// let expected = -1.5 * (2.0 * std::f64::consts::PI).ln() - 1.0;
+/// assert!((density::log_likelihood(&*normal, &samples) - expected).abs() < 1e-12);
+/// ```
+pub fn log_likelihood<D: Density + ?Sized>(density: &D, samples: &[f64]) -> f64 {
+    samples.iter().map(|&x| density.log_density(x)).sum()
+}
+
+/// Construct an equally spaced grid of points from min to max inclusive.
+///
+/// # Panics
+///
+/// There must be at least two points and min must be less than max.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let xs = kernel_density::density::linspace(0.0, 1.0, 5);
+///
+/// assert_eq!(xs, vec!(0.0, 0.25, 0.5, 0.75, 1.0));
+/// ```
+pub fn linspace(min: f64, max: f64, points: usize) -> Vec<f64> {
+    assert!(points > 1);
+    assert!(min < max);
+
+    let step = (max - min) / (points - 1) as f64;
+
+    let mut xs: Vec<f64> = (0..points).map(|i| min + i as f64 * step).collect();
+    xs[points - 1] = max;
+
+    xs
 }
 
 mod ecdf;
@@ -25,11 +283,11 @@ mod normal;
 /// let variance = 1.0;
 /// kernel_density::density::normal(mean, variance);
 /// ```
-pub fn normal(mean: f64, variance: f64) -> Box<Density> {
+pub fn normal(mean: f64, variance: f64) -> Box<dyn Density> {
     assert!(variance > 0.0);
 
     Box::new(normal::NormalDensity {
-        mean: mean,
-        variance: variance,
+        mean,
+        variance,
     })
 }
@@ -1,8 +1,11 @@
 //! Normal kernel density estimation functions.
 
+extern crate rand;
 extern crate special_fun;
 
 use density::Density;
+use self::rand::{Rand, Rng};
+use self::rand::distributions::normal::StandardNormal;
 use self::special_fun::FloatSpecial;
 use std::f64::consts::PI;
 
@@ -32,6 +35,23 @@ impl Density for NormalDensity {
         coefficient * exponent.exp()
     }
 
+    /// Calculate the natural logarithm of the normal density function for a
+    /// given value, without underflow far from the mean.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// let normal = kernel_density::density::normal(0.0, 1.0);
+    ///
+    /// assert_eq!(normal.density(40.0), 0.0);
+    /// assert!((normal.log_density(40.0) + 800.9189385332047).abs() < 1e-9);
+    /// ```
+    fn log_density(&self, x: f64) -> f64 {
+        -0.5 * (2.0 * PI * self.variance).ln() - (x - self.mean).powi(2) / (2.0 * self.variance)
+    }
+
     /// Calculate a value of the cumulative density function for this normal
     /// density.
     ///
@@ -50,4 +70,51 @@ impl Density for NormalDensity {
         let z: f64 = (x - self.mean) / self.variance.sqrt();
         z.norm()
     }
+
+    /// Calculate the quantile function for this normal density using the
+    /// probit function.
+    ///
+    /// # Panics
+    ///
+    /// The probability must satisfy 0 < p < 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// let mean = 0.0;
+    /// let variance = 1.0;
+    /// let normal = kernel_density::density::normal(mean, variance);
+    ///
+    /// assert_eq!(normal.quantile(0.5), 0.0);
+    /// ```
+    fn quantile(&self, p: f64) -> f64 {
+        assert!(0.0 < p && p < 1.0);
+
+        self.mean + self.variance.sqrt() * p.norm_inv()
+    }
+
+    /// Draw a random value from this normal density.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    /// extern crate rand;
+    ///
+    /// use rand::{SeedableRng, StdRng};
+    ///
+    /// let mean = 0.0;
+    /// let variance = 1.0;
+    /// let normal = kernel_density::density::normal(mean, variance);
+    ///
+    /// let seed: &[_] = &[1, 2, 3, 4];
+    /// let mut rng: StdRng = SeedableRng::from_seed(seed);
+    /// let x = normal.sample(&mut rng);
+    /// ```
+    fn sample(&self, mut rng: &mut dyn Rng) -> f64 {
+        let StandardNormal(z) = StandardNormal::rand(&mut rng);
+        self.mean + self.variance.sqrt() * z
+    }
 }
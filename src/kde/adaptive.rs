@@ -0,0 +1,161 @@
+//! Adaptive kernel density estimation for an arbitrary kernel.
+
+extern crate rand;
+
+use density::Density;
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+use self::rand::Rng;
+
+/// Kernel density estimation with a local bandwidth for each sample.
+///
+/// Local bandwidths follow Abramson's square-root law. A pilot fixed
+/// bandwidth kernel density estimation f is evaluated at each sample and the
+/// sample bandwidth is set to:
+///
+/// h_i = h * (f(x_i) / g)^(-sensitivity)
+///
+/// Where h is the pilot bandwidth and g is the geometric mean of the pilot
+/// densities f(x_i). Samples in sparse regions such as long tails get wider
+/// kernels than samples in dense regions. A sensitivity of 0.5 is Abramson's
+/// square-root law and a sensitivity of 0 is the fixed bandwidth estimation.
+pub struct AdaptiveKernelDensityEstimation<K: Kernel> {
+    samples: Vec<f64>,
+    bandwidths: Vec<f64>,
+    kernel: K,
+}
+
+impl<K: Kernel> AdaptiveKernelDensityEstimation<K> {
+    /// Construct a new adaptive kernel density estimation for a given sample,
+    /// pilot bandwidth, sensitivity and kernel.
+    ///
+    /// # Panics
+    ///
+    /// Bandwidth must be greater than zero, sensitivity must be in [0, 1]
+    /// and the sample set must be non-empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::{AdaptiveKernelDensityEstimation, NormalKernel};
+    ///
+    /// let samples = vec!(0.0, 0.1, 0.2, 0.3, 0.4, 1.0, 5.0, 20.0);
+    /// let bandwidth = 0.5;
+    /// let kde = AdaptiveKernelDensityEstimation::new(&samples, bandwidth, 0.5, NormalKernel);
+    ///
+    /// assert!(kde.bandwidths()[7] > kde.bandwidths()[0]);
+    /// ```
+    pub fn new(samples: &[f64],
+               bandwidth: f64,
+               sensitivity: f64,
+               kernel: K)
+               -> AdaptiveKernelDensityEstimation<K> {
+        assert!((0.0..=1.0).contains(&sensitivity));
+
+        let pilot = KernelDensityEstimation::new(samples, bandwidth, kernel);
+        // Each sample contributes k(0) to the pilot density at its own
+        // location, but the density can still underflow to zero, for example
+        // for a very large pilot bandwidth. Pilot densities are floored at the
+        // smallest positive value so their logarithms are finite.
+        let pilot_densities: Vec<f64> = samples.iter()
+            .map(|&x| pilot.density(x).max(f64::MIN_POSITIVE))
+            .collect();
+
+        // Geometric mean of the pilot densities, computed in log space.
+        let n = samples.len() as f64;
+        let log_geometric_mean = pilot_densities.iter().map(|f| f.ln()).sum::<f64>() / n;
+
+        let bandwidths = pilot_densities.iter()
+            .map(|f| bandwidth * (-sensitivity * (f.ln() - log_geometric_mean)).exp())
+            .collect();
+
+        AdaptiveKernelDensityEstimation {
+            samples: samples.to_vec(),
+            bandwidths,
+            kernel: pilot.into_kernel(),
+        }
+    }
+
+    /// Return the samples, in the order given.
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+
+    /// Return the local bandwidth of each sample, in the same order as
+    /// `samples`.
+    pub fn bandwidths(&self) -> &[f64] {
+        &self.bandwidths
+    }
+
+    /// Return the kernel of the estimation.
+    pub fn kernel(&self) -> &K {
+        &self.kernel
+    }
+}
+
+impl<K: Kernel> Density for AdaptiveKernelDensityEstimation<K> {
+    /// Calculate a value of the adaptive kernel density function for a given
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{AdaptiveKernelDensityEstimation, UniformKernel};
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let bandwidth = 0.1;
+    /// let kde = AdaptiveKernelDensityEstimation::new(&samples, bandwidth, 0.5, UniformKernel);
+    ///
+    /// assert_eq!(kde.density(4.0), 0.5);
+    /// ```
+    fn density(&self, x: f64) -> f64 {
+        let mut sum = 0.0;
+        for (sample, bandwidth) in self.samples.iter().zip(&self.bandwidths) {
+            sum += self.kernel.density((x - sample) / bandwidth) / bandwidth;
+        }
+
+        sum / self.samples.len() as f64
+    }
+
+    /// Calculate a value of the cumulative density function for this
+    /// adaptive kernel density estimation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{AdaptiveKernelDensityEstimation, UniformKernel};
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let bandwidth = 0.1;
+    /// let kde = AdaptiveKernelDensityEstimation::new(&samples, bandwidth, 0.5, UniformKernel);
+    ///
+    /// assert_eq!(kde.cdf(0.1), 0.1);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        let mut sum = 0.0;
+        for (sample, bandwidth) in self.samples.iter().zip(&self.bandwidths) {
+            sum += self.kernel.cdf((x - sample) / bandwidth);
+        }
+
+        sum / self.samples.len() as f64
+    }
+
+    /// Draw a random value from this adaptive kernel density estimation.
+    ///
+    /// A sample is chosen uniformly at random and kernel noise scaled by its
+    /// local bandwidth is added to it.
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let length = self.samples.len();
+        let index = ((rng.next_f64() * length as f64) as usize).min(length - 1);
+
+        self.samples[index] + self.bandwidths[index] * self.kernel.sample(rng)
+    }
+}
@@ -0,0 +1,727 @@
+//! Bandwidth selection rules.
+//!
+//! The rules of thumb here are normal reference rules. They estimate the
+//! bandwidth minimising the asymptotic mean integrated squared error of a
+//! Normal kernel density estimation under the assumption that the samples
+//! come from a normal distribution. The Sheather-Jones plug-in bandwidth
+//! instead estimates the unknown density curvature from the sample itself.
+//!
+//! Bandwidths for other kernels are obtained by rescaling with the ratio of
+//! canonical bandwidths. Kernels with equal canonical bandwidth produce
+//! estimations of the same smoothness, so the rescaled bandwidth is the
+//! equivalent optimal choice for the requested kernel.
+
+use kde::{BandwidthMatrix, Kernel, NormalKernel};
+use std::f64::consts::PI;
+use std::f64;
+
+/// Rescale a Normal kernel bandwidth to the equivalent bandwidth for another
+/// kernel.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::{EpanechnikovKernel, NormalKernel};
+/// use kernel_density::kde::bandwidth::rescale;
+///
+/// assert_eq!(rescale(0.5, &NormalKernel), 0.5);
+/// assert!(rescale(0.5, &EpanechnikovKernel) > 1.0);
+/// ```
+pub fn rescale<K: Kernel>(bandwidth: f64, kernel: &K) -> f64 {
+    assert!(bandwidth > 0.0);
+
+    let normal = NormalKernel.canonical_bandwidth();
+    let canonical = kernel.canonical_bandwidth();
+
+    if canonical == normal {
+        return bandwidth;
+    }
+
+    bandwidth * canonical / normal
+}
+
+/// Calculate Silverman's rule of thumb bandwidth for a Normal kernel density
+/// estimation of the given sample.
+///
+/// h = 0.9 * min(sd, IQR / 1.34) * n^(-1/5)
+///
+/// Where the spread of the sample is zero, the standard deviation, the
+/// magnitude of the first sample, and finally 1 are used instead. This
+/// matches the `bw.nrd0` function in R.
+///
+/// # Panics
+///
+/// The sample set must be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = kernel_density::kde::bandwidth::silverman(&samples);
+///
+/// assert!((bandwidth - 1.719286404692283).abs() < 1e-12);
+/// ```
+pub fn silverman(samples: &[f64]) -> f64 {
+    let length = samples.len();
+    assert!(length > 0);
+
+    0.9 * spread(samples) * (length as f64).powf(-0.2)
+}
+
+/// Calculate Scott's rule of thumb bandwidth for a Normal kernel density
+/// estimation of the given sample.
+///
+/// h = 1.06 * min(sd, IQR / 1.34) * n^(-1/5)
+///
+/// Where the spread of the sample is zero, the standard deviation, the
+/// magnitude of the first sample, and finally 1 are used instead. This
+/// matches the `bw.nrd` function in R.
+///
+/// # Panics
+///
+/// The sample set must be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = kernel_density::kde::bandwidth::scott(&samples);
+///
+/// assert!((bandwidth - 2.0249373210820227).abs() < 1e-12);
+/// ```
+pub fn scott(samples: &[f64]) -> f64 {
+    let length = samples.len();
+    assert!(length > 0);
+
+    1.06 * spread(samples) * (length as f64).powf(-0.2)
+}
+
+/// Calculate the effective sample size of a weighted sample.
+///
+/// n_eff = (sum w)^2 / sum(w^2)
+///
+/// This is the number of equally weighted samples carrying the same
+/// information as the weighted sample, and is the sample size used by the
+/// weighted bandwidth rules.
+///
+/// # Panics
+///
+/// The weights must be non-empty and have a positive total.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let weights = vec!(1.0, 1.0, 1.0, 1.0);
+/// let size = kernel_density::kde::bandwidth::effective_sample_size(&weights);
+///
+/// assert_eq!(size, 4.0);
+/// ```
+pub fn effective_sample_size(weights: &[f64]) -> f64 {
+    let length = weights.len();
+    assert!(length > 0);
+
+    let total_weight: f64 = weights.iter().sum();
+    assert!(total_weight > 0.0);
+
+    let sum_squares: f64 = weights.iter().map(|w| w * w).sum();
+
+    total_weight * total_weight / sum_squares
+}
+
+/// Calculate Silverman's rule of thumb bandwidth for a Normal kernel density
+/// estimation of the given weighted sample.
+///
+/// h = 0.9 * min(sd, IQR / 1.34) * n_eff^(-1/5)
+///
+/// Where sd and IQR are the weighted standard deviation and interquartile
+/// range, and n_eff is the effective sample size. The weighted quartiles
+/// interpolate linearly between samples placed at the midpoints of their
+/// cumulative weights, so for equal weights this differs slightly from
+/// `silverman`.
+///
+/// The weights are importance or reliability weights, so only their
+/// relative sizes matter. For frequency weights counting repeated values,
+/// use `frequency_silverman`.
+///
+/// # Panics
+///
+/// The sample set must be non-empty and there must be one weight for each
+/// sample. Weights must be finite, non-negative and have a positive total.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 1.0, 2.0, 3.0);
+/// let weights = vec!(0.1, 0.3, 0.3, 0.1);
+/// let bandwidth = kernel_density::kde::bandwidth::weighted_silverman(&samples, &weights);
+/// ```
+pub fn weighted_silverman(samples: &[f64], weights: &[f64]) -> f64 {
+    let size = effective_sample_size(weights);
+    0.9 * weighted_spread(samples, weights, size, weighted_quantile) * size.powf(-0.2)
+}
+
+/// Calculate Scott's rule of thumb bandwidth for a Normal kernel density
+/// estimation of the given weighted sample.
+///
+/// h = 1.06 * min(sd, IQR / 1.34) * n_eff^(-1/5)
+///
+/// Where sd and IQR are the weighted standard deviation and interquartile
+/// range, and n_eff is the effective sample size.
+///
+/// The weights are importance or reliability weights, so only their
+/// relative sizes matter. For frequency weights counting repeated values,
+/// use `frequency_scott`.
+///
+/// # Panics
+///
+/// The sample set must be non-empty and there must be one weight for each
+/// sample. Weights must be finite, non-negative and have a positive total.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 1.0, 2.0, 3.0);
+/// let weights = vec!(0.1, 0.3, 0.3, 0.1);
+/// let bandwidth = kernel_density::kde::bandwidth::weighted_scott(&samples, &weights);
+/// ```
+pub fn weighted_scott(samples: &[f64], weights: &[f64]) -> f64 {
+    let size = effective_sample_size(weights);
+    1.06 * weighted_spread(samples, weights, size, weighted_quantile) * size.powf(-0.2)
+}
+
+/// Calculate Silverman's rule of thumb bandwidth for a Normal kernel density
+/// estimation of a sample of distinct values with frequency weights, such as
+/// (value, count) pairs.
+///
+/// h = 0.9 * min(sd, IQR / 1.34) * n^(-1/5)
+///
+/// Where sd and IQR are the weighted standard deviation and interquartile
+/// range, and the sample size n is the total of the counts. For integer
+/// counts this is `silverman` of the sample with each value repeated count
+/// times, but without expanding the sample.
+///
+/// # Panics
+///
+/// The sample set must be non-empty and there must be one count for each
+/// sample. Counts must be finite, non-negative and have a positive total.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 1.0, 2.0, 3.0);
+/// let counts = vec!(10.0, 30.0, 30.0, 10.0);
+/// let bandwidth = kernel_density::kde::bandwidth::frequency_silverman(&samples, &counts);
+/// ```
+pub fn frequency_silverman(samples: &[f64], counts: &[f64]) -> f64 {
+    let size: f64 = counts.iter().sum();
+    0.9 * weighted_spread(samples, counts, size, frequency_quantile) * size.powf(-0.2)
+}
+
+/// Calculate Scott's rule of thumb bandwidth for a Normal kernel density
+/// estimation of a sample of distinct values with frequency weights, such as
+/// (value, count) pairs.
+///
+/// h = 1.06 * min(sd, IQR / 1.34) * n^(-1/5)
+///
+/// Where sd and IQR are the weighted standard deviation and interquartile
+/// range, and the sample size n is the total of the counts. For integer
+/// counts this is `scott` of the sample with each value repeated count
+/// times.
+///
+/// # Panics
+///
+/// The sample set must be non-empty and there must be one count for each
+/// sample. Counts must be finite, non-negative and have a positive total.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 1.0, 2.0, 3.0);
+/// let counts = vec!(10.0, 30.0, 30.0, 10.0);
+/// let bandwidth = kernel_density::kde::bandwidth::frequency_scott(&samples, &counts);
+/// ```
+pub fn frequency_scott(samples: &[f64], counts: &[f64]) -> f64 {
+    let size: f64 = counts.iter().sum();
+    1.06 * weighted_spread(samples, counts, size, frequency_quantile) * size.powf(-0.2)
+}
+
+/// Calculate Scott's rule of thumb bandwidth matrix for a Normal product
+/// kernel density estimation of the given sample of d-dimensional points.
+///
+/// H = n^(-2/(d+4)) * S
+///
+/// Where S is the sample covariance matrix.
+///
+/// # Panics
+///
+/// The sample set must have more than one element and every sample must have
+/// the same non-zero dimension.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(vec!(0.0, 1.0), vec!(1.0, 3.0), vec!(2.0, 2.0), vec!(3.0, 5.0));
+/// let bandwidth = kernel_density::kde::bandwidth::multivariate_scott(&samples);
+/// ```
+pub fn multivariate_scott(samples: &[Vec<f64>]) -> BandwidthMatrix {
+    multivariate_rule(samples, 1.0)
+}
+
+/// Calculate Silverman's rule of thumb bandwidth matrix for a Normal product
+/// kernel density estimation of the given sample of d-dimensional points.
+///
+/// H = (4 / (d + 2))^(2/(d+4)) * n^(-2/(d+4)) * S
+///
+/// Where S is the sample covariance matrix. For d = 2 this is identical to
+/// Scott's rule.
+///
+/// # Panics
+///
+/// The sample set must have more than one element and every sample must have
+/// the same non-zero dimension.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(vec!(0.0, 1.0), vec!(1.0, 3.0), vec!(2.0, 2.0), vec!(3.0, 5.0));
+/// let bandwidth = kernel_density::kde::bandwidth::multivariate_silverman(&samples);
+/// ```
+pub fn multivariate_silverman(samples: &[Vec<f64>]) -> BandwidthMatrix {
+    let length = samples.len();
+    assert!(length > 1);
+
+    let d = samples[0].len() as f64;
+    multivariate_rule(samples, (4.0 / (d + 2.0)).powf(2.0 / (d + 4.0)))
+}
+
+/// Calculate Scott's rule of thumb bandwidths for a Normal conditional kernel
+/// density estimation of responses given conditions.
+///
+/// h_x = min(sd_x, IQR_x / 1.34) * n^(-1/6)
+///
+/// h_y = min(sd_y, IQR_y / 1.34) * n^(-1/6)
+///
+/// These are the diagonal of Scott's rule for the bivariate joint density of
+/// the (condition, response) pairs, with the same robust spread and
+/// fallbacks as `scott`. The result is the pair (condition bandwidth,
+/// response bandwidth).
+///
+/// # Panics
+///
+/// The sample set must be non-empty and there must be one response for each
+/// condition.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let conditions = vec!(0.0, 1.0, 2.0, 3.0, 4.0);
+/// let responses = vec!(0.5, 1.5, 1.0, 3.5, 4.0);
+/// let (condition_bandwidth, response_bandwidth) =
+///     kernel_density::kde::bandwidth::conditional_scott(&conditions, &responses);
+/// ```
+pub fn conditional_scott(conditions: &[f64], responses: &[f64]) -> (f64, f64) {
+    let length = conditions.len();
+    assert!(length > 0);
+    assert!(responses.len() == length);
+
+    let scale = (length as f64).powf(-1.0 / 6.0);
+    (spread(conditions) * scale, spread(responses) * scale)
+}
+
+/// Calculate the Sheather-Jones solve-the-equation plug-in bandwidth for a
+/// Normal kernel density estimation of the given sample.
+///
+/// The bandwidth h solves
+///
+/// h = (R(k) / (n * S(a(h))))^(1/5)
+///
+/// Where S(g) is the Normal kernel estimate with bandwidth g of the integral
+/// of the squared second derivative of the density, and the pilot bandwidth
+/// a(h) = 1.357 * (S(a) / -T(b))^(1/7) * h^(5/7) is derived from normal
+/// reference pilot estimates of S and of T, the corresponding functional of
+/// the third derivative.
+///
+/// This is the solve-the-equation rule of Sheather and Jones (1991) with the
+/// pilot constants of their paper. Pairwise sample differences are binned
+/// into 1000 bins of equal width from the sample minimum to make each
+/// functional estimate O(1). The binning perturbs the bandwidth slightly from
+/// the solution using exact pairwise differences, and the result is not
+/// expected to reproduce the `bw.SJ` function in R to full precision.
+///
+/// # Panics
+///
+/// The sample set must have more than one element.
+///
+/// There is a sparse sample panic if the pilot estimates of the density
+/// functionals are degenerate, for example if there are too few distinct
+/// sample values.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = kernel_density::kde::bandwidth::sheather_jones(&samples);
+/// let kde = kernel_density::kde::normal(&samples, bandwidth);
+/// ```
+pub fn sheather_jones(samples: &[f64]) -> f64 {
+    let length = samples.len();
+    assert!(length > 1);
+
+    let n = length as f64;
+    let differences = BinnedDifferences::new(samples);
+
+    let scale = spread_with(samples, 1.349);
+    let a = 1.24 * scale * n.powf(-1.0 / 7.0);
+    let b = 1.23 * scale * n.powf(-1.0 / 9.0);
+    let c1 = 1.0 / (2.0 * PI.sqrt() * n);
+
+    let td = -differences.phi6(b);
+    if !td.is_finite() || td <= 0.0 {
+        panic!("Sample is too sparse to estimate the sixth derivative functional.");
+    }
+
+    let alpha2 = 1.357 * (differences.phi4(a) / td).powf(1.0 / 7.0);
+    if !alpha2.is_finite() {
+        panic!("Sample is too sparse to estimate the pilot bandwidth.");
+    }
+
+    let equation = |h: f64| (c1 / differences.phi4(alpha2 * h.powf(5.0 / 7.0))).powf(0.2) - h;
+
+    // Widen the initial bracket around the normal reference bandwidth until
+    // the equation changes sign.
+    let reference = 1.144 * scale * n.powf(-0.2);
+    let mut low = 0.1 * reference;
+    let mut high = reference;
+
+    let mut iteration = 1;
+    while equation(low) * equation(high) > 0.0 {
+        if iteration > 99 {
+            panic!("No solution for sheather_jones within [{}, {}].", low, high);
+        }
+
+        if iteration % 2 == 1 {
+            high *= 1.2;
+        } else {
+            low /= 1.2;
+        }
+        iteration += 1;
+    }
+
+    // Bisect to locate the root.
+    let mut equation_low = equation(low);
+    while high - low > 1e-10 * high {
+        let mid = low + (high - low) / 2.0;
+        let equation_mid = equation(mid);
+
+        if equation_low * equation_mid <= 0.0 {
+            high = mid;
+        } else {
+            low = mid;
+            equation_low = equation_mid;
+        }
+    }
+
+    low + (high - low) / 2.0
+}
+
+/// Number of bins for pairwise sample differences in `sheather_jones`.
+const DIFFERENCE_BINS: usize = 1000;
+
+/// Histogram of absolute pairwise sample differences.
+struct BinnedDifferences {
+    length: usize,
+    width: f64,
+    counts: Vec<f64>,
+}
+
+impl BinnedDifferences {
+    fn new(samples: &[f64]) -> BinnedDifferences {
+        let mut min = f64::MAX;
+        let mut max = f64::MIN;
+        for &x in samples {
+            min = min.min(x);
+            max = max.max(x);
+        }
+
+        let mut width = 1.01 * (max - min) / DIFFERENCE_BINS as f64;
+        if width == 0.0 {
+            width = 1.0;
+        }
+
+        let bins: Vec<usize> = samples.iter().map(|x| ((x - min) / width) as usize).collect();
+
+        let mut counts = vec![0.0; DIFFERENCE_BINS];
+        for i in 1..bins.len() {
+            for j in 0..i {
+                counts[bins[i].abs_diff(bins[j])] += 1.0;
+            }
+        }
+
+        BinnedDifferences {
+            length: samples.len(),
+            width,
+            counts,
+        }
+    }
+
+    /// Estimate the integral of the squared second density derivative using
+    /// a Normal kernel with bandwidth h.
+    fn phi4(&self, h: f64) -> f64 {
+        let n = self.length as f64;
+
+        let mut sum = 0.0;
+        for (i, count) in self.counts.iter().enumerate() {
+            let delta = (i as f64 * self.width / h).powi(2);
+            if delta >= 1000.0 {
+                break;
+            }
+            sum += count * (-0.5 * delta).exp() * (delta * delta - 6.0 * delta + 3.0);
+        }
+
+        // Diagonal terms contribute 3 each.
+        sum = 2.0 * sum + 3.0 * n;
+
+        sum / (n * (n - 1.0) * h.powi(5) * (2.0 * PI).sqrt())
+    }
+
+    /// Estimate the negated integral of the squared third density derivative
+    /// using a Normal kernel with bandwidth h.
+    fn phi6(&self, h: f64) -> f64 {
+        let n = self.length as f64;
+
+        let mut sum = 0.0;
+        for (i, count) in self.counts.iter().enumerate() {
+            let delta = (i as f64 * self.width / h).powi(2);
+            if delta >= 1000.0 {
+                break;
+            }
+            sum += count * (-0.5 * delta).exp() *
+                   (delta.powi(3) - 15.0 * delta * delta + 45.0 * delta - 15.0);
+        }
+
+        // Diagonal terms contribute -15 each.
+        sum = 2.0 * sum - 15.0 * n;
+
+        sum / (n * (n - 1.0) * h.powi(7) * (2.0 * PI).sqrt())
+    }
+}
+
+/// Robust estimate of the sample scale, min(sd, IQR / 1.34), with fallbacks
+/// for degenerate samples.
+fn spread(samples: &[f64]) -> f64 {
+    spread_with(samples, 1.34)
+}
+
+/// Robust estimate of the sample scale, min(sd, IQR / iqr_ratio), with
+/// fallbacks for degenerate samples.
+fn spread_with(samples: &[f64], iqr_ratio: f64) -> f64 {
+    let sd = standard_deviation(samples);
+
+    let mut spread = sd.min(interquartile_range(samples) / iqr_ratio);
+
+    if spread == 0.0 {
+        spread = sd;
+    }
+    if spread == 0.0 {
+        spread = samples[0].abs();
+    }
+    if spread == 0.0 {
+        spread = 1.0;
+    }
+
+    spread
+}
+
+/// Sample standard deviation. Zero for a single sample.
+fn standard_deviation(samples: &[f64]) -> f64 {
+    let length = samples.len();
+    if length < 2 {
+        return 0.0;
+    }
+
+    let mean = samples.iter().sum::<f64>() / length as f64;
+    let sum_squares: f64 = samples.iter().map(|x| (x - mean).powi(2)).sum();
+
+    (sum_squares / (length - 1) as f64).sqrt()
+}
+
+/// Sample interquartile range using linear interpolation between order
+/// statistics.
+fn interquartile_range(samples: &[f64]) -> f64 {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|x_1, x_2| x_1.partial_cmp(x_2).unwrap());
+
+    quantile(&sorted, 0.75) - quantile(&sorted, 0.25)
+}
+
+/// Quantile of a sorted sample using linear interpolation between order
+/// statistics, type 7 in the Hyndman and Fan taxonomy.
+fn quantile(sorted: &[f64], p: f64) -> f64 {
+    let index = p * (sorted.len() - 1) as f64;
+    let low = index.floor() as usize;
+    let high = index.ceil() as usize;
+
+    sorted[low] + (index - low as f64) * (sorted[high] - sorted[low])
+}
+
+/// Weighted robust estimate of the sample scale, min(sd, IQR / 1.34), with
+/// the same fallbacks for degenerate samples as `spread`. The sample size
+/// is used in the unbiased variance correction.
+fn weighted_spread(samples: &[f64],
+                   weights: &[f64],
+                   size: f64,
+                   quantile: fn(&[(f64, f64)], f64, f64) -> f64)
+                   -> f64 {
+    let length = samples.len();
+    assert!(length > 0);
+    assert!(weights.len() == length);
+    assert!(weights.iter().all(|&weight| weight >= 0.0 && weight.is_finite()));
+
+    let total_weight: f64 = weights.iter().sum();
+    assert!(total_weight > 0.0);
+
+    // Weighted variance with the given sample size in place of n in the
+    // unbiased correction.
+    let mean = samples.iter().zip(weights).map(|(x, w)| w * x).sum::<f64>() / total_weight;
+    let variance = samples.iter()
+        .zip(weights)
+        .map(|(x, w)| w * (x - mean).powi(2))
+        .sum::<f64>() / total_weight;
+
+    let sd = if size > 1.0 {
+        (variance * size / (size - 1.0)).sqrt()
+    } else {
+        0.0
+    };
+
+    let mut sorted: Vec<(f64, f64)> = samples.iter()
+        .cloned()
+        .zip(weights.iter().cloned())
+        .filter(|&(_, w)| w > 0.0)
+        .collect();
+    sorted.sort_by(|x_1, x_2| x_1.0.partial_cmp(&x_2.0).unwrap());
+
+    let iqr = quantile(&sorted, total_weight, 0.75) - quantile(&sorted, total_weight, 0.25);
+
+    let mut spread = sd.min(iqr / 1.34);
+
+    if spread == 0.0 {
+        spread = sd;
+    }
+    if spread == 0.0 {
+        spread = sorted[0].0.abs();
+    }
+    if spread == 0.0 {
+        spread = 1.0;
+    }
+
+    spread
+}
+
+/// Quantile of a sorted weighted sample interpolating linearly between
+/// samples placed at the midpoints of their cumulative weights.
+fn weighted_quantile(sorted: &[(f64, f64)], total_weight: f64, p: f64) -> f64 {
+    let target = p * total_weight;
+
+    let mut cumulative = 0.0;
+    let mut previous: Option<(f64, f64)> = None;
+
+    for &(x, w) in sorted {
+        let position = cumulative + 0.5 * w;
+        if position >= target {
+            return match previous {
+                None => x,
+                Some((x_0, position_0)) => {
+                    x_0 + (target - position_0) / (position - position_0) * (x - x_0)
+                }
+            };
+        }
+
+        previous = Some((x, position));
+        cumulative += w;
+    }
+
+    sorted[sorted.len() - 1].0
+}
+
+/// Quantile of a sorted sample with frequency weights, interpolating between
+/// the order statistics of the sample with each value repeated count times,
+/// type 7 in the Hyndman and Fan taxonomy.
+fn frequency_quantile(sorted: &[(f64, f64)], total_count: f64, p: f64) -> f64 {
+    let order_statistic = |k: f64| {
+        let mut cumulative = 0.0;
+        for &(x, count) in sorted {
+            cumulative += count;
+            if cumulative > k {
+                return x;
+            }
+        }
+
+        sorted[sorted.len() - 1].0
+    };
+
+    let index = (p * (total_count - 1.0)).max(0.0);
+    let low = index.floor();
+    let lower = order_statistic(low);
+
+    lower + (index - low) * (order_statistic(low + 1.0) - lower)
+}
+
+/// Scale the sample covariance matrix by a factor and n^(-2/(d+4)).
+fn multivariate_rule(samples: &[Vec<f64>], factor: f64) -> BandwidthMatrix {
+    let length = samples.len();
+    assert!(length > 1);
+
+    let dimension = samples[0].len();
+    assert!(dimension > 0);
+    assert!(samples.iter().all(|sample| sample.len() == dimension));
+
+    let n = length as f64;
+    let means: Vec<f64> = (0..dimension)
+        .map(|i| samples.iter().map(|sample| sample[i]).sum::<f64>() / n)
+        .collect();
+
+    let scale = factor * n.powf(-2.0 / (dimension as f64 + 4.0));
+
+    let mut matrix = vec![vec![0.0; dimension]; dimension];
+    for i in 0..dimension {
+        for j in 0..(i + 1) {
+            let covariance = samples.iter()
+                .map(|sample| (sample[i] - means[i]) * (sample[j] - means[j]))
+                .sum::<f64>() / (n - 1.0);
+
+            matrix[i][j] = scale * covariance;
+            matrix[j][i] = scale * covariance;
+        }
+    }
+
+    BandwidthMatrix::Full(matrix)
+}
@@ -0,0 +1,49 @@
+//! Biweight kernel density estimation functions.
+
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+
+/// The Biweight kernel.
+///
+/// k(x) = 15 * (1 - x^2)^2 / 16 for abs(x) <= 1 and 0 otherwise.
+///
+/// Also known as the quartic kernel.
+#[derive(Clone, Copy, Debug)]
+pub struct BiweightKernel;
+
+impl Kernel for BiweightKernel {
+    fn density(&self, x: f64) -> f64 {
+        if x.abs() <= 1.0 {
+            15.0 * (1.0 - x.powi(2)).powi(2) / 16.0
+        } else {
+            0.0
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x >= 1.0 {
+            1.0
+        } else if x > -1.0 {
+            // Horner form keeps rounding errors from breaking monotonicity.
+            let x2 = x * x;
+            0.5 + x * (15.0 + x2 * (-10.0 + 3.0 * x2)) / 16.0
+        } else {
+            0.0
+        }
+    }
+
+    fn support(&self) -> f64 {
+        1.0
+    }
+
+    fn second_moment(&self) -> f64 {
+        1.0 / 7.0
+    }
+
+    fn roughness(&self) -> f64 {
+        5.0 / 7.0
+    }
+}
+
+/// Kernel density estimation using the Biweight kernel.
+pub type BiweightKernelDensityEstimation = KernelDensityEstimation<BiweightKernel>;
@@ -0,0 +1,390 @@
+//! Boundary corrected kernel density estimation for bounded supports.
+
+extern crate rand;
+
+use density::Density;
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::{effective_support, Kernel};
+use self::rand::Rng;
+use std::f64;
+
+/// Upper limit on the number of reflections either side of a doubly bounded
+/// support. Bounds narrower than the kernel need many reflections to fold
+/// back all of the kernel mass.
+const MAX_REFLECTIONS: i64 = 1000;
+
+/// Boundary correction method.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Boundary {
+    /// Reflect kernel mass falling outside the bounds back inside them. With
+    /// two bounds the kernel is reflected repeatedly between the bounds.
+    Reflection,
+    /// Truncate each kernel to the bounds and rescale it to unit mass, also
+    /// known as cut-and-normalise.
+    Renormalisation,
+    /// Estimate the density of the samples transformed to the real line and
+    /// transform the estimation back. The transformation is log(x - lower)
+    /// for a lower bound, -log(upper - x) for an upper bound and
+    /// log((x - lower) / (upper - x)) for both.
+    Transformation,
+}
+
+/// Kernel density estimation for samples known to lie between bounds.
+///
+/// The lower and upper bounds may be infinite for a support bounded on one
+/// side. Both the density and the cumulative density are zero below the
+/// lower bound and the density is zero above the upper bound. The density
+/// integrates to one over the bounds.
+pub struct BoundedKernelDensityEstimation<K: Kernel> {
+    /// The underlying kernel density estimation. For the transformation
+    /// method, this holds the transformed samples.
+    estimation: KernelDensityEstimation<K>,
+    lower: f64,
+    upper: f64,
+    boundary: Boundary,
+}
+
+impl<K: Kernel> BoundedKernelDensityEstimation<K> {
+    /// Construct a new bounded kernel density estimation for a given sample,
+    /// bandwidth, kernel, bounds and boundary correction method.
+    ///
+    /// For the transformation method, the bandwidth applies to the
+    /// transformed samples.
+    ///
+    /// # Panics
+    ///
+    /// Bandwidth must be greater than zero, the sample set must be non-empty
+    /// and lower must be less than upper.
+    ///
+    /// Samples must be between the bounds. For the transformation method,
+    /// samples must be strictly between the bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{Boundary, BoundedKernelDensityEstimation, NormalKernel};
+    /// use std::f64;
+    ///
+    /// let samples = vec!(0.0, 0.5, 1.0, 2.0, 4.0);
+    /// let bandwidth = 0.5;
+    /// let kde = BoundedKernelDensityEstimation::new(&samples, bandwidth, NormalKernel,
+    ///                                               0.0, f64::INFINITY, Boundary::Reflection);
+    ///
+    /// assert_eq!(kde.cdf(0.0), 0.0);
+    /// ```
+    pub fn new(samples: &[f64],
+               bandwidth: f64,
+               kernel: K,
+               lower: f64,
+               upper: f64,
+               boundary: Boundary)
+               -> BoundedKernelDensityEstimation<K> {
+        assert!(lower < upper);
+
+        let estimation = match boundary {
+            Boundary::Transformation => {
+                assert!(samples.iter().all(|&x| lower < x && x < upper));
+
+                let transformed: Vec<f64> =
+                    samples.iter().map(|&x| transform(x, lower, upper)).collect();
+                KernelDensityEstimation::new(&transformed, bandwidth, kernel)
+            }
+            _ => {
+                assert!(samples.iter().all(|&x| (lower..=upper).contains(&x)));
+                KernelDensityEstimation::new(samples, bandwidth, kernel)
+            }
+        };
+
+        BoundedKernelDensityEstimation {
+            estimation,
+            lower,
+            upper,
+            boundary,
+        }
+    }
+
+    /// Return the underlying kernel density estimation. For the
+    /// transformation method, this holds the transformed samples.
+    pub fn estimation(&self) -> &KernelDensityEstimation<K> {
+        &self.estimation
+    }
+
+    /// Return the lower bound, which may be negative infinity.
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Return the upper bound, which may be infinity.
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Return the boundary correction method.
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Number of reflections either side of a doubly bounded support needed
+    /// to cover the kernel support.
+    fn reflections(&self) -> i64 {
+        if !(self.lower.is_finite() && self.upper.is_finite()) {
+            return 0;
+        }
+
+        let radius = effective_support(self.estimation.kernel()) * self.estimation.bandwidth();
+        let reflections = (radius / (2.0 * (self.upper - self.lower))).ceil() + 1.0;
+
+        reflections.min(MAX_REFLECTIONS as f64) as i64
+    }
+
+    /// Call a function with the location of each reflected image of a
+    /// sample, including the sample itself.
+    fn images<F: FnMut(f64)>(&self, sample: f64, reflections: i64, mut f: F) {
+        let lower = self.lower;
+        let upper = self.upper;
+
+        match (lower.is_finite(), upper.is_finite()) {
+            (true, true) => {
+                let period = 2.0 * (upper - lower);
+                for k in -reflections..(reflections + 1) {
+                    let shift = k as f64 * period;
+                    f(sample + shift);
+                    f(2.0 * lower - sample + shift);
+                }
+            }
+            (true, false) => {
+                f(sample);
+                f(2.0 * lower - sample);
+            }
+            (false, true) => {
+                f(sample);
+                f(2.0 * upper - sample);
+            }
+            (false, false) => f(sample),
+        }
+    }
+
+    /// Kernel mass of a sample inside the bounds after boundary correction.
+    fn mass(&self, sample: f64, reflections: i64) -> f64 {
+        let kernel = self.estimation.kernel();
+        let bandwidth = self.estimation.bandwidth();
+
+        let (low, high) = match self.boundary {
+            // The reflected images cover the bounds folded out to
+            // lower +/- (2 * reflections + 1) * (upper - lower).
+            Boundary::Reflection if self.lower.is_finite() && self.upper.is_finite() => {
+                let reach = (2 * reflections + 1) as f64 * (self.upper - self.lower);
+                (self.lower - reach, self.lower + reach)
+            }
+            Boundary::Reflection => return 1.0,
+            _ => (self.lower, self.upper),
+        };
+
+        kernel_cdf(kernel, (high - sample) / bandwidth) -
+        kernel_cdf(kernel, (low - sample) / bandwidth)
+    }
+}
+
+impl<K: Kernel> Density for BoundedKernelDensityEstimation<K> {
+    /// Calculate a value of the bounded kernel density function for a given
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{Boundary, BoundedKernelDensityEstimation, UniformKernel};
+    ///
+    /// let samples = vec!(0.0);
+    /// let kde = BoundedKernelDensityEstimation::new(&samples, 1.0, UniformKernel,
+    ///                                               0.0, 1.0, Boundary::Renormalisation);
+    ///
+    /// assert_eq!(kde.density(0.5), 1.0);
+    /// assert_eq!(kde.density(-0.5), 0.0);
+    /// ```
+    fn density(&self, x: f64) -> f64 {
+        if x < self.lower || x > self.upper {
+            return 0.0;
+        }
+
+        let estimation = &self.estimation;
+        let kernel = estimation.kernel();
+        let bandwidth = estimation.bandwidth();
+
+        if self.boundary == Boundary::Transformation {
+            if x == self.lower || x == self.upper {
+                return 0.0;
+            }
+
+            return estimation.density(transform(x, self.lower, self.upper)) *
+                   transform_derivative(x, self.lower, self.upper);
+        }
+
+        let reflections = self.reflections();
+
+        let mut sum = 0.0;
+        let mut total_weight = 0.0;
+        for (&sample, weight) in estimation.samples().iter().zip(estimation.weights()) {
+            let mut images = 0.0;
+            if self.boundary == Boundary::Reflection {
+                self.images(sample,
+                            reflections,
+                            |image| images += kernel.density((x - image) / bandwidth));
+            } else {
+                images = kernel.density((x - sample) / bandwidth);
+            }
+
+            sum += weight * images / self.mass(sample, reflections);
+            total_weight += weight;
+        }
+
+        sum / (total_weight * bandwidth)
+    }
+
+    /// Calculate a value of the cumulative density function for this bounded
+    /// kernel density estimation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{Boundary, BoundedKernelDensityEstimation, UniformKernel};
+    ///
+    /// let samples = vec!(0.0);
+    /// let kde = BoundedKernelDensityEstimation::new(&samples, 1.0, UniformKernel,
+    ///                                               0.0, 1.0, Boundary::Reflection);
+    ///
+    /// assert_eq!(kde.cdf(0.25), 0.25);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= self.lower {
+            return 0.0;
+        }
+
+        if x >= self.upper {
+            return 1.0;
+        }
+
+        let estimation = &self.estimation;
+        let kernel = estimation.kernel();
+        let bandwidth = estimation.bandwidth();
+
+        if self.boundary == Boundary::Transformation {
+            return estimation.cdf(transform(x, self.lower, self.upper));
+        }
+
+        let reflections = self.reflections();
+
+        // Kernel mass between the lower bound and x for a kernel centred on
+        // a given location.
+        let increment = |centre: f64| {
+            kernel.cdf((x - centre) / bandwidth) -
+            kernel_cdf(kernel, (self.lower - centre) / bandwidth)
+        };
+
+        let mut sum = 0.0;
+        let mut total_weight = 0.0;
+        for (&sample, weight) in estimation.samples().iter().zip(estimation.weights()) {
+            let mut images = 0.0;
+            if self.boundary == Boundary::Reflection {
+                self.images(sample, reflections, |image| images += increment(image));
+            } else {
+                images = increment(sample);
+            }
+
+            sum += weight * images / self.mass(sample, reflections);
+            total_weight += weight;
+        }
+
+        (sum / total_weight).min(1.0)
+    }
+
+    /// Draw a random value from this bounded kernel density estimation.
+    ///
+    /// For reflection, a value drawn from the unbounded estimation is folded
+    /// back between the bounds. For renormalisation, kernel noise for the
+    /// chosen sample is redrawn until the value is between the bounds, so
+    /// draws are slow for bandwidths much wider than the bounds. For the
+    /// transformation method, a value drawn from the estimation of the
+    /// transformed samples is transformed back.
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let estimation = &self.estimation;
+        let lower = self.lower;
+        let upper = self.upper;
+
+        match self.boundary {
+            Boundary::Reflection => {
+                let x = estimation.sample(rng);
+
+                match (lower.is_finite(), upper.is_finite()) {
+                    (true, true) => {
+                        let width = upper - lower;
+                        let folded = (x - lower).rem_euclid(2.0 * width);
+                        lower + if folded > width { 2.0 * width - folded } else { folded }
+                    }
+                    (true, false) if x < lower => 2.0 * lower - x,
+                    (false, true) if x > upper => 2.0 * upper - x,
+                    _ => x,
+                }
+            }
+            Boundary::Renormalisation => {
+                let sample = estimation.samples()[estimation.choose(rng)];
+
+                loop {
+                    let x = sample + estimation.bandwidth() * estimation.kernel().sample(rng);
+                    if (lower..=upper).contains(&x) {
+                        return x;
+                    }
+                }
+            }
+            Boundary::Transformation => {
+                let y = estimation.sample(rng);
+
+                match (lower.is_finite(), upper.is_finite()) {
+                    (true, true) => lower + (upper - lower) / (1.0 + (-y).exp()),
+                    (true, false) => lower + y.exp(),
+                    (false, true) => upper - (-y).exp(),
+                    (false, false) => y,
+                }
+            }
+        }
+    }
+}
+
+/// Kernel cumulative density allowing infinite arguments for infinite bounds.
+fn kernel_cdf<K: Kernel>(kernel: &K, x: f64) -> f64 {
+    if x == f64::INFINITY {
+        1.0
+    } else if x == f64::NEG_INFINITY {
+        0.0
+    } else {
+        kernel.cdf(x)
+    }
+}
+
+/// Map a value between the bounds to the real line.
+fn transform(x: f64, lower: f64, upper: f64) -> f64 {
+    match (lower.is_finite(), upper.is_finite()) {
+        (true, true) => ((x - lower) / (upper - x)).ln(),
+        (true, false) => (x - lower).ln(),
+        (false, true) => -(upper - x).ln(),
+        (false, false) => x,
+    }
+}
+
+/// Derivative of the transformation to the real line.
+fn transform_derivative(x: f64, lower: f64, upper: f64) -> f64 {
+    match (lower.is_finite(), upper.is_finite()) {
+        (true, true) => 1.0 / (x - lower) + 1.0 / (upper - x),
+        (true, false) => 1.0 / (x - lower),
+        (false, true) => 1.0 / (upper - x),
+        (false, false) => 1.0,
+    }
+}
@@ -0,0 +1,811 @@
+//! Circular kernel density estimation.
+//!
+//! Circular data such as angles or times of day wrap around after a period,
+//! for example 360 degrees or 24 hours, so values just below the period are
+//! close to values just above zero. Kernel density estimation on the real
+//! line splits the mass of samples near the wrap point across both ends. A
+//! circular kernel density estimation instead places a kernel on the circle
+//! at each sample and its density is periodic.
+//!
+//! Values are in the units of the period and angles on the circle are
+//! theta = 2 * pi * x / period. Densities are per unit of the period, so
+//! they integrate to one over a full period.
+//!
+//! Both kernels here are unimodal distributions on the circle whose spread
+//! is set by a concentration parameter kappa. Larger concentrations give
+//! narrower kernels. For large kappa, both kernels are approximately Normal
+//! with standard deviation 1 / sqrt(kappa) radians, so a concentration
+//! corresponds to a bandwidth of period / (2 * pi * sqrt(kappa)) on the line.
+
+extern crate rand;
+
+use density::Density;
+use kde::{Kernel, NormalKernel};
+use self::rand::Rng;
+use std::f64;
+use std::f64::consts::PI;
+
+/// Fourier coefficients of the kernel are truncated below this value.
+const COEFFICIENT_TOLERANCE: f64 = 1e-17;
+
+/// Arguments above this use the asymptotic expansion of the modified Bessel
+/// functions rather than the power series.
+const BESSEL_ASYMPTOTIC: f64 = 50.0;
+
+/// Upper limit on the number of terms in the asymptotic expansion of the
+/// modified Bessel functions.
+const BESSEL_TERMS: usize = 30;
+
+/// Number of concentrations in the cross-validation grid.
+const GRID_POINTS: usize = 50;
+
+/// Width of the bracket, in log concentration or in radians, at which golden
+/// section searches stop.
+const TOLERANCE: f64 = 1e-9;
+
+/// Number of bisection steps when inverting the cumulative density function.
+const QUANTILE_ITERATIONS: usize = 100;
+
+/// Kernel on the circle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CircularKernel {
+    /// The von Mises kernel with concentration kappa:
+    ///
+    /// k(theta) = exp(kappa * cos(theta)) / (2 * pi * I_0(kappa))
+    ///
+    /// Where I_0 is the modified Bessel function of order zero.
+    VonMises,
+    /// The wrapped Normal kernel, the Normal density with standard deviation
+    /// sigma wrapped around the circle:
+    ///
+    /// k(theta) = sum(phi((theta + 2 * pi * j) / sigma) / sigma)
+    ///
+    /// Where the sum is over all integers j. The standard deviation is
+    /// chosen to give the same mean resultant length as the von Mises kernel
+    /// with the same concentration, sigma^2 = -2 * log(I_1(kappa) /
+    /// I_0(kappa)).
+    WrappedNormal,
+}
+
+/// A circular kernel with a given concentration.
+struct Shape {
+    kernel: CircularKernel,
+    concentration: f64,
+    /// I_0(kappa) * exp(-kappa), for the von Mises kernel.
+    normaliser: f64,
+    /// Standard deviation of the wrapped Normal kernel.
+    sigma: f64,
+    /// Fourier coefficients rho_j = E(cos(j * theta)) for j = 1, 2, ...
+    coefficients: Vec<f64>,
+}
+
+impl Shape {
+    fn new(kernel: CircularKernel, concentration: f64) -> Shape {
+        let resultant_length = bessel_ratios(concentration, 1)[0];
+        let sigma = (-2.0 * resultant_length.ln()).sqrt();
+
+        // For large kappa, rho_j is about exp(-j^2 / (2 * kappa)).
+        let terms = (9.0 * concentration.sqrt()).ceil() as usize + 30;
+
+        let coefficients = match kernel {
+            CircularKernel::VonMises => {
+                let mut coefficient = 1.0;
+                bessel_ratios(concentration, terms)
+                    .into_iter()
+                    .map(|ratio| {
+                        coefficient *= ratio;
+                        coefficient
+                    })
+                    .take_while(|&coefficient| coefficient >= COEFFICIENT_TOLERANCE)
+                    .collect()
+            }
+            CircularKernel::WrappedNormal => {
+                (1..(terms + 1))
+                    .map(|j| (-0.5 * (j * j) as f64 * sigma * sigma).exp())
+                    .take_while(|&coefficient| coefficient >= COEFFICIENT_TOLERANCE)
+                    .collect()
+            }
+        };
+
+        Shape {
+            kernel,
+            concentration,
+            normaliser: (log_bessel_i(0, concentration) - concentration).exp(),
+            sigma,
+            coefficients,
+        }
+    }
+
+    /// Kernel density per radian at an angle from the kernel centre.
+    fn density(&self, theta: f64) -> f64 {
+        match self.kernel {
+            CircularKernel::VonMises => {
+                (self.concentration * (theta.cos() - 1.0)).exp() / (2.0 * PI * self.normaliser)
+            }
+            CircularKernel::WrappedNormal => {
+                let theta = theta.rem_euclid(2.0 * PI);
+                let wraps = (8.5 * self.sigma / (2.0 * PI)).ceil() as i64 + 1;
+
+                (-wraps..(wraps + 1))
+                    .map(|j| NormalKernel.density((theta + 2.0 * PI * j as f64) / self.sigma))
+                    .sum::<f64>() / self.sigma
+            }
+        }
+    }
+
+    /// Kernel mass on the arc from angle a to angle a + t, for t in [0, 2 *
+    /// pi], relative to the kernel centre.
+    fn arc(&self, a: f64, t: f64) -> f64 {
+        let b = a + t;
+
+        let series: f64 = self.coefficients
+            .iter()
+            .enumerate()
+            .map(|(i, coefficient)| {
+                let j = (i + 1) as f64;
+                coefficient * ((j * b).sin() - (j * a).sin()) / j
+            })
+            .sum();
+
+        t / (2.0 * PI) + series / PI
+    }
+
+    /// Draw a random angle from the kernel centred on zero.
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let kappa = self.concentration;
+
+        match self.kernel {
+            CircularKernel::WrappedNormal => self.sigma * NormalKernel.sample(rng),
+            // The von Mises kernel is indistinguishable from uniform for
+            // tiny concentrations and from Normal for huge ones.
+            CircularKernel::VonMises if kappa < 1e-8 => 2.0 * PI * rng.next_f64(),
+            CircularKernel::VonMises if kappa > 1e5 => NormalKernel.sample(rng) / kappa.sqrt(),
+            CircularKernel::VonMises => {
+                // Best and Fisher (1979) rejection sampling.
+                let tau = 1.0 + (1.0 + 4.0 * kappa * kappa).sqrt();
+                let rho = (tau - (2.0 * tau).sqrt()) / (2.0 * kappa);
+                let r = (1.0 + rho * rho) / (2.0 * rho);
+
+                loop {
+                    let z = (PI * rng.next_f64()).cos();
+                    let f = (1.0 + r * z) / (r + z);
+                    let c = kappa * (r - f);
+                    let u = rng.next_f64();
+
+                    if c * (2.0 - c) > u || (c / u).ln() + 1.0 >= c {
+                        let theta = f.clamp(-1.0, 1.0).acos();
+                        return if rng.next_f64() < 0.5 { -theta } else { theta };
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Kernel density estimation for circular samples with a given period.
+///
+/// The density is:
+///
+/// f(x) = 2 * pi * sum(w_i * k(2 * pi * (x - x_i) / period)) / (period *
+/// sum(w_i))
+///
+/// Where k is the circular kernel with the estimation concentration and w_i
+/// are the sample weights.
+///
+/// The cumulative density function is the mass on the arc from the origin
+/// anticlockwise to a value, which is zero at the origin and increases to
+/// one just before the origin is reached again. It is periodic like the
+/// density. Each cumulative density evaluation costs O(n * J) for n samples,
+/// where the number of kernel Fourier coefficients J grows with the square
+/// root of the concentration for the von Mises kernel.
+pub struct CircularKernelDensityEstimation {
+    /// Samples reduced to [0, period).
+    samples: Vec<f64>,
+    weights: Vec<f64>,
+    period: f64,
+    concentration: f64,
+    kernel: CircularKernel,
+    /// Start of the arc for the cumulative density function and quantiles,
+    /// zero by default.
+    pub origin: f64,
+    shape: Shape,
+}
+
+impl CircularKernelDensityEstimation {
+    /// Construct a new circular kernel density estimation for a given
+    /// sample, period, concentration and kernel.
+    ///
+    /// # Panics
+    ///
+    /// Period and concentration must be finite and greater than zero and the
+    /// sample set must be non-empty with finite values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::circular::{CircularKernel, CircularKernelDensityEstimation};
+    ///
+    /// // Hours of the day.
+    /// let samples = vec!(22.5, 23.0, 23.5, 0.0, 0.5, 1.0, 12.0);
+    /// let kde = CircularKernelDensityEstimation::new(&samples, 24.0, 50.0,
+    ///                                                CircularKernel::VonMises);
+    ///
+    /// assert!((kde.density(23.75) - kde.density(47.75)).abs() < 1e-12);
+    /// assert!(kde.density(23.75) > kde.density(12.0));
+    /// ```
+    pub fn new(samples: &[f64],
+               period: f64,
+               concentration: f64,
+               kernel: CircularKernel)
+               -> CircularKernelDensityEstimation {
+        let weights = vec![1.0; samples.len()];
+        CircularKernelDensityEstimation::weighted(samples, &weights, period, concentration, kernel)
+    }
+
+    /// Construct a new circular kernel density estimation for a given
+    /// sample, sample weights, period, concentration and kernel.
+    ///
+    /// # Panics
+    ///
+    /// Period and concentration must be finite and greater than zero and the
+    /// sample set must be non-empty with finite values. There must be one
+    /// weight for each sample. Weights must be finite and non-negative, and
+    /// at least one weight must be greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::circular::{CircularKernel, CircularKernelDensityEstimation};
+    ///
+    /// let samples = vec!(90.0, 270.0);
+    /// let weights = vec!(3.0, 1.0);
+    /// let kde = CircularKernelDensityEstimation::weighted(&samples, &weights, 360.0, 50.0,
+    ///                                                     CircularKernel::WrappedNormal);
+    ///
+    /// assert!((kde.cdf(180.0) - 0.75).abs() < 1e-9);
+    /// ```
+    pub fn weighted(samples: &[f64],
+                    weights: &[f64],
+                    period: f64,
+                    concentration: f64,
+                    kernel: CircularKernel)
+                    -> CircularKernelDensityEstimation {
+        assert!(period > 0.0 && period.is_finite());
+        assert!(concentration > 0.0 && concentration.is_finite());
+
+        let length = samples.len();
+        assert!(length > 0);
+        assert!(weights.len() == length);
+        assert!(samples.iter().all(|x| x.is_finite()));
+
+        assert!(weights.iter().all(|&weight| weight >= 0.0 && weight.is_finite()));
+
+        let total_weight: f64 = weights.iter().sum();
+        assert!(total_weight > 0.0);
+
+        CircularKernelDensityEstimation {
+            samples: samples.iter().map(|x| x.rem_euclid(period)).collect(),
+            weights: weights.to_vec(),
+            period,
+            concentration,
+            kernel,
+            origin: 0.0,
+            shape: Shape::new(kernel, concentration),
+        }
+    }
+
+    /// Return the samples reduced to [0, period), in the order given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::circular::{CircularKernel, CircularKernelDensityEstimation};
+    ///
+    /// let kde = CircularKernelDensityEstimation::new(&[-90.0, 450.0], 360.0, 10.0,
+    ///                                                CircularKernel::VonMises);
+    ///
+    /// assert_eq!(kde.samples(), &[270.0, 90.0]);
+    /// ```
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+
+    /// Return the sample weights, in the same order as `samples`. The weights
+    /// are one for an unweighted estimation.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Return the period of the estimation.
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    /// Return the concentration of the kernel.
+    pub fn concentration(&self) -> f64 {
+        self.concentration
+    }
+
+    /// Return the circular kernel of the estimation.
+    pub fn kernel(&self) -> CircularKernel {
+        self.kernel
+    }
+
+    /// Convert a value to an angle in radians.
+    fn angle(&self, x: f64) -> f64 {
+        2.0 * PI * x / self.period
+    }
+
+    /// Convert an angle in radians to a value in [0, period).
+    fn value(&self, theta: f64) -> f64 {
+        (theta * self.period / (2.0 * PI)).rem_euclid(self.period)
+    }
+
+    /// Calculate the total of the sample weights.
+    fn total_weight(&self) -> f64 {
+        self.weights.iter().sum()
+    }
+
+    /// Calculate the weighted sums of the sample cosines and sines.
+    fn resultant(&self) -> (f64, f64) {
+        let mut cosines = 0.0;
+        let mut sines = 0.0;
+        for (&sample, weight) in self.samples.iter().zip(&self.weights) {
+            let theta = self.angle(sample);
+            cosines += weight * theta.cos();
+            sines += weight * theta.sin();
+        }
+
+        (cosines, sines)
+    }
+
+    /// Calculate the mass on the arc of a given length in radians from the
+    /// origin.
+    fn arc(&self, t: f64) -> f64 {
+        let origin = self.angle(self.origin);
+
+        let mut sum = 0.0;
+        for (&sample, weight) in self.samples.iter().zip(&self.weights) {
+            sum += weight * self.shape.arc(origin - self.angle(sample), t);
+        }
+
+        (sum / self.total_weight()).clamp(0.0, 1.0)
+    }
+
+    /// Calculate the circular mean of the estimation, the direction of the
+    /// weighted mean of the samples as points on the unit circle, as a value
+    /// in [0, period).
+    ///
+    /// The circular mean is undefined when the mean resultant length is
+    /// zero, for example for two opposite samples, and zero is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::circular::{CircularKernel, CircularKernelDensityEstimation};
+    ///
+    /// let samples = vec!(350.0, 10.0, 20.0);
+    /// let kde = CircularKernelDensityEstimation::new(&samples, 360.0, 10.0,
+    ///                                                CircularKernel::VonMises);
+    ///
+    /// assert!((kde.mean() - 6.7).abs() < 0.01);
+    /// ```
+    pub fn mean(&self) -> f64 {
+        let (cosines, sines) = self.resultant();
+        self.value(sines.atan2(cosines))
+    }
+
+    /// Calculate the mean resultant length of the estimation, the length of
+    /// the mean of the estimation as points on the unit circle.
+    ///
+    /// The mean resultant length is between zero, for example for a uniform
+    /// density, and one for a point mass. It is the mean resultant length of
+    /// the samples scaled by that of the kernel, I_1(kappa) / I_0(kappa).
+    pub fn mean_resultant_length(&self) -> f64 {
+        let (cosines, sines) = self.resultant();
+        let resultant_length = cosines.hypot(sines) / self.total_weight();
+
+        resultant_length * bessel_ratios(self.concentration, 1)[0]
+    }
+
+    /// Find the mode of the estimation, the value in [0, period) with the
+    /// highest density.
+    ///
+    /// The density is evaluated on a grid around the circle finer than the
+    /// kernel width and the highest grid point is refined by golden section
+    /// search. Each grid evaluation costs O(n), and the grid has O(sqrt(kappa))
+    /// points. Of several modes of equal height, any may be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::circular::{CircularKernel, CircularKernelDensityEstimation};
+    ///
+    /// let samples = vec!(23.0, 23.5, 0.0, 0.5, 1.0, 12.0);
+    /// let kde = CircularKernelDensityEstimation::new(&samples, 24.0, 20.0,
+    ///                                                CircularKernel::VonMises);
+    ///
+    /// let mode = kde.mode();
+    /// assert!(mode < 1.0 || mode > 23.0);
+    /// ```
+    pub fn mode(&self) -> f64 {
+        let density = |theta: f64| {
+            self.samples
+                .iter()
+                .zip(&self.weights)
+                .map(|(&sample, weight)| weight * self.shape.density(theta - self.angle(sample)))
+                .sum::<f64>()
+        };
+
+        let points = ((16.0 * self.concentration.sqrt()).ceil() as usize).max(64);
+        let step = 2.0 * PI / points as f64;
+
+        let mut best = 0;
+        let mut maximum = f64::NEG_INFINITY;
+        for i in 0..points {
+            let value = density(i as f64 * step);
+            if value > maximum {
+                best = i;
+                maximum = value;
+            }
+        }
+
+        let centre = best as f64 * step;
+        let theta = maximise(density, centre - step, centre + step);
+
+        self.value(theta)
+    }
+}
+
+impl Density for CircularKernelDensityEstimation {
+    /// Calculate a value of the circular kernel density function for a given
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::circular::{CircularKernel, CircularKernelDensityEstimation};
+    ///
+    /// let samples = vec!(0.0);
+    /// let kde = CircularKernelDensityEstimation::new(&samples, 360.0, 1e-12,
+    ///                                                CircularKernel::VonMises);
+    ///
+    /// assert!((kde.density(90.0) - 1.0 / 360.0).abs() < 1e-12);
+    /// ```
+    fn density(&self, x: f64) -> f64 {
+        let theta = self.angle(x);
+
+        let mut sum = 0.0;
+        for (&sample, weight) in self.samples.iter().zip(&self.weights) {
+            sum += weight * self.shape.density(theta - self.angle(sample));
+        }
+
+        2.0 * PI * sum / (self.period * self.total_weight())
+    }
+
+    /// Calculate the mass of the circular kernel density estimation on the
+    /// arc from the origin anticlockwise to a given value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::circular::{CircularKernel, CircularKernelDensityEstimation};
+    ///
+    /// let samples = vec!(22.0, 2.0);
+    /// let mut kde = CircularKernelDensityEstimation::new(&samples, 24.0, 10.0,
+    ///                                                    CircularKernel::VonMises);
+    ///
+    /// kde.origin = 12.0;
+    /// assert!((kde.cdf(24.0) - 0.5).abs() < 1e-9);
+    /// assert!((kde.cdf(0.0) - 0.5).abs() < 1e-9);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        let t = (x - self.origin).rem_euclid(self.period);
+        self.arc(self.angle(t))
+    }
+
+    /// Calculate the value on the arc from the origin, in [origin, origin +
+    /// period), with a given mass on the arc from the origin to it.
+    ///
+    /// # Panics
+    ///
+    /// Quantile probability must satisfy 0 < p < 1.
+    fn quantile(&self, p: f64) -> f64 {
+        assert!(0.0 < p && p < 1.0);
+
+        let mut lower = 0.0;
+        let mut upper = 2.0 * PI;
+        for _ in 0..QUANTILE_ITERATIONS {
+            let middle = 0.5 * (lower + upper);
+            if middle <= lower || middle >= upper {
+                break;
+            }
+
+            if self.arc(middle) < p {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+
+        self.origin + upper * self.period / (2.0 * PI)
+    }
+
+    /// Draw a random value in [0, period) from this circular kernel density
+    /// estimation.
+    ///
+    /// A sample is chosen in proportion to its weight and a draw from the
+    /// kernel is added to it.
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let mut target = rng.next_f64() * self.total_weight();
+
+        let mut chosen = self.samples.len() - 1;
+        for (i, &weight) in self.weights.iter().enumerate() {
+            if target < weight {
+                chosen = i;
+                break;
+            }
+
+            target -= weight;
+        }
+
+        let theta = self.angle(self.samples[chosen]) + self.shape.sample(rng);
+        self.value(theta)
+    }
+}
+
+/// Select a von Mises kernel concentration for a sample with a given period
+/// by a rule of thumb.
+///
+/// The rule of thumb of Taylor (2008) minimises the asymptotic mean
+/// integrated squared error when the samples are von Mises distributed:
+///
+/// kappa = (3 * n * k^2 * I_2(2 * k) / (4 * sqrt(pi) * I_0(k)^2))^(2 / 5)
+///
+/// Where k is the von Mises concentration fitted to the samples from their
+/// mean resultant length, using the approximation of Best and Fisher (1981).
+/// Like Silverman's rule, it oversmooths multimodal samples.
+///
+/// # Panics
+///
+/// Period must be finite and greater than zero, and the sample set must have
+/// more than one element and not all samples may be equal.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::circular;
+/// use kernel_density::kde::circular::{CircularKernel, CircularKernelDensityEstimation};
+///
+/// let samples = vec!(22.0, 23.0, 23.5, 0.5, 1.0, 2.0, 3.0);
+/// let concentration = circular::rule_of_thumb(&samples, 24.0);
+///
+/// let kde = CircularKernelDensityEstimation::new(&samples, 24.0, concentration,
+///                                                CircularKernel::VonMises);
+/// ```
+pub fn rule_of_thumb(samples: &[f64], period: f64) -> f64 {
+    assert!(period > 0.0 && period.is_finite());
+
+    let length = samples.len();
+    assert!(length > 1);
+
+    let n = length as f64;
+    let (cosines, sines) = samples.iter()
+        .map(|x| 2.0 * PI * x / period)
+        .fold((0.0, 0.0), |(c, s), theta| (c + theta.cos(), s + theta.sin()));
+    let resultant_length = cosines.hypot(sines) / n;
+    assert!(resultant_length < 1.0);
+
+    let kappa = inverse_bessel_ratio(resultant_length);
+    let log_ratio = log_bessel_i(2, 2.0 * kappa) - 2.0 * log_bessel_i(0, kappa);
+
+    (3.0 * n * kappa * kappa * log_ratio.exp() / (4.0 * PI.sqrt())).powf(0.4)
+}
+
+/// Select a concentration for a sample with a given period and kernel by
+/// likelihood cross-validation.
+///
+/// The concentration maximises the leave-one-out log-likelihood:
+///
+/// CV(kappa) = sum(log(f_-i(x_i)))
+///
+/// Where f_-i is the circular kernel density estimation with sample x_i left
+/// out. Each evaluation costs O(n^2) kernel evaluations. The objective is
+/// evaluated on a logarithmic grid of concentrations between given bounds
+/// and the best grid point is refined with a golden section search.
+///
+/// # Panics
+///
+/// Period must be finite and greater than zero, the sample set must have
+/// more than one element and the bounds must satisfy 0 < lower < upper.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::circular;
+/// use kernel_density::kde::circular::CircularKernel;
+///
+/// let samples = vec!(350.0, 355.0, 0.0, 5.0, 15.0, 170.0, 180.0, 185.0);
+/// let concentration = circular::likelihood(&samples, 360.0, CircularKernel::VonMises,
+///                                          0.1, 1000.0);
+///
+/// assert!(0.1 <= concentration && concentration <= 1000.0);
+/// ```
+pub fn likelihood(samples: &[f64],
+                  period: f64,
+                  kernel: CircularKernel,
+                  lower: f64,
+                  upper: f64)
+                  -> f64 {
+    assert!(period > 0.0 && period.is_finite());
+    assert!(samples.len() > 1);
+    assert!(0.0 < lower && lower < upper);
+
+    let angles: Vec<f64> = samples.iter().map(|x| 2.0 * PI * x / period).collect();
+    let length = angles.len();
+
+    let objective = |log_concentration: f64| {
+        let shape = Shape::new(kernel, log_concentration.exp());
+        let mut sums = vec![0.0; length];
+
+        for i in 0..length {
+            for j in (i + 1)..length {
+                let k = shape.density(angles[i] - angles[j]);
+                sums[i] += k;
+                sums[j] += k;
+            }
+        }
+
+        // Samples without neighbours within the kernel reach have zero
+        // leave-one-out density.
+        if sums.contains(&0.0) {
+            return f64::NEG_INFINITY;
+        }
+
+        sums.iter().map(|sum| sum.ln()).sum::<f64>()
+    };
+
+    let (low, high) = (lower.ln(), upper.ln());
+    let step = (high - low) / (GRID_POINTS - 1) as f64;
+
+    let mut best = 0;
+    let mut maximum = f64::NEG_INFINITY;
+    for i in 0..GRID_POINTS {
+        let value = objective(low + i as f64 * step);
+        if value > maximum {
+            best = i;
+            maximum = value;
+        }
+    }
+
+    let centre = low + best as f64 * step;
+    let log_concentration = maximise(objective,
+                                     (centre - step).max(low),
+                                     (centre + step).min(high));
+
+    log_concentration.exp()
+}
+
+/// Maximise a function on [low, high] by golden section search.
+fn maximise<F: Fn(f64) -> f64>(f: F, mut low: f64, mut high: f64) -> f64 {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut left = high - ratio * (high - low);
+    let mut right = low + ratio * (high - low);
+    let mut left_value = f(left);
+    let mut right_value = f(right);
+
+    while high - low > TOLERANCE {
+        if left_value >= right_value {
+            high = right;
+            right = left;
+            right_value = left_value;
+            left = high - ratio * (high - low);
+            left_value = f(left);
+        } else {
+            low = left;
+            left = right;
+            left_value = right_value;
+            right = low + ratio * (high - low);
+            right_value = f(right);
+        }
+    }
+
+    0.5 * (low + high)
+}
+
+/// Calculate the ratios I_j(x) / I_(j - 1)(x) of modified Bessel functions
+/// for j = 1, ..., terms by backward recurrence, which is stable for all x
+/// and does not overflow.
+fn bessel_ratios(x: f64, terms: usize) -> Vec<f64> {
+    // The recurrence converges from any starting value well above the
+    // largest needed order, and for large x the ratios are close to one up
+    // to orders of about sqrt(x).
+    let start = terms + (10.0 * x.sqrt()).ceil() as usize + 50;
+
+    let mut ratios = vec![0.0; terms];
+    let mut ratio = 0.0;
+    for j in (1..(start + 1)).rev() {
+        ratio = 1.0 / (2.0 * j as f64 / x + ratio);
+        if j <= terms {
+            ratios[j - 1] = ratio;
+        }
+    }
+
+    ratios
+}
+
+/// Calculate the logarithm of the modified Bessel function I_v(x) of the
+/// first kind and integer order, by its power series for small arguments and
+/// its asymptotic expansion for large ones.
+fn log_bessel_i(order: usize, x: f64) -> f64 {
+    let v = order as f64;
+
+    if x <= BESSEL_ASYMPTOTIC {
+        // I_v(x) = sum((x / 2)^(2 * k + v) / (k! * (k + v)!)) for k >= 0.
+        let square = 0.25 * x * x;
+        let mut term = (1..(order + 1)).fold(1.0, |term, i| term * 0.5 * x / i as f64);
+        let mut sum = term;
+        let mut k = 1.0;
+        while term > f64::EPSILON * sum {
+            term *= square / (k * (k + v));
+            sum += term;
+            k += 1.0;
+        }
+
+        return sum.ln();
+    }
+
+    // I_v(x) = exp(x) / sqrt(2 * pi * x) * sum((-1)^k * a_k(v) / x^k), where
+    // a_k(v) = (4 * v^2 - 1) * (4 * v^2 - 9) * ... * (4 * v^2 - (2 * k - 1)^2) /
+    // (k! * 8^k). The terms decrease until k is about 2 * x.
+    let mu = 4.0 * v * v;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for k in 1..BESSEL_TERMS {
+        let k = k as f64;
+        let next = -term * (mu - (2.0 * k - 1.0).powi(2)) / (8.0 * k * x);
+        if next.abs() >= term.abs() {
+            break;
+        }
+
+        term = next;
+        sum += term;
+    }
+
+    x - 0.5 * (2.0 * PI * x).ln() + sum.ln()
+}
+
+/// Approximate the von Mises concentration with a given mean resultant
+/// length, the inverse of I_1(kappa) / I_0(kappa), by Best and Fisher
+/// (1981).
+fn inverse_bessel_ratio(r: f64) -> f64 {
+    if r < 0.53 {
+        2.0 * r + r.powi(3) + 5.0 * r.powi(5) / 6.0
+    } else if r < 0.85 {
+        -0.4 + 1.39 * r + 0.43 / (1.0 - r)
+    } else {
+        1.0 / (r.powi(3) - 4.0 * r * r + 3.0 * r)
+    }
+}
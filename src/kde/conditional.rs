@@ -0,0 +1,112 @@
+//! Conditional kernel density estimation for an arbitrary kernel.
+
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+
+/// Kernel density estimation of the density of a response conditional on
+/// the value of a conditioning variable, from paired samples (x_i, y_i).
+///
+/// The conditional density given x is the kernel density estimation of the
+/// responses y_i weighted by the kernel of the distance of their conditions
+/// from x, in the style of the Nadaraya-Watson regression estimator:
+///
+/// f(y | x) = sum(w_i(x) * k((y - y_i) / h_y)) / (h_y * sum(w_i(x)))
+///
+/// w_i(x) = k((x - x_i) / h_x)
+///
+/// Where h_x and h_y are the condition and response bandwidths. This is the
+/// ratio of the product kernel estimation of the joint density to the kernel
+/// density estimation of the conditions.
+pub struct ConditionalKernelDensityEstimation<K: Kernel> {
+    pub conditions: Vec<f64>,
+    pub responses: Vec<f64>,
+    pub condition_bandwidth: f64,
+    pub response_bandwidth: f64,
+    pub kernel: K,
+}
+
+impl<K: Kernel + Clone> ConditionalKernelDensityEstimation<K> {
+    /// Construct a new conditional kernel density estimation for given
+    /// paired samples of conditions and responses, bandwidths and kernel.
+    ///
+    /// # Panics
+    ///
+    /// Bandwidths must be greater than zero, the sample set must be
+    /// non-empty and there must be one response for each condition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::{ConditionalKernelDensityEstimation, NormalKernel};
+    ///
+    /// let conditions = vec!(0.0, 1.0, 2.0, 3.0, 4.0);
+    /// let responses = vec!(0.5, 1.5, 1.0, 3.5, 4.0);
+    /// let kde = ConditionalKernelDensityEstimation::new(&conditions, &responses,
+    ///                                                   1.0, 0.5, NormalKernel);
+    /// ```
+    pub fn new(conditions: &[f64],
+               responses: &[f64],
+               condition_bandwidth: f64,
+               response_bandwidth: f64,
+               kernel: K)
+               -> ConditionalKernelDensityEstimation<K> {
+        assert!(condition_bandwidth > 0.0);
+        assert!(response_bandwidth > 0.0);
+
+        let length = conditions.len();
+        assert!(length > 0);
+        assert!(responses.len() == length);
+
+        ConditionalKernelDensityEstimation {
+            conditions: conditions.to_vec(),
+            responses: responses.to_vec(),
+            condition_bandwidth,
+            response_bandwidth,
+            kernel,
+        }
+    }
+
+    /// Calculate the kernel weight of each sample for a given conditioning
+    /// value, in sample order.
+    pub fn weights(&self, x: f64) -> Vec<f64> {
+        self.conditions
+            .iter()
+            .map(|condition| self.kernel.density((x - condition) / self.condition_bandwidth))
+            .collect()
+    }
+
+    /// Construct the univariate kernel density estimation of the response
+    /// conditional on a given conditioning value.
+    ///
+    /// # Panics
+    ///
+    /// At least one sample condition must be close enough to the
+    /// conditioning value to have a non-zero kernel weight. For compact
+    /// kernels this means within the kernel support.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{ConditionalKernelDensityEstimation, UniformKernel};
+    ///
+    /// let conditions = vec!(0.0, 0.0, 5.0);
+    /// let responses = vec!(1.0, 2.0, 10.0);
+    /// let kde = ConditionalKernelDensityEstimation::new(&conditions, &responses,
+    ///                                                   1.0, 0.5, UniformKernel);
+    ///
+    /// let conditional = kde.given(0.5);
+    /// assert_eq!(conditional.cdf(1.5), 0.5);
+    /// assert_eq!(conditional.density(10.0), 0.0);
+    /// ```
+    pub fn given(&self, x: f64) -> KernelDensityEstimation<K> {
+        KernelDensityEstimation::weighted(&self.responses,
+                                          &self.weights(x),
+                                          self.response_bandwidth,
+                                          self.kernel.clone())
+    }
+}
@@ -0,0 +1,166 @@
+//! Confidence bands for kernel density estimation.
+//!
+//! A kernel density estimation f_h estimates the smoothed density E(f_h)
+//! rather than the density itself, and the difference, the smoothing bias,
+//! is of order h^2. The bands here cover the smoothed density and do not
+//! include the smoothing bias. Choosing a bandwidth slightly smaller than
+//! the rules in `kde::bandwidth` reduces the bias relative to the band
+//! width.
+//!
+//! Pointwise bands cover the smoothed density at each value separately with
+//! the given confidence level. Simultaneous bands cover it at every value at
+//! once and are wider.
+
+extern crate rand;
+extern crate special_fun;
+
+use density::Density;
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+use self::rand::Rng;
+use self::special_fun::FloatSpecial;
+
+/// Confidence band for a kernel density estimation over a set of values.
+pub struct Band {
+    /// Values the band is evaluated at.
+    pub xs: Vec<f64>,
+    /// Lower limit of the band at each value.
+    pub lower: Vec<f64>,
+    /// Kernel density estimation density at each value.
+    pub estimate: Vec<f64>,
+    /// Upper limit of the band at each value.
+    pub upper: Vec<f64>,
+}
+
+/// Calculate an asymptotic pointwise confidence band for a kernel density
+/// estimation.
+///
+/// The variance of the density estimation at x is approximately:
+///
+/// var(f_h(x)) = f(x) * R(k) / (n * h)
+///
+/// Where R(k) is the kernel roughness and n is the effective sample size.
+/// With f(x) estimated by f_h(x), the band is f_h(x) +/- z * sqrt(var) with z
+/// the Normal quantile for the confidence level, and the lower limit is
+/// truncated at zero. The approximation needs many samples within the kernel
+/// support of x and is poor in the tails.
+///
+/// # Panics
+///
+/// The confidence level must satisfy 0 < level < 1.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::{confidence, KernelDensityEstimation, NormalKernel};
+///
+/// let samples = vec!(0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0);
+/// let kde = KernelDensityEstimation::new(&samples, 1.0, NormalKernel);
+///
+/// let band = confidence::pointwise(&kde, &[1.0, 2.0, 3.0], 0.95);
+/// assert!(band.lower[1] < band.estimate[1] && band.estimate[1] < band.upper[1]);
+/// ```
+pub fn pointwise<K: Kernel>(estimation: &KernelDensityEstimation<K>,
+                            xs: &[f64],
+                            level: f64)
+                            -> Band {
+    assert!(0.0 < level && level < 1.0);
+
+    let z = (0.5 + 0.5 * level).norm_inv();
+    let scale = estimation.kernel().roughness() /
+                (estimation.effective_sample_size() * estimation.bandwidth());
+
+    let estimate = estimation.densities(xs);
+    let half_widths: Vec<f64> = estimate.iter().map(|f| z * (f * scale).sqrt()).collect();
+
+    Band {
+        xs: xs.to_vec(),
+        lower: estimate.iter().zip(&half_widths).map(|(f, w)| (f - w).max(0.0)).collect(),
+        upper: estimate.iter().zip(&half_widths).map(|(f, w)| f + w).collect(),
+        estimate,
+    }
+}
+
+/// Calculate a bootstrap simultaneous confidence band for a kernel density
+/// estimation.
+///
+/// The square root of the density estimation has approximately constant
+/// variance R(k) / (4 * n * h), so the band has constant width on the square
+/// root scale. Each bootstrap replicate resamples the samples with
+/// replacement, in proportion to their weights, and the band half width c is
+/// the level quantile over the replicates of:
+///
+/// max(abs(sqrt(f*_h(x)) - sqrt(f_h(x))))
+///
+/// Where the maximum is over the given values and f*_h is the replicate
+/// estimation. The band is then (max(sqrt(f_h(x)) - c, 0))^2 to
+/// (sqrt(f_h(x)) + c)^2. The band is simultaneous over the given values, so
+/// they should cover the range of interest at a spacing finer than the
+/// bandwidth. Each replicate costs a density evaluation at every value.
+///
+/// # Panics
+///
+/// The confidence level must satisfy 0 < level < 1 and there must be at
+/// least one replicate.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+/// extern crate rand;
+///
+/// use kernel_density::kde::{confidence, KernelDensityEstimation, NormalKernel};
+///
+/// let samples = vec!(0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0);
+/// let kde = KernelDensityEstimation::new(&samples, 1.0, NormalKernel);
+/// let xs = vec!(0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, 4.0);
+///
+/// let band = confidence::simultaneous(&kde, &xs, 0.95, 200, &mut rand::thread_rng());
+/// assert!(band.lower[4] < band.estimate[4] && band.estimate[4] < band.upper[4]);
+/// ```
+pub fn simultaneous<K: Kernel + Clone>(estimation: &KernelDensityEstimation<K>,
+                                       xs: &[f64],
+                                       level: f64,
+                                       replicates: usize,
+                                       rng: &mut dyn Rng)
+                                       -> Band {
+    assert!(0.0 < level && level < 1.0);
+    assert!(replicates > 0);
+
+    let estimate = estimation.densities(xs);
+    let roots: Vec<f64> = estimate.iter().map(|f| f.sqrt()).collect();
+
+    let length = estimation.samples().len();
+    let mut resampled = vec![0.0; length];
+    let mut deviations = Vec::with_capacity(replicates);
+
+    for _ in 0..replicates {
+        for value in &mut resampled {
+            *value = estimation.samples()[estimation.choose(rng)];
+        }
+
+        let replicate = KernelDensityEstimation::new(&resampled,
+                                                     estimation.bandwidth(),
+                                                     estimation.kernel().clone());
+
+        let deviation = replicate.densities(xs)
+            .iter()
+            .zip(&roots)
+            .map(|(f, root)| (f.sqrt() - root).abs())
+            .fold(0.0, f64::max);
+        deviations.push(deviation);
+    }
+
+    deviations.sort_by(|d_1, d_2| d_1.partial_cmp(d_2).unwrap());
+    let index = ((level * replicates as f64).ceil() as usize).clamp(1, replicates) - 1;
+    let half_width = deviations[index];
+
+    Band {
+        xs: xs.to_vec(),
+        lower: roots.iter().map(|root| (root - half_width).max(0.0).powi(2)).collect(),
+        upper: roots.iter().map(|root| (root + half_width).powi(2)).collect(),
+        estimate,
+    }
+}
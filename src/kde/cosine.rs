@@ -0,0 +1,46 @@
+//! Cosine kernel density estimation functions.
+
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// The Cosine kernel.
+///
+/// k(x) = pi * cos(pi * x / 2) / 4 for abs(x) <= 1 and 0 otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct CosineKernel;
+
+impl Kernel for CosineKernel {
+    fn density(&self, x: f64) -> f64 {
+        if x.abs() <= 1.0 {
+            PI * (FRAC_PI_2 * x).cos() / 4.0
+        } else {
+            0.0
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x >= 1.0 {
+            1.0
+        } else if x > -1.0 {
+            0.5 + 0.5 * (FRAC_PI_2 * x).sin()
+        } else {
+            0.0
+        }
+    }
+
+    fn support(&self) -> f64 {
+        1.0
+    }
+
+    fn second_moment(&self) -> f64 {
+        1.0 - 8.0 / PI.powi(2)
+    }
+
+    fn roughness(&self) -> f64 {
+        PI.powi(2) / 16.0
+    }
+}
+
+/// Kernel density estimation using the Cosine kernel.
+pub type CosineKernelDensityEstimation = KernelDensityEstimation<CosineKernel>;
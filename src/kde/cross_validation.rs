@@ -0,0 +1,217 @@
+//! Cross-validation bandwidth selection.
+//!
+//! Both objectives here are computed exactly from all pairs of samples, so
+//! each evaluation costs O(n^2) kernel evaluations. The optimiser evaluates
+//! the objective on a logarithmic grid of bandwidths between given bounds and
+//! then refines the best grid point with a golden section search.
+
+use kde::Kernel;
+use std::f64;
+
+/// Number of bandwidths in the objective curve grid.
+const GRID_POINTS: usize = 50;
+
+/// Relative width of the bracket at which the golden section search stops.
+const TOLERANCE: f64 = 1e-6;
+
+/// Cross-validation bandwidth selection result.
+pub struct CrossValidation {
+    /// The bandwidth minimising the objective.
+    pub bandwidth: f64,
+    /// The objective value at the chosen bandwidth.
+    pub objective: f64,
+    /// The objective evaluated on the grid of bandwidths, as (bandwidth,
+    /// objective) pairs in increasing bandwidth order.
+    pub curve: Vec<(f64, f64)>,
+}
+
+/// Select a bandwidth by unbiased least-squares cross-validation.
+///
+/// The objective minimised is an unbiased estimate of the integrated squared
+/// error of the kernel density estimation, up to a constant:
+///
+/// LSCV(h) = integral(f_h^2) - 2 * sum(f_h,-i(x_i)) / n
+///
+/// Where f_h,-i is the kernel density estimation with sample x_i left out.
+///
+/// # Panics
+///
+/// The sample set must have more than one element and the bounds must
+/// satisfy 0 < lower < upper.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde;
+/// use kernel_density::kde::NormalKernel;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let cv = kde::cross_validation::least_squares(&samples, &NormalKernel, 0.1, 10.0);
+///
+/// let kde = kde::normal(&samples, cv.bandwidth);
+/// ```
+pub fn least_squares<K: Kernel>(samples: &[f64],
+                                kernel: &K,
+                                lower: f64,
+                                upper: f64)
+                                -> CrossValidation {
+    let length = samples.len();
+    assert!(length > 1);
+
+    let n = length as f64;
+
+    let objective = |bandwidth: f64| {
+        let mut convolution_sum = 0.0;
+        let mut kernel_sum = 0.0;
+
+        // Pairs are symmetric so only visit i < j and double.
+        for i in 0..length {
+            for j in (i + 1)..length {
+                let rescaled = (samples[i] - samples[j]) / bandwidth;
+                convolution_sum += kernel.convolution(rescaled);
+                kernel_sum += kernel.density(rescaled);
+            }
+        }
+
+        // Diagonal terms of the integrated square contribute R(k) each.
+        let integrated_square = (n * kernel.convolution(0.0) + 2.0 * convolution_sum) /
+                                (n * n * bandwidth);
+        let leave_one_out = 2.0 * kernel_sum / ((n - 1.0) * bandwidth);
+
+        integrated_square - 2.0 * leave_one_out / n
+    };
+
+    minimise(objective, lower, upper)
+}
+
+/// Select a bandwidth by leave-one-out maximum likelihood cross-validation.
+///
+/// The objective minimised is the negated mean leave-one-out log-likelihood:
+///
+/// LCV(h) = -sum(log(f_h,-i(x_i))) / n
+///
+/// Where f_h,-i is the kernel density estimation with sample x_i left out.
+/// For compactly supported kernels, bandwidths leaving any sample isolated
+/// have infinite objective.
+///
+/// # Panics
+///
+/// The sample set must have more than one element and the bounds must
+/// satisfy 0 < lower < upper.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde;
+/// use kernel_density::kde::EpanechnikovKernel;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let cv = kde::cross_validation::likelihood(&samples, &EpanechnikovKernel, 0.1, 10.0);
+///
+/// let kde = kde::epanechnikov(&samples, cv.bandwidth);
+/// ```
+pub fn likelihood<K: Kernel>(samples: &[f64],
+                             kernel: &K,
+                             lower: f64,
+                             upper: f64)
+                             -> CrossValidation {
+    let length = samples.len();
+    assert!(length > 1);
+
+    let n = length as f64;
+
+    let objective = |bandwidth: f64| {
+        let mut sums = vec![0.0; length];
+
+        for i in 0..length {
+            for j in (i + 1)..length {
+                let k = kernel.density((samples[i] - samples[j]) / bandwidth);
+                sums[i] += k;
+                sums[j] += k;
+            }
+        }
+
+        let log_likelihood: f64 = sums.iter()
+            .map(|sum| (sum / ((n - 1.0) * bandwidth)).ln())
+            .sum();
+
+        -log_likelihood / n
+    };
+
+    minimise(objective, lower, upper)
+}
+
+/// Minimise an objective over bandwidths in [lower, upper].
+///
+/// The objective is first evaluated on a logarithmic grid to locate the
+/// global minimum approximately, and then refined by golden section search
+/// between the grid neighbours of the best grid point. NaN objective values
+/// are treated as infinite.
+pub(crate) fn minimise<F: Fn(f64) -> f64>(objective: F, lower: f64, upper: f64) -> CrossValidation {
+    assert!(0.0 < lower && lower < upper);
+
+    let objective = |bandwidth: f64| {
+        let value = objective(bandwidth);
+        if value.is_nan() { f64::INFINITY } else { value }
+    };
+
+    let log_ratio = (upper / lower).ln();
+    let curve: Vec<(f64, f64)> = (0..GRID_POINTS)
+        .map(|i| {
+            let bandwidth = lower * (log_ratio * i as f64 / (GRID_POINTS - 1) as f64).exp();
+            (bandwidth, objective(bandwidth))
+        })
+        .collect();
+
+    let mut best = 0;
+    for (i, &(_, value)) in curve.iter().enumerate() {
+        if value < curve[best].1 {
+            best = i;
+        }
+    }
+
+    let (mut bandwidth, mut minimum) = curve[best];
+
+    // Golden section search in log bandwidth between the grid neighbours.
+    let mut low = curve[best.saturating_sub(1)].0.ln();
+    let mut high = curve[(best + 1).min(GRID_POINTS - 1)].0.ln();
+
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut left = high - ratio * (high - low);
+    let mut right = low + ratio * (high - low);
+    let mut left_value = objective(left.exp());
+    let mut right_value = objective(right.exp());
+
+    while high - low > TOLERANCE {
+        if left_value <= right_value {
+            high = right;
+            right = left;
+            right_value = left_value;
+            left = high - ratio * (high - low);
+            left_value = objective(left.exp());
+        } else {
+            low = left;
+            left = right;
+            left_value = right_value;
+            right = low + ratio * (high - low);
+            right_value = objective(right.exp());
+        }
+    }
+
+    for &(candidate, value) in &[(left, left_value), (right, right_value)] {
+        if value < minimum {
+            bandwidth = candidate.exp();
+            minimum = value;
+        }
+    }
+
+    CrossValidation {
+        bandwidth,
+        objective: minimum,
+        curve,
+    }
+}
@@ -1,67 +1,93 @@
 //! Epanechnikov kernel density estimation functions.
 
-use density::Density;
+extern crate rand;
 
-pub struct EpanechnikovKernelDensityEstimation {
-    pub samples: Vec<f64>,
-    pub bandwidth: f64,
-}
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+use self::rand::Rng;
+
+/// The Epanechnikov kernel.
+///
+/// k(x) = 3 * (1 - x^2) / 4 for abs(x) <= 1 and 0 otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct EpanechnikovKernel;
 
-impl Density for EpanechnikovKernelDensityEstimation {
-    /// Calculate a value of the kernel density function for a given value.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// extern crate kernel_density;
-    ///
-    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
-    /// let bandwidth = 0.1;
-    /// let kde = kernel_density::kde::epanechnikov(&samples, bandwidth);
-    ///
-    /// assert_eq!(kde.density(4.0), 0.75);
-    /// ```
+impl Kernel for EpanechnikovKernel {
     fn density(&self, x: f64) -> f64 {
-        let length = self.samples.len();
+        if x.abs() <= 1.0 {
+            0.75 * (1.0 - x.powi(2))
+        } else {
+            0.0
+        }
+    }
 
-        let mut sum = 0.0;
-        for sample in &self.samples {
-            let rescaled: f64 = (x - sample) / self.bandwidth;
-            if rescaled.abs() <= 1.0 {
-                sum += 1.0 - rescaled.powi(2);
-            }
+    fn cdf(&self, x: f64) -> f64 {
+        if x >= 1.0 {
+            1.0
+        } else if x > -1.0 {
+            0.5 + 0.25 * (3.0 * x - x.powi(3))
+        } else {
+            0.0
         }
+    }
 
-        0.75 * sum / (length as f64 * self.bandwidth)
+    fn derivative(&self, x: f64) -> f64 {
+        if x.abs() < 1.0 {
+            -1.5 * x
+        } else {
+            0.0
+        }
     }
 
-    /// Calculate a value of the cumulative density function for this kernel
-    /// density estimation.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// extern crate kernel_density;
-    ///
-    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
-    /// let bandwidth = 0.1;
-    /// let kde = kernel_density::kde::epanechnikov(&samples, bandwidth);
-    ///
-    /// assert_eq!(kde.cdf(0.1), 0.1);
-    /// ```
-    fn cdf(&self, x: f64) -> f64 {
-        let length = self.samples.len();
+    fn support(&self) -> f64 {
+        1.0
+    }
 
-        let mut sum = 0.0;
-        for sample in &self.samples {
-            let rescaled: f64 = (x - sample) / self.bandwidth;
-            if rescaled >= 1.0 {
-                sum += 1.0;
-            } else if rescaled > -1.0 {
-                sum += 0.5 + 0.25 * (3.0 * rescaled - rescaled.powi(3));
-            }
+    fn second_moment(&self) -> f64 {
+        0.2
+    }
+
+    fn roughness(&self) -> f64 {
+        0.6
+    }
+
+    fn convolution(&self, x: f64) -> f64 {
+        let x = x.abs();
+
+        if x <= 2.0 {
+            3.0 * (2.0 - x).powi(3) * (x.powi(2) + 6.0 * x + 4.0) / 160.0
+        } else {
+            0.0
         }
+    }
+
+    /// Devroye's method: of three independent Uniform(-1, 1) values, take the
+    /// second if the third has the largest magnitude and the third otherwise.
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let u1 = 2.0 * rng.next_f64() - 1.0;
+        let u2 = 2.0 * rng.next_f64() - 1.0;
+        let u3 = 2.0 * rng.next_f64() - 1.0;
 
-        sum / length as f64
+        if u3.abs() >= u2.abs() && u3.abs() >= u1.abs() {
+            u2
+        } else {
+            u3
+        }
     }
 }
+
+/// Kernel density estimation using the Epanechnikov kernel.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::epanechnikov(&samples, bandwidth);
+///
+/// assert_eq!(kde.density(4.0), 0.75);
+/// assert_eq!(kde.cdf(0.1), 0.1);
+/// ```
+pub type EpanechnikovKernelDensityEstimation = KernelDensityEstimation<EpanechnikovKernel>;
@@ -0,0 +1,601 @@
+//! Kernel density estimation for an arbitrary kernel.
+
+extern crate rand;
+
+use density;
+use density::Density;
+use kde::bandwidth;
+use kde::kernel::{effective_support, Kernel};
+use self::rand::Rng;
+
+/// Kernel density estimation for a sample, bandwidth and kernel.
+///
+/// The samples are held sorted in increasing order, with their weights in the
+/// same order. For kernels with compact support, the density and cumulative
+/// density are evaluated by binary search for the samples within the kernel
+/// support of the point, so each value costs O(log n + k) for k samples in
+/// the support rather than O(n).
+pub struct KernelDensityEstimation<K: Kernel> {
+    samples: Vec<f64>,
+    weights: Vec<f64>,
+    bandwidth: f64,
+    kernel: K,
+    cumulative_weights: Vec<f64>,
+}
+
+impl<K: Kernel> KernelDensityEstimation<K> {
+    /// Construct a new kernel density estimation for a given sample, bandwidth
+    /// and kernel.
+    ///
+    /// # Panics
+    ///
+    /// Bandwidth must be greater than zero, the sample set must be non-empty
+    /// and no sample may be NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let bandwidth = 0.1;
+    /// let kde = KernelDensityEstimation::new(&samples, bandwidth, NormalKernel);
+    /// ```
+    pub fn new(samples: &[f64], bandwidth: f64, kernel: K) -> KernelDensityEstimation<K> {
+        assert!(bandwidth > 0.0);
+
+        let length = samples.len();
+        assert!(length > 0);
+        assert!(samples.iter().all(|sample| !sample.is_nan()));
+
+        KernelDensityEstimation::sorted(samples, &vec![1.0; length], bandwidth, kernel)
+    }
+
+    /// Construct a new kernel density estimation for a given weighted sample,
+    /// bandwidth and kernel.
+    ///
+    /// Each sample contributes to the density and the cumulative density in
+    /// proportion to its weight. Weights are normalised by their total so,
+    /// for instance, sample counts can be used directly as weights.
+    ///
+    /// # Panics
+    ///
+    /// Bandwidth must be greater than zero, the sample set must be non-empty
+    /// and no sample may be NaN.
+    ///
+    /// There must be one weight for each sample, weights must be finite and
+    /// non-negative, and at least one weight must be greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, UniformKernel};
+    ///
+    /// let samples = vec!(0.0, 1.0);
+    /// let counts = vec!(3.0, 1.0);
+    /// let bandwidth = 0.1;
+    /// let kde = KernelDensityEstimation::weighted(&samples, &counts, bandwidth, UniformKernel);
+    ///
+    /// assert_eq!(kde.cdf(0.5), 0.75);
+    /// ```
+    pub fn weighted(samples: &[f64],
+                    weights: &[f64],
+                    bandwidth: f64,
+                    kernel: K)
+                    -> KernelDensityEstimation<K> {
+        assert!(bandwidth > 0.0);
+
+        let length = samples.len();
+        assert!(length > 0);
+        assert!(samples.iter().all(|sample| !sample.is_nan()));
+        assert!(weights.len() == length);
+
+        assert!(weights.iter().all(|&weight| weight >= 0.0 && weight.is_finite()));
+
+        let total_weight: f64 = weights.iter().sum();
+        assert!(total_weight > 0.0);
+
+        KernelDensityEstimation::sorted(samples, weights, bandwidth, kernel)
+    }
+
+    /// Construct the kernel density estimation of the concatenated weighted
+    /// samples of given kernel density estimations with the same bandwidth,
+    /// for example estimations built on separate shards of a sample.
+    ///
+    /// Sample weights are kept as they are, so unweighted estimations are
+    /// combined in proportion to their numbers of samples. Estimations with
+    /// different bandwidths can be combined with
+    /// `MixtureKernelDensityEstimation` instead.
+    ///
+    /// # Panics
+    ///
+    /// There must be at least one estimation and every estimation must have
+    /// the same bandwidth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, UniformKernel};
+    ///
+    /// let first = KernelDensityEstimation::new(&[0.0, 1.0, 2.0], 0.1, UniformKernel);
+    /// let second = KernelDensityEstimation::new(&[3.0], 0.1, UniformKernel);
+    /// let kde = KernelDensityEstimation::concatenate(&[&first, &second]);
+    ///
+    /// assert_eq!(kde.samples(), &[0.0, 1.0, 2.0, 3.0]);
+    /// assert_eq!(kde.cdf(2.5), 0.75);
+    /// ```
+    pub fn concatenate(estimations: &[&KernelDensityEstimation<K>]) -> KernelDensityEstimation<K>
+        where K: Clone
+    {
+        let length = estimations.len();
+        assert!(length > 0);
+
+        let bandwidth = estimations[0].bandwidth;
+        assert!(estimations.iter().all(|estimation| estimation.bandwidth == bandwidth));
+
+        let mut samples = vec![];
+        let mut weights = vec![];
+        for estimation in estimations {
+            samples.extend_from_slice(estimation.samples());
+            weights.extend_from_slice(estimation.weights());
+        }
+
+        KernelDensityEstimation::sorted(&samples,
+                                        &weights,
+                                        bandwidth,
+                                        estimations[0].kernel.clone())
+    }
+
+    /// Construct with the samples and weights sorted by sample, along with
+    /// the running totals of the sorted weights. The samples must not be NaN.
+    fn sorted(samples: &[f64],
+              weights: &[f64],
+              bandwidth: f64,
+              kernel: K)
+              -> KernelDensityEstimation<K> {
+        let mut pairs: Vec<(f64, f64)> =
+            samples.iter().cloned().zip(weights.iter().cloned()).collect();
+        pairs.sort_by(|&(x_1, _), &(x_2, _)| x_1.partial_cmp(&x_2).unwrap());
+
+        let (samples, weights): (Vec<f64>, Vec<f64>) = pairs.into_iter().unzip();
+
+        let mut cumulative_weights = Vec::with_capacity(weights.len() + 1);
+        let mut total_weight = 0.0;
+        cumulative_weights.push(total_weight);
+        for weight in &weights {
+            total_weight += weight;
+            cumulative_weights.push(total_weight);
+        }
+
+        KernelDensityEstimation {
+            samples,
+            weights,
+            bandwidth,
+            kernel,
+            cumulative_weights,
+        }
+    }
+
+    /// Find the index range of the sorted samples within the kernel support
+    /// of a given point. Samples before the range have kernel cdf one and
+    /// samples after it have kernel cdf zero.
+    ///
+    /// The bounds are found on the rescaled distances used to evaluate the
+    /// kernel, which are monotone in the sample, so the range agrees exactly
+    /// with the kernel support.
+    fn support_range(&self, x: f64) -> (usize, usize) {
+        let support = self.kernel.support();
+        if !support.is_finite() {
+            return (0, self.samples.len());
+        }
+
+        let bandwidth = self.bandwidth;
+        let start = self.samples.partition_point(|&sample| (x - sample) / bandwidth > support);
+        let end = self.samples.partition_point(|&sample| (x - sample) / bandwidth >= -support);
+
+        (start, end.max(start))
+    }
+
+    /// Calculate the log density with the log-sum-exp method, optionally
+    /// leaving out the sample at a given index in sorted order. The remaining
+    /// weights are renormalised.
+    pub(crate) fn log_density_excluding(&self, x: f64, excluded: Option<usize>) -> f64 {
+        let (start, end) = self.support_range(x);
+
+        let terms: Vec<f64> = (start..end)
+            .filter(|&i| Some(i) != excluded)
+            .map(|i| {
+                self.weights[i].ln() +
+                self.kernel.log_density((x - self.samples[i]) / self.bandwidth)
+            })
+            .collect();
+
+        let max = terms.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        if max == f64::NEG_INFINITY {
+            return f64::NEG_INFINITY;
+        }
+
+        let sum: f64 = terms.iter().map(|term| (term - max).exp()).sum();
+
+        let mut total_weight = self.cumulative_weights[self.samples.len()];
+        if let Some(i) = excluded {
+            total_weight -= self.weights[i];
+        }
+
+        max + sum.ln() - (total_weight * self.bandwidth).ln()
+    }
+
+    /// Calculate the density from the samples in a given index range.
+    fn density_over(&self, x: f64, start: usize, end: usize) -> f64 {
+        let mut sum = 0.0;
+        for (sample, weight) in self.samples[start..end].iter().zip(&self.weights[start..end]) {
+            sum += weight * self.kernel.density((x - sample) / self.bandwidth);
+        }
+
+        let total_weight = self.cumulative_weights[self.samples.len()];
+        sum / (total_weight * self.bandwidth)
+    }
+
+    /// Calculate the cumulative density with samples before a given index
+    /// range contributing their whole weight.
+    fn cdf_over(&self, x: f64, start: usize, end: usize) -> f64 {
+        let mut sum = self.cumulative_weights[start];
+        for (sample, weight) in self.samples[start..end].iter().zip(&self.weights[start..end]) {
+            sum += weight * self.kernel.cdf((x - sample) / self.bandwidth);
+        }
+
+        let total_weight = self.cumulative_weights[self.samples.len()];
+        sum / total_weight
+    }
+
+    /// Evaluate a function of the kernel support index range for a slice of
+    /// values.
+    ///
+    /// For compact kernels and nondecreasing values, the support range is
+    /// swept forward through the sorted samples instead of binary searched
+    /// for each value, costing O(n + m) in total plus the samples in the
+    /// supports. The ranges are identical to those from `support_range`.
+    fn sweep<F: Fn(f64, usize, usize) -> f64>(&self, xs: &[f64], f: F) -> Vec<f64> {
+        let support = self.kernel.support();
+        let nondecreasing = xs.windows(2).all(|pair| pair[0] <= pair[1]);
+
+        if !support.is_finite() || !nondecreasing {
+            return xs.iter()
+                .map(|&x| {
+                    let (start, end) = self.support_range(x);
+                    f(x, start, end)
+                })
+                .collect();
+        }
+
+        let length = self.samples.len();
+        let bandwidth = self.bandwidth;
+
+        let mut start = 0;
+        let mut end = 0;
+
+        xs.iter()
+            .map(|&x| {
+                while start < length && (x - self.samples[start]) / bandwidth > support {
+                    start += 1;
+                }
+
+                end = end.max(start);
+                while end < length && (x - self.samples[end]) / bandwidth >= -support {
+                    end += 1;
+                }
+
+                f(x, start, end)
+            })
+            .collect()
+    }
+
+    /// Return the samples in increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+    ///
+    /// let kde = KernelDensityEstimation::new(&[2.0, 0.0, 1.0], 0.1, NormalKernel);
+    ///
+    /// assert_eq!(kde.samples(), &[0.0, 1.0, 2.0]);
+    /// ```
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+
+    /// Return the sample weights, in the same order as `samples`. The weights
+    /// are one for an unweighted kernel density estimation.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Return the bandwidth of the estimation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+    ///
+    /// let kde = KernelDensityEstimation::new(&[0.0, 1.0], 0.1, NormalKernel);
+    ///
+    /// assert_eq!(kde.bandwidth(), 0.1);
+    /// ```
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Return the kernel of the estimation.
+    pub fn kernel(&self) -> &K {
+        &self.kernel
+    }
+
+    /// Consume the estimation and return its kernel.
+    pub(crate) fn into_kernel(self) -> K {
+        self.kernel
+    }
+
+    /// Calculate the effective sample size of the weighted sample.
+    ///
+    /// This is the number of equally weighted samples carrying the same
+    /// information as the weighted sample, (sum w)^2 / sum(w^2). It is the
+    /// sample length for an unweighted kernel density estimation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+    ///
+    /// let samples = vec!(0.0, 1.0, 2.0, 3.0);
+    /// let weights = vec!(1.0, 1.0, 0.0, 0.0);
+    /// let kde = KernelDensityEstimation::weighted(&samples, &weights, 0.1, NormalKernel);
+    ///
+    /// assert_eq!(kde.effective_sample_size(), 2.0);
+    /// ```
+    pub fn effective_sample_size(&self) -> f64 {
+        bandwidth::effective_sample_size(&self.weights)
+    }
+
+    /// Choose the index of a sample at random with probability in proportion
+    /// to its weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    /// extern crate rand;
+    ///
+    /// use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+    ///
+    /// let samples = vec!(0.0, 1.0, 2.0);
+    /// let weights = vec!(0.0, 1.0, 0.0);
+    /// let kde = KernelDensityEstimation::weighted(&samples, &weights, 0.1, NormalKernel);
+    ///
+    /// assert_eq!(kde.choose(&mut rand::thread_rng()), 1);
+    /// ```
+    pub fn choose(&self, rng: &mut dyn Rng) -> usize {
+        let length = self.samples.len();
+        let target = rng.next_f64() * self.cumulative_weights[length];
+
+        // The first sample whose running total passes the target, which
+        // always has positive weight.
+        let index = self.cumulative_weights[1..].partition_point(|&total| total <= target);
+        index.min(length - 1)
+    }
+}
+
+impl<K: Kernel> Density for KernelDensityEstimation<K> {
+    /// Calculate a value of the kernel density function for a given value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, UniformKernel};
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let bandwidth = 0.1;
+    /// let kde = KernelDensityEstimation::new(&samples, bandwidth, UniformKernel);
+    ///
+    /// assert_eq!(kde.density(4.0), 0.5);
+    /// ```
+    fn density(&self, x: f64) -> f64 {
+        let (start, end) = self.support_range(x);
+        self.density_over(x, start, end)
+    }
+
+    /// Calculate the natural logarithm of the kernel density function for a
+    /// given value.
+    ///
+    /// The kernel logarithms are combined with the log-sum-exp method:
+    ///
+    /// log(f(x)) = m + log(sum(w_i * exp(log(k(u_i)) - m))) - log(W * h)
+    ///
+    /// Where u_i = (x - x_i) / h, W is the total weight and m is the largest
+    /// of log(w_i) + log(k(u_i)). For kernels with a closed form log density,
+    /// like the Normal kernel, this stays finite far from the samples where
+    /// the density itself underflows to zero. The result is negative infinity
+    /// outside the support of a compact kernel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+    ///
+    /// let samples = vec!(0.0, 1.0);
+    /// let kde = KernelDensityEstimation::new(&samples, 0.1, NormalKernel);
+    ///
+    /// assert_eq!(kde.density(10.0), 0.0);
+    /// assert!(kde.log_density(10.0).is_finite());
+    /// ```
+    fn log_density(&self, x: f64) -> f64 {
+        self.log_density_excluding(x, None)
+    }
+
+    /// Calculate a value of the cumulative density function for this kernel
+    /// density estimation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, UniformKernel};
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let bandwidth = 0.1;
+    /// let kde = KernelDensityEstimation::new(&samples, bandwidth, UniformKernel);
+    ///
+    /// assert_eq!(kde.cdf(0.1), 0.1);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        let (start, end) = self.support_range(x);
+        self.cdf_over(x, start, end)
+    }
+
+    /// Calculate the cumulative density function for a slice of values.
+    ///
+    /// For compact kernels, nondecreasing values are evaluated in a single
+    /// sweep through the sorted samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::{self, Density};
+    /// use kernel_density::kde::{KernelDensityEstimation, EpanechnikovKernel};
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let kde = KernelDensityEstimation::new(&samples, 0.5, EpanechnikovKernel);
+    ///
+    /// let xs = density::linspace(-1.0, 10.0, 1101);
+    /// let cdfs = kde.cdfs(&xs);
+    ///
+    /// assert_eq!(cdfs[1100], 1.0);
+    /// ```
+    fn cdfs(&self, xs: &[f64]) -> Vec<f64> {
+        self.sweep(xs, |x, start, end| self.cdf_over(x, start, end))
+    }
+
+    /// Calculate the kernel density function for a slice of values.
+    ///
+    /// For compact kernels, nondecreasing values are evaluated in a single
+    /// sweep through the sorted samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::{self, Density};
+    /// use kernel_density::kde::{KernelDensityEstimation, UniformKernel};
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let kde = KernelDensityEstimation::new(&samples, 0.1, UniformKernel);
+    ///
+    /// let densities = kde.densities(&[0.0, 0.5, 4.0]);
+    ///
+    /// assert_eq!(densities, vec!(0.5, 0.0, 0.5));
+    /// ```
+    fn densities(&self, xs: &[f64]) -> Vec<f64> {
+        self.sweep(xs, |x, start, end| self.density_over(x, start, end))
+    }
+
+    /// Calculate the quantile function for this kernel density estimation.
+    ///
+    /// The quantile is bracketed by the sample minimum and maximum extended
+    /// by the kernel support, or the effective kernel support for kernels
+    /// with unbounded support, and found with `density::invert_cdf`.
+    ///
+    /// # Panics
+    ///
+    /// The probability must satisfy 0 < p < 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, UniformKernel};
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let kde = KernelDensityEstimation::new(&samples, 0.1, UniformKernel);
+    ///
+    /// assert!((kde.quantile(0.05) - 0.0).abs() < 1e-12);
+    /// ```
+    fn quantile(&self, p: f64) -> f64 {
+        assert!(0.0 < p && p < 1.0);
+
+        let radius = effective_support(&self.kernel) * self.bandwidth;
+        let min = self.samples[0];
+        let max = self.samples[self.samples.len() - 1];
+
+        // Kernels with unbounded support have mass beyond the effective
+        // support for extreme probabilities.
+        let mut offset = radius;
+        while self.cdf(min - offset) >= p && (min - offset).is_finite() {
+            offset *= 2.0;
+        }
+        let lower = min - offset;
+
+        let mut offset = radius;
+        while self.cdf(max + offset) < p && (max + offset).is_finite() {
+            offset *= 2.0;
+        }
+        let upper = max + offset;
+
+        density::invert_cdf(self, p, lower, upper)
+    }
+
+    /// Draw a random value from this kernel density estimation.
+    ///
+    /// A sample is chosen in proportion to its weight and kernel noise scaled
+    /// by the bandwidth is added to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    /// extern crate rand;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, UniformKernel};
+    /// use rand::{SeedableRng, StdRng};
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let kde = KernelDensityEstimation::new(&samples, 0.1, UniformKernel);
+    ///
+    /// let seed: &[_] = &[1, 2, 3, 4];
+    /// let mut rng: StdRng = SeedableRng::from_seed(seed);
+    /// let x = kde.sample(&mut rng);
+    ///
+    /// assert!(kde.density(x) > 0.0);
+    /// ```
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let index = self.choose(rng);
+        self.samples[index] + self.bandwidth * self.kernel.sample(rng)
+    }
+}
@@ -0,0 +1,272 @@
+//! Improved fast Gauss transform for the Normal kernel density estimation.
+//!
+//! The Normal kernel has infinite support, so exact evaluation costs O(n) per
+//! point. The improved fast Gauss transform groups the samples into clusters
+//! of nearby samples and replaces each cluster by a truncated Taylor series
+//! about its centre. Evaluating m points then costs O((n + m * c) * p) for c
+//! clusters within range of each point and p series terms, independently of
+//! the number of samples in each cluster.
+//!
+//! With scale d = sqrt(2) * h, each Gaussian factors about a cluster centre c
+//! as:
+//!
+//! exp(-((x - s)/d)^2) =
+//!     exp(-((x - c)/d)^2) * exp(-((s - c)/d)^2) * exp(2 * (x - c) * (s - c) / d^2)
+//!
+//! The last factor is expanded as a Taylor series. Clusters further than a
+//! cutoff radius from the evaluation point are ignored. The truncation order
+//! and cutoff radius are chosen so that the absolute error of the density is
+//! at most the error requested.
+//!
+//! An error bound relative to the density at each point is not supported, as
+//! the density far from the samples falls below any fixed truncation error.
+//! `FastGaussTransform::relative` instead bounds the error relative to the
+//! maximum of the density.
+
+use kde::normal::NormalKernelDensityEstimation;
+use std::f64::consts::PI;
+
+/// Cluster radius in units of the scale sqrt(2) * h.
+const CLUSTER_RADIUS: f64 = 0.5;
+
+/// Approximate evaluator for a Normal kernel density estimation.
+pub struct FastGaussTransform {
+    /// Scale of the Gaussian, sqrt(2) * h.
+    scale: f64,
+    /// Number of Taylor series terms.
+    order: usize,
+    /// Clusters further than this from a point are ignored.
+    cutoff: f64,
+    /// Cluster centres in increasing order.
+    centres: Vec<f64>,
+    /// Taylor series coefficients, order values for each cluster.
+    coefficients: Vec<f64>,
+    /// Normalising factor for the density, 1 / (total weight * h * sqrt(2 * pi)).
+    normalisation: f64,
+}
+
+impl FastGaussTransform {
+    /// Construct a fast Gauss transform for a Normal kernel density
+    /// estimation with a given absolute error bound on the density.
+    ///
+    /// # Panics
+    ///
+    /// The error must be greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+    /// use kernel_density::kde::fast_gauss::FastGaussTransform;
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let kde = KernelDensityEstimation::new(&samples, 0.5, NormalKernel);
+    /// let transform = FastGaussTransform::new(&kde, 1e-9);
+    ///
+    /// assert!((transform.density(4.2) - kde.density(4.2)).abs() < 1e-9);
+    /// ```
+    pub fn new(estimation: &NormalKernelDensityEstimation, error: f64) -> FastGaussTransform {
+        assert!(error > 0.0);
+
+        FastGaussTransform::bounded(estimation, error)
+    }
+
+    /// Construct a fast Gauss transform for a Normal kernel density
+    /// estimation with an error bound relative to the maximum of the density.
+    ///
+    /// The absolute error of the density is at most the error times a lower
+    /// bound on the maximum density. The lower bound is the density at the
+    /// centre of the cluster with the most weight from that cluster alone,
+    /// so the bound is conservative by a small factor. The error relative to
+    /// the density at any single point is not bounded.
+    ///
+    /// # Panics
+    ///
+    /// The error must be greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+    /// use kernel_density::kde::fast_gauss::FastGaussTransform;
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let kde = KernelDensityEstimation::new(&samples, 0.5, NormalKernel);
+    /// let transform = FastGaussTransform::relative(&kde, 1e-6);
+    ///
+    /// assert!((transform.density(4.2) - kde.density(4.2)).abs() < 1e-6 * kde.density(4.0));
+    /// ```
+    pub fn relative(estimation: &NormalKernelDensityEstimation,
+                    error: f64)
+                    -> FastGaussTransform {
+        assert!(error > 0.0);
+
+        let bandwidth = estimation.bandwidth();
+        let scale = 2f64.sqrt() * bandwidth;
+        let radius = CLUSTER_RADIUS * scale;
+        let total_weight: f64 = estimation.weights().iter().sum();
+        let normalisation = 1.0 / (total_weight * bandwidth * (2.0 * PI).sqrt());
+
+        // Every sample in a cluster is within the cluster radius of the
+        // centre, contributing at least exp(-(radius/d)^2) there.
+        let origin = estimation.samples()[0];
+        let mut centre = origin;
+        let mut cluster_weight = 0.0;
+        let mut max_cluster_weight: f64 = 0.0;
+
+        for (&sample, &weight) in estimation.samples().iter().zip(estimation.weights()) {
+            let sample_centre = cluster_centre(origin, radius, sample);
+            if sample_centre != centre {
+                centre = sample_centre;
+                cluster_weight = 0.0;
+            }
+
+            cluster_weight += weight;
+            max_cluster_weight = max_cluster_weight.max(cluster_weight);
+        }
+
+        let peak = max_cluster_weight * normalisation * (-CLUSTER_RADIUS.powi(2)).exp();
+
+        FastGaussTransform::bounded(estimation, error * peak)
+    }
+
+    /// Construct with a given absolute error bound on the density.
+    fn bounded(estimation: &NormalKernelDensityEstimation, error: f64) -> FastGaussTransform {
+        let bandwidth = estimation.bandwidth();
+        let scale = 2f64.sqrt() * bandwidth;
+        let total_weight: f64 = estimation.weights().iter().sum();
+        let normalisation = 1.0 / (total_weight * bandwidth * (2.0 * PI).sqrt());
+
+        // Allowed error in the unnormalised Gaussian for each unit of weight.
+        let source_error = (error / normalisation / total_weight).min(1.0);
+
+        // Sources in ignored clusters are at least cutoff - cluster radius
+        // away, contributing at most exp(-((cutoff - radius)/d)^2).
+        let radius = CLUSTER_RADIUS * scale;
+        let cutoff = radius + scale * (-source_error.ln()).sqrt();
+
+        // The Taylor series remainder after p terms is at most
+        // (2 * radius * cutoff / d^2)^p / p!.
+        let ratio = 2.0 * radius * cutoff / scale.powi(2);
+        let mut order = 0;
+        let mut remainder = 1.0;
+        while remainder > source_error {
+            order += 1;
+            remainder *= ratio / order as f64;
+        }
+        let order = order.max(1);
+
+        // Samples are sorted, so clusters are consecutive runs of samples.
+        let origin = estimation.samples()[0];
+        let mut centres: Vec<f64> = vec![];
+        let mut coefficients: Vec<f64> = vec![];
+
+        for (&sample, &weight) in estimation.samples().iter().zip(estimation.weights()) {
+            let centre = cluster_centre(origin, radius, sample);
+
+            if centres.last() != Some(&centre) {
+                centres.push(centre);
+                coefficients.extend(vec![0.0; order]);
+            }
+
+            let offset = coefficients.len() - order;
+            let distance = (sample - centre) / scale;
+            let mut term = weight * (-distance.powi(2)).exp();
+            for k in 0..order {
+                coefficients[offset + k] += term;
+                term *= 2.0 * distance / (k + 1) as f64;
+            }
+        }
+
+        FastGaussTransform {
+            scale,
+            order,
+            cutoff,
+            centres,
+            coefficients,
+            normalisation,
+        }
+    }
+
+    /// Calculate the approximate value of the kernel density function for a
+    /// given value.
+    pub fn density(&self, x: f64) -> f64 {
+        let start = self.centres.partition_point(|&centre| centre < x - self.cutoff);
+        let end = self.centres.partition_point(|&centre| centre <= x + self.cutoff);
+
+        let mut sum = 0.0;
+        for cluster in start..end {
+            let distance = (x - self.centres[cluster]) / self.scale;
+            let coefficients = &self.coefficients[cluster * self.order..(cluster + 1) * self.order];
+
+            // Horner's rule for the Taylor series in the distance.
+            let mut series = 0.0;
+            for coefficient in coefficients.iter().rev() {
+                series = series * distance + coefficient;
+            }
+
+            sum += (-distance.powi(2)).exp() * series;
+        }
+
+        sum * self.normalisation
+    }
+
+    /// Calculate the approximate values of the kernel density function for a
+    /// slice of values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+    /// use kernel_density::kde::fast_gauss::FastGaussTransform;
+    ///
+    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+    /// let kde = KernelDensityEstimation::new(&samples, 0.5, NormalKernel);
+    /// let transform = FastGaussTransform::new(&kde, 1e-6);
+    ///
+    /// let densities = transform.densities(&[0.0, 0.5, 1.0]);
+    /// assert_eq!(densities.len(), 3);
+    /// ```
+    pub fn densities(&self, xs: &[f64]) -> Vec<f64> {
+        xs.iter().map(|&x| self.density(x)).collect()
+    }
+}
+
+/// Centre of the cluster of a sample, for clusters of a given radius tiling
+/// the line from an origin.
+fn cluster_centre(origin: f64, radius: f64, sample: f64) -> f64 {
+    let index = ((sample - origin) / (2.0 * radius)).floor();
+    origin + (index + 0.5) * 2.0 * radius
+}
+
+/// Calculate the values of a Normal kernel density estimation for a slice of
+/// values with a given absolute error bound, using the improved fast Gauss
+/// transform.
+///
+/// # Panics
+///
+/// The error must be greater than zero.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::{fast_gauss, KernelDensityEstimation, NormalKernel};
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let kde = KernelDensityEstimation::new(&samples, 0.5, NormalKernel);
+///
+/// let densities = fast_gauss::densities(&kde, &[0.0, 0.5, 1.0], 1e-6);
+/// ```
+pub fn densities(estimation: &NormalKernelDensityEstimation, xs: &[f64], error: f64) -> Vec<f64> {
+    FastGaussTransform::new(estimation, error).densities(xs)
+}
@@ -0,0 +1,138 @@
+//! Kernel function definitions.
+
+extern crate rand;
+
+use self::rand::Rng;
+use std::f64;
+
+/// Number of Simpson's rule intervals used for numerical kernel integrals.
+const INTEGRATION_INTERVALS: usize = 2000;
+
+/// Number of bisections used when sampling by inverting the kernel cdf.
+const SAMPLE_BISECTIONS: usize = 64;
+
+/// Step used for numerical kernel derivatives.
+const DERIVATIVE_STEP: f64 = 1e-6;
+
+/// A kernel for kernel density estimation.
+///
+/// A kernel is a symmetric probability density function k with zero mean and
+/// finite second moment. Implementations supply the kernel density and its
+/// cumulative distribution function, along with the constants required for
+/// bandwidth selection.
+pub trait Kernel {
+    /// Calculate the value of the kernel at a given point.
+    fn density(&self, x: f64) -> f64;
+
+    /// Calculate the integral of the kernel from negative infinity to a
+    /// given point.
+    fn cdf(&self, x: f64) -> f64;
+
+    /// Calculate the natural logarithm of the kernel at a given point.
+    ///
+    /// The default implementation takes the logarithm of the kernel density,
+    /// which is negative infinity where the density underflows. Kernels with
+    /// unbounded support should override this with a closed form.
+    fn log_density(&self, x: f64) -> f64 {
+        self.density(x).ln()
+    }
+
+    /// Calculate the derivative of the kernel at a given point.
+    ///
+    /// The default implementation is a numerical central difference. Kernels
+    /// with a closed form derivative should override this.
+    fn derivative(&self, x: f64) -> f64 {
+        let step = DERIVATIVE_STEP * x.abs().max(1.0);
+        (self.density(x + step) - self.density(x - step)) / (2.0 * step)
+    }
+
+    /// Return the radius of the kernel support, i.e. the smallest r with
+    /// k(x) = 0 for abs(x) > r. Kernels with unbounded support return
+    /// infinity.
+    fn support(&self) -> f64;
+
+    /// Return the second moment of the kernel, the integral of x^2 * k(x).
+    fn second_moment(&self) -> f64;
+
+    /// Return the roughness of the kernel, the integral of k(x)^2.
+    fn roughness(&self) -> f64;
+
+    /// Return the canonical bandwidth of the kernel,
+    /// (roughness / second_moment^2)^(1/5).
+    ///
+    /// Kernel density estimations using kernels with bandwidths in proportion
+    /// to their canonical bandwidths have equivalent smoothness.
+    fn canonical_bandwidth(&self) -> f64 {
+        (self.roughness() / self.second_moment().powi(2)).powf(0.2)
+    }
+
+    /// Calculate the convolution of the kernel with itself at a given point,
+    /// the integral of k(t) * k(x - t) over t.
+    ///
+    /// The default implementation integrates numerically using Simpson's
+    /// rule over the overlapping kernel supports. Kernels with a closed form
+    /// convolution should override this.
+    fn convolution(&self, x: f64) -> f64 {
+        let radius = effective_support(self);
+
+        let low = (-radius).max(x - radius);
+        let high = radius.min(x + radius);
+
+        if low >= high {
+            return 0.0;
+        }
+
+        simpson(|t| self.density(t) * self.density(x - t), low, high)
+    }
+
+    /// Draw a random value with the kernel as its density.
+    ///
+    /// The default implementation inverts the kernel cdf by bisection over
+    /// the effective kernel support. Kernels with a direct sampling method
+    /// should override this.
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let u = rng.next_f64();
+
+        let mut low = -effective_support(self);
+        let mut high = -low;
+        for _ in 0..SAMPLE_BISECTIONS {
+            let middle = 0.5 * (low + high);
+            if self.cdf(middle) < u {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        0.5 * (low + high)
+    }
+}
+
+/// Support radius of a kernel, or for unbounded kernels the radius outside of
+/// which the kernel has negligible mass.
+pub fn effective_support<K: Kernel + ?Sized>(kernel: &K) -> f64 {
+    let support = kernel.support();
+    if support.is_finite() {
+        return support;
+    }
+
+    let mut radius = 1.0;
+    while kernel.cdf(-radius) > 1e-16 && radius < 1e6 {
+        radius *= 2.0;
+    }
+
+    radius
+}
+
+/// Integrate a function over [low, high] using composite Simpson's rule.
+fn simpson<F: Fn(f64) -> f64>(f: F, low: f64, high: f64) -> f64 {
+    let step = (high - low) / INTEGRATION_INTERVALS as f64;
+
+    let mut sum = f(low) + f(high);
+    for i in 1..INTEGRATION_INTERVALS {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * f(low + i as f64 * step);
+    }
+
+    sum * step / 3.0
+}
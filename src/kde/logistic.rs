@@ -0,0 +1,48 @@
+//! Logistic kernel density estimation functions.
+
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+use std::f64::consts::PI;
+use std::f64;
+
+/// The Logistic kernel.
+///
+/// k(x) = 1 / (exp(x) + 2 + exp(-x))
+#[derive(Clone, Copy, Debug)]
+pub struct LogisticKernel;
+
+impl Kernel for LogisticKernel {
+    fn density(&self, x: f64) -> f64 {
+        // Symmetric form avoiding overflow of exp for large abs(x).
+        let exponential = (-x.abs()).exp();
+        exponential / (1.0 + exponential).powi(2)
+    }
+
+    fn log_density(&self, x: f64) -> f64 {
+        -x.abs() - 2.0 * (-x.abs()).exp().ln_1p()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x >= 0.0 {
+            1.0 / (1.0 + (-x).exp())
+        } else {
+            let exponential = x.exp();
+            exponential / (1.0 + exponential)
+        }
+    }
+
+    fn support(&self) -> f64 {
+        f64::INFINITY
+    }
+
+    fn second_moment(&self) -> f64 {
+        PI.powi(2) / 3.0
+    }
+
+    fn roughness(&self) -> f64 {
+        1.0 / 6.0
+    }
+}
+
+/// Kernel density estimation using the Logistic kernel.
+pub type LogisticKernelDensityEstimation = KernelDensityEstimation<LogisticKernel>;
@@ -0,0 +1,242 @@
+//! Mixtures of kernel density estimations.
+
+extern crate rand;
+
+use density::Density;
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+use self::rand::Rng;
+
+/// Weighted mixture of kernel density estimations with the same kernel, for
+/// example estimations built on separate shards of a sample.
+///
+/// The mixture density is:
+///
+/// f(x) = sum(p_j * f_j(x)) / sum(p_j)
+///
+/// Where f_j are the component estimations and p_j their mixture weights.
+/// The components may have different bandwidths and sample weights. With
+/// each mixture weight the total sample weight of its component, as from
+/// `pooled`, the mixture is the kernel density estimation of the
+/// concatenated samples, each keeping the bandwidth of its component.
+pub struct MixtureKernelDensityEstimation<K: Kernel> {
+    components: Vec<KernelDensityEstimation<K>>,
+    /// Mixture weights, normalised by their total when evaluated.
+    weights: Vec<f64>,
+}
+
+impl<K: Kernel> MixtureKernelDensityEstimation<K> {
+    /// Construct a new mixture of given kernel density estimations with
+    /// given mixture weights.
+    ///
+    /// # Panics
+    ///
+    /// There must be at least one component and one weight for each
+    /// component. Weights must be finite and non-negative, and at least one
+    /// weight must be greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, MixtureKernelDensityEstimation,
+    ///                           UniformKernel};
+    ///
+    /// let low = KernelDensityEstimation::new(&[0.0], 1.0, UniformKernel);
+    /// let high = KernelDensityEstimation::new(&[10.0], 2.0, UniformKernel);
+    /// let kde = MixtureKernelDensityEstimation::new(vec!(low, high), &[3.0, 1.0]);
+    ///
+    /// assert_eq!(kde.density(0.0), 0.375);
+    /// assert_eq!(kde.cdf(5.0), 0.75);
+    /// ```
+    pub fn new(components: Vec<KernelDensityEstimation<K>>,
+               weights: &[f64])
+               -> MixtureKernelDensityEstimation<K> {
+        let length = components.len();
+        assert!(length > 0);
+        assert!(weights.len() == length);
+
+        assert!(weights.iter().all(|&weight| weight >= 0.0 && weight.is_finite()));
+
+        let total_weight: f64 = weights.iter().sum();
+        assert!(total_weight > 0.0);
+
+        MixtureKernelDensityEstimation {
+            components,
+            weights: weights.to_vec(),
+        }
+    }
+
+    /// Construct the mixture of given kernel density estimations weighted by
+    /// their total sample weights, so each sample counts equally whichever
+    /// component it belongs to. For unweighted estimations, this is the
+    /// number of samples in each.
+    ///
+    /// # Panics
+    ///
+    /// There must be at least one component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, MixtureKernelDensityEstimation,
+    ///                           NormalKernel};
+    ///
+    /// let first = KernelDensityEstimation::new(&[0.0, 1.0, 2.0], 0.5, NormalKernel);
+    /// let second = KernelDensityEstimation::new(&[5.0], 1.0, NormalKernel);
+    /// let kde = MixtureKernelDensityEstimation::pooled(vec!(first, second));
+    ///
+    /// assert_eq!(kde.weights(), &[3.0, 1.0]);
+    /// ```
+    pub fn pooled(components: Vec<KernelDensityEstimation<K>>)
+                  -> MixtureKernelDensityEstimation<K> {
+        let weights: Vec<f64> = components.iter()
+            .map(|component| component.weights().iter().sum())
+            .collect();
+
+        MixtureKernelDensityEstimation::new(components, &weights)
+    }
+
+    /// Add a component with a given mixture weight.
+    ///
+    /// # Panics
+    ///
+    /// The weight must be finite and non-negative.
+    pub fn push(&mut self, component: KernelDensityEstimation<K>, weight: f64) {
+        assert!(weight >= 0.0 && weight.is_finite());
+
+        self.components.push(component);
+        self.weights.push(weight);
+    }
+
+    /// Remove and return the component at a given index, subtracting its
+    /// contribution from the mixture. The remaining weights are
+    /// renormalised.
+    ///
+    /// # Panics
+    ///
+    /// The index must be in range and at least one of the remaining weights
+    /// must be greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{KernelDensityEstimation, MixtureKernelDensityEstimation,
+    ///                           UniformKernel};
+    ///
+    /// let low = KernelDensityEstimation::new(&[0.0], 1.0, UniformKernel);
+    /// let high = KernelDensityEstimation::new(&[10.0], 1.0, UniformKernel);
+    /// let mut kde = MixtureKernelDensityEstimation::pooled(vec!(low, high));
+    ///
+    /// kde.remove(1);
+    /// assert_eq!(kde.density(0.0), 0.5);
+    /// ```
+    pub fn remove(&mut self, index: usize) -> KernelDensityEstimation<K> {
+        assert!(index < self.components.len());
+
+        let total_weight: f64 = self.weights.iter().sum::<f64>() - self.weights[index];
+        assert!(total_weight > 0.0);
+
+        self.weights.remove(index);
+        self.components.remove(index)
+    }
+
+    /// Return the component estimations.
+    pub fn components(&self) -> &[KernelDensityEstimation<K>] {
+        &self.components
+    }
+
+    /// Return the mixture weights, in the same order as `components`. The
+    /// weights are not normalised.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Calculate the total of the mixture weights.
+    fn total_weight(&self) -> f64 {
+        self.weights.iter().sum()
+    }
+}
+
+impl<K: Kernel> Density for MixtureKernelDensityEstimation<K> {
+    /// Calculate a value of the mixture density function for a given value.
+    fn density(&self, x: f64) -> f64 {
+        let sum: f64 = self.components
+            .iter()
+            .zip(&self.weights)
+            .map(|(component, weight)| weight * component.density(x))
+            .sum();
+
+        sum / self.total_weight()
+    }
+
+    /// Calculate a value of the cumulative density function for this
+    /// mixture.
+    fn cdf(&self, x: f64) -> f64 {
+        let sum: f64 = self.components
+            .iter()
+            .zip(&self.weights)
+            .map(|(component, weight)| weight * component.cdf(x))
+            .sum();
+
+        (sum / self.total_weight()).min(1.0)
+    }
+
+    /// Calculate the mixture density function for a slice of values,
+    /// evaluating each component with its own batch evaluation.
+    fn densities(&self, xs: &[f64]) -> Vec<f64> {
+        let total_weight = self.total_weight();
+        let mut densities = vec![0.0; xs.len()];
+
+        for (component, weight) in self.components.iter().zip(&self.weights) {
+            for (density, value) in densities.iter_mut().zip(component.densities(xs)) {
+                *density += weight * value / total_weight;
+            }
+        }
+
+        densities
+    }
+
+    /// Calculate the cumulative density function for a slice of values,
+    /// evaluating each component with its own batch evaluation.
+    fn cdfs(&self, xs: &[f64]) -> Vec<f64> {
+        let total_weight = self.total_weight();
+        let mut cdfs = vec![0.0; xs.len()];
+
+        for (component, weight) in self.components.iter().zip(&self.weights) {
+            for (cdf, value) in cdfs.iter_mut().zip(component.cdfs(xs)) {
+                *cdf += weight * value / total_weight;
+            }
+        }
+
+        cdfs.into_iter().map(|cdf| cdf.min(1.0)).collect()
+    }
+
+    /// Draw a random value from this mixture.
+    ///
+    /// A component is chosen in proportion to its mixture weight and a value
+    /// is drawn from it.
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        let mut target = rng.next_f64() * self.total_weight();
+
+        for (component, &weight) in self.components.iter().zip(&self.weights) {
+            if target < weight {
+                return component.sample(rng);
+            }
+
+            target -= weight;
+        }
+
+        // Rounding can leave the target just past the last positive weight.
+        let last = self.weights.iter().rposition(|&weight| weight > 0.0).unwrap();
+        self.components[last].sample(rng)
+    }
+}
@@ -1,10 +1,52 @@
 //! Kernel Density Estimation functions.
 
+pub mod bandwidth;
+pub mod circular;
+pub mod confidence;
+pub mod cross_validation;
+pub mod fast_gauss;
+pub mod grid;
+pub mod modes;
+
+mod adaptive;
+mod biweight;
+mod bounded;
+mod conditional;
+mod cosine;
 mod epanechnikov;
+mod estimation;
+mod kernel;
+mod logistic;
+mod mixture;
+mod multivariate;
 mod normal;
+mod sigmoid;
+mod streaming;
+mod triangular;
+mod tricube;
+mod triweight;
 mod uniform;
 
-use density::Density;
+pub use self::adaptive::AdaptiveKernelDensityEstimation;
+pub use self::biweight::{BiweightKernel, BiweightKernelDensityEstimation};
+pub use self::bounded::{Boundary, BoundedKernelDensityEstimation};
+pub use self::conditional::ConditionalKernelDensityEstimation;
+pub use self::cosine::{CosineKernel, CosineKernelDensityEstimation};
+pub use self::epanechnikov::{EpanechnikovKernel, EpanechnikovKernelDensityEstimation};
+pub use self::estimation::KernelDensityEstimation;
+pub use self::kernel::Kernel;
+pub use self::logistic::{LogisticKernel, LogisticKernelDensityEstimation};
+pub use self::mixture::MixtureKernelDensityEstimation;
+pub use self::multivariate::{BandwidthMatrix, MultivariateKernelDensityEstimation};
+pub use self::normal::{NormalKernel, NormalKernelDensityEstimation};
+pub use self::sigmoid::{SigmoidKernel, SigmoidKernelDensityEstimation};
+pub use self::streaming::{StreamingComponent, StreamingKernelDensityEstimation};
+pub use self::triangular::{TriangularKernel, TriangularKernelDensityEstimation};
+pub use self::tricube::{TricubeKernel, TricubeKernelDensityEstimation};
+pub use self::triweight::{TriweightKernel, TriweightKernelDensityEstimation};
+pub use self::uniform::{UniformKernel, UniformKernelDensityEstimation};
+
+use density::{Density, MultivariateDensity};
 
 /// Construct a kernel density estimation for a given sample. Uses the
 /// Epanenchnikov kernel.
@@ -25,16 +67,125 @@ use density::Density;
 /// let bandwidth = 0.1;
 /// let kde = kernel_density::kde::epanechnikov(&samples, bandwidth);
 /// ```
-pub fn epanechnikov(samples: &[f64], bandwidth: f64) -> Box<Density> {
-    assert!(bandwidth > 0.0);
+///
+/// The same estimation as a concrete type, for example for `modes`, is
+/// `KernelDensityEstimation::new(&samples, bandwidth, EpanechnikovKernel)`.
+pub fn epanechnikov(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, EpanechnikovKernel))
+}
 
-    let length = samples.len();
-    assert!(length > 0);
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Epanechnikov kernel with bandwidth chosen by Silverman's rule of thumb.
+///
+/// # Panics
+///
+/// The sample set must be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let kde = kernel_density::kde::epanechnikov_auto(&samples);
+/// ```
+pub fn epanechnikov_auto(samples: &[f64]) -> Box<dyn Density> {
+    let bandwidth = bandwidth::rescale(bandwidth::silverman(samples), &EpanechnikovKernel);
+    epanechnikov(samples, bandwidth)
+}
+
+/// Construct a kernel density estimation for a given weighted sample. Uses
+/// the Epanechnikov kernel.
+///
+/// Each sample contributes in proportion to its weight, with weights
+/// normalised by their total.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// There must be one weight for each sample, weights must be finite and
+/// non-negative, and at least one weight must be greater than zero.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 1.0, 2.0, 3.0);
+/// let counts = vec!(10.0, 30.0, 30.0, 10.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::epanechnikov_weighted(&samples, &counts, bandwidth);
+/// ```
+pub fn epanechnikov_weighted(samples: &[f64], weights: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::weighted(samples, weights, bandwidth, EpanechnikovKernel))
+}
+
+/// Construct an adaptive kernel density estimation for a given sample. Uses
+/// the Epanechnikov kernel.
+///
+/// A pilot kernel density estimation with the given bandwidth assigns each
+/// sample a local bandwidth proportional to pilot density^(-sensitivity).
+/// Sensitivity 0.5 is Abramson's square-root law.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero, sensitivity must be in [0, 1] and
+/// the sample set must be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::epanechnikov_adaptive(&samples, bandwidth, 0.5);
+/// ```
+pub fn epanechnikov_adaptive(samples: &[f64], bandwidth: f64, sensitivity: f64) -> Box<dyn Density> {
+    Box::new(AdaptiveKernelDensityEstimation::new(samples, bandwidth, sensitivity, EpanechnikovKernel))
+}
 
-    Box::new(epanechnikov::EpanechnikovKernelDensityEstimation {
-        samples: samples.to_vec(),
-        bandwidth: bandwidth,
-    })
+/// Construct a boundary corrected kernel density estimation for a given
+/// sample known to lie between bounds. Uses the Epanechnikov kernel.
+///
+/// Either bound may be infinite. The density and cumulative density respect
+/// the bounds and the density integrates to one between them.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero, the sample set must be non-empty
+/// and lower must be less than upper.
+///
+/// Samples must be between the bounds. For the transformation method,
+/// samples must be strictly between the bounds.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::Boundary;
+/// use std::f64;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let boundary = Boundary::Reflection;
+/// let kde = kernel_density::kde::epanechnikov_bounded(&samples, bandwidth, 0.0, f64::INFINITY, boundary);
+/// ```
+pub fn epanechnikov_bounded(samples: &[f64],
+                            bandwidth: f64,
+                            lower: f64,
+                            upper: f64,
+                            boundary: Boundary)
+                           -> Box<dyn Density> {
+    Box::new(BoundedKernelDensityEstimation::new(samples,
+                                                 bandwidth,
+                                                 EpanechnikovKernel,
+                                                 lower,
+                                                 upper,
+                                                 boundary))
 }
 
 /// Construct a kernel density estimation for a given sample. Uses the
@@ -56,16 +207,125 @@ pub fn epanechnikov(samples: &[f64], bandwidth: f64) -> Box<Density> {
 /// let bandwidth = 0.1;
 /// let kde = kernel_density::kde::normal(&samples, bandwidth);
 /// ```
-pub fn normal(samples: &[f64], bandwidth: f64) -> Box<Density> {
-    assert!(bandwidth > 0.0);
+///
+/// The same estimation as a concrete type, for example for `modes`, is
+/// `KernelDensityEstimation::new(&samples, bandwidth, NormalKernel)`.
+pub fn normal(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, NormalKernel))
+}
+
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Normal kernel with bandwidth chosen by Silverman's rule of thumb.
+///
+/// # Panics
+///
+/// The sample set must be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let kde = kernel_density::kde::normal_auto(&samples);
+/// ```
+pub fn normal_auto(samples: &[f64]) -> Box<dyn Density> {
+    let bandwidth = bandwidth::rescale(bandwidth::silverman(samples), &NormalKernel);
+    normal(samples, bandwidth)
+}
+
+/// Construct a kernel density estimation for a given weighted sample. Uses
+/// the Normal kernel.
+///
+/// Each sample contributes in proportion to its weight, with weights
+/// normalised by their total.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// There must be one weight for each sample, weights must be finite and
+/// non-negative, and at least one weight must be greater than zero.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 1.0, 2.0, 3.0);
+/// let counts = vec!(10.0, 30.0, 30.0, 10.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::normal_weighted(&samples, &counts, bandwidth);
+/// ```
+pub fn normal_weighted(samples: &[f64], weights: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::weighted(samples, weights, bandwidth, NormalKernel))
+}
 
-    let length = samples.len();
-    assert!(length > 0);
+/// Construct an adaptive kernel density estimation for a given sample. Uses
+/// the Normal kernel.
+///
+/// A pilot kernel density estimation with the given bandwidth assigns each
+/// sample a local bandwidth proportional to pilot density^(-sensitivity).
+/// Sensitivity 0.5 is Abramson's square-root law.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero, sensitivity must be in [0, 1] and
+/// the sample set must be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::normal_adaptive(&samples, bandwidth, 0.5);
+/// ```
+pub fn normal_adaptive(samples: &[f64], bandwidth: f64, sensitivity: f64) -> Box<dyn Density> {
+    Box::new(AdaptiveKernelDensityEstimation::new(samples, bandwidth, sensitivity, NormalKernel))
+}
 
-    Box::new(normal::NormalKernelDensityEstimation {
-        samples: samples.to_vec(),
-        bandwidth: bandwidth,
-    })
+/// Construct a boundary corrected kernel density estimation for a given
+/// sample known to lie between bounds. Uses the Normal kernel.
+///
+/// Either bound may be infinite. The density and cumulative density respect
+/// the bounds and the density integrates to one between them.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero, the sample set must be non-empty
+/// and lower must be less than upper.
+///
+/// Samples must be between the bounds. For the transformation method,
+/// samples must be strictly between the bounds.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::Boundary;
+/// use std::f64;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let boundary = Boundary::Reflection;
+/// let kde = kernel_density::kde::normal_bounded(&samples, bandwidth, 0.0, f64::INFINITY, boundary);
+/// ```
+pub fn normal_bounded(samples: &[f64],
+                      bandwidth: f64,
+                      lower: f64,
+                      upper: f64,
+                      boundary: Boundary)
+                     -> Box<dyn Density> {
+    Box::new(BoundedKernelDensityEstimation::new(samples,
+                                                 bandwidth,
+                                                 NormalKernel,
+                                                 lower,
+                                                 upper,
+                                                 boundary))
 }
 
 /// Construct a kernel density estimation for a given sample. Uses the
@@ -87,14 +347,310 @@ pub fn normal(samples: &[f64], bandwidth: f64) -> Box<Density> {
 /// let bandwidth = 0.1;
 /// let kde = kernel_density::kde::uniform(&samples, bandwidth);
 /// ```
-pub fn uniform(samples: &[f64], bandwidth: f64) -> Box<Density> {
-    assert!(bandwidth > 0.0);
+pub fn uniform(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, UniformKernel))
+}
 
-    let length = samples.len();
-    assert!(length > 0);
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Uniform kernel with bandwidth chosen by Silverman's rule of thumb.
+///
+/// # Panics
+///
+/// The sample set must be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let kde = kernel_density::kde::uniform_auto(&samples);
+/// ```
+pub fn uniform_auto(samples: &[f64]) -> Box<dyn Density> {
+    let bandwidth = bandwidth::rescale(bandwidth::silverman(samples), &UniformKernel);
+    uniform(samples, bandwidth)
+}
 
-    Box::new(uniform::UniformKernelDensityEstimation {
-        samples: samples.to_vec(),
-        bandwidth: bandwidth,
-    })
+/// Construct a kernel density estimation for a given weighted sample. Uses
+/// the Uniform kernel.
+///
+/// Each sample contributes in proportion to its weight, with weights
+/// normalised by their total.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// There must be one weight for each sample, weights must be finite and
+/// non-negative, and at least one weight must be greater than zero.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 1.0, 2.0, 3.0);
+/// let counts = vec!(10.0, 30.0, 30.0, 10.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::uniform_weighted(&samples, &counts, bandwidth);
+/// ```
+pub fn uniform_weighted(samples: &[f64], weights: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::weighted(samples, weights, bandwidth, UniformKernel))
+}
+
+/// Construct an adaptive kernel density estimation for a given sample. Uses
+/// the Uniform kernel.
+///
+/// A pilot kernel density estimation with the given bandwidth assigns each
+/// sample a local bandwidth proportional to pilot density^(-sensitivity).
+/// Sensitivity 0.5 is Abramson's square-root law.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero, sensitivity must be in [0, 1] and
+/// the sample set must be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::uniform_adaptive(&samples, bandwidth, 0.5);
+/// ```
+pub fn uniform_adaptive(samples: &[f64], bandwidth: f64, sensitivity: f64) -> Box<dyn Density> {
+    Box::new(AdaptiveKernelDensityEstimation::new(samples, bandwidth, sensitivity, UniformKernel))
+}
+
+/// Construct a boundary corrected kernel density estimation for a given
+/// sample known to lie between bounds. Uses the Uniform kernel.
+///
+/// Either bound may be infinite. The density and cumulative density respect
+/// the bounds and the density integrates to one between them.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero, the sample set must be non-empty
+/// and lower must be less than upper.
+///
+/// Samples must be between the bounds. For the transformation method,
+/// samples must be strictly between the bounds.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::Boundary;
+/// use std::f64;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let boundary = Boundary::Reflection;
+/// let kde = kernel_density::kde::uniform_bounded(&samples, bandwidth, 0.0, f64::INFINITY, boundary);
+/// ```
+pub fn uniform_bounded(samples: &[f64],
+                       bandwidth: f64,
+                       lower: f64,
+                       upper: f64,
+                       boundary: Boundary)
+                      -> Box<dyn Density> {
+    Box::new(BoundedKernelDensityEstimation::new(samples,
+                                                 bandwidth,
+                                                 UniformKernel,
+                                                 lower,
+                                                 upper,
+                                                 boundary))
+}
+
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Triangular kernel.
+///
+/// k(x) = 1 - abs(x) for abs(x) <= 1 and 0 otherwise.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::triangular(&samples, bandwidth);
+/// ```
+pub fn triangular(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, TriangularKernel))
+}
+
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Biweight kernel.
+///
+/// k(x) = 15 * (1 - x^2)^2 / 16 for abs(x) <= 1 and 0 otherwise.
+///
+/// Also known as the quartic kernel.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::biweight(&samples, bandwidth);
+/// ```
+pub fn biweight(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, BiweightKernel))
+}
+
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Triweight kernel.
+///
+/// k(x) = 35 * (1 - x^2)^3 / 32 for abs(x) <= 1 and 0 otherwise.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::triweight(&samples, bandwidth);
+/// ```
+pub fn triweight(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, TriweightKernel))
+}
+
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Tricube kernel.
+///
+/// k(x) = 70 * (1 - abs(x)^3)^3 / 81 for abs(x) <= 1 and 0 otherwise.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::tricube(&samples, bandwidth);
+/// ```
+pub fn tricube(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, TricubeKernel))
+}
+
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Cosine kernel.
+///
+/// k(x) = pi * cos(pi * x / 2) / 4 for abs(x) <= 1 and 0 otherwise.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::cosine(&samples, bandwidth);
+/// ```
+pub fn cosine(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, CosineKernel))
+}
+
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Logistic kernel.
+///
+/// k(x) = 1 / (exp(x) + 2 + exp(-x))
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::logistic(&samples, bandwidth);
+/// ```
+pub fn logistic(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, LogisticKernel))
+}
+
+/// Construct a kernel density estimation for a given sample. Uses the
+/// Sigmoid kernel.
+///
+/// k(x) = 2 / (pi * (exp(x) + exp(-x)))
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::sigmoid(&samples, bandwidth);
+/// ```
+pub fn sigmoid(samples: &[f64], bandwidth: f64) -> Box<dyn Density> {
+    Box::new(KernelDensityEstimation::new(samples, bandwidth, SigmoidKernel))
+}
+
+/// Construct a multivariate kernel density estimation for a given sample of
+/// points. Uses the product of Normal kernels, which is the multivariate
+/// Normal kernel with covariance given by the bandwidth matrix.
+///
+/// # Panics
+///
+/// The sample set must be non-empty and every sample must have the same
+/// non-zero dimension. The bandwidth matrix must have the sample dimension
+/// and be positive definite.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::bandwidth;
+///
+/// let samples = vec!(vec!(0.0, 1.0), vec!(1.0, 3.0), vec!(2.0, 2.0), vec!(3.0, 5.0));
+/// let bandwidth = bandwidth::multivariate_scott(&samples);
+/// let kde = kernel_density::kde::normal_multivariate(&samples, bandwidth);
+/// ```
+pub fn normal_multivariate(samples: &[Vec<f64>],
+                           bandwidth: BandwidthMatrix)
+                           -> Box<dyn MultivariateDensity> {
+    Box::new(MultivariateKernelDensityEstimation::new(samples, bandwidth, NormalKernel))
 }
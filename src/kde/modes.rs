@@ -0,0 +1,419 @@
+//! Mode finding and mean-shift clustering.
+//!
+//! Mean shift repeatedly moves a point to a weighted mean of the samples:
+//!
+//! x <- sum(w_i * g((x - x_i) / h) * x_i) / sum(w_i * g((x - x_i) / h))
+//!
+//! Where w_i are the sample weights and g(u) = -k'(u) / u. Each step moves
+//! uphill on the kernel density estimation and the iteration converges to a
+//! local maximum of the density, a mode. For the Normal kernel g is the
+//! Normal kernel itself, and for the Epanechnikov kernel g is constant over
+//! the kernel support, so each step moves to the mean of the samples within
+//! one bandwidth.
+//!
+//! Starting mean shift from every sample finds the modes of the estimation
+//! and clusters the samples by the mode they converge to. The basin of
+//! attraction of each mode extends to the local minima of the density, the
+//! antimodes, between it and its neighbouring modes.
+//!
+//! Mean shift converges slowly where the density is nearly flat and may stop
+//! short of a mode. Neighbouring end points with no dip in the density
+//! between them are therefore merged, and each mode is then located by
+//! golden section search between its antimodes.
+//!
+//! The `modes` and `mean_shift` functions take a `KernelDensityEstimation`,
+//! as the boxed `Density` returned by `kde::normal` and the other
+//! constructors does not expose its samples. The same estimation as
+//! `kde::normal(samples, bandwidth)` is `KernelDensityEstimation::new(samples,
+//! bandwidth, NormalKernel)`, and likewise for the other kernels. The
+//! `normal` and `epanechnikov` functions and their mean shift counterparts
+//! take a sample and bandwidth directly.
+
+use density::Density;
+use kde::epanechnikov::EpanechnikovKernel;
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::{effective_support, Kernel};
+use kde::normal::NormalKernel;
+use std::f64;
+
+/// Upper limit on the number of mean shift steps from each sample.
+const MAX_ITERATIONS: usize = 1000;
+
+/// Mean shift stops when a step is shorter than this many bandwidths.
+const SHIFT_TOLERANCE: f64 = 1e-9;
+
+/// Mean shift end points closer than this many bandwidths are one mode.
+const MERGE_TOLERANCE: f64 = 1e-3;
+
+/// Rescaled distance below which g(u) is evaluated at this distance instead,
+/// approximating the limit at zero.
+const SHADOW_LIMIT: f64 = 1e-4;
+
+/// Neighbouring mean shift end points are one mode unless the log density
+/// between them dips by more than this below the lower of the two.
+const DIP_TOLERANCE: f64 = 1e-6;
+
+/// Width in bandwidths at which the golden section search for a mode or an
+/// antimode stops.
+const GOLDEN_TOLERANCE: f64 = 1e-9;
+
+/// Upper limit on the number of golden section steps for a mode or an
+/// antimode.
+const GOLDEN_ITERATIONS: usize = 200;
+
+/// A local maximum of a kernel density estimation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mode {
+    /// Location of the local maximum.
+    pub location: f64,
+    /// Density at the local maximum.
+    pub density: f64,
+    /// Lower end of the basin of attraction, the antimode below the mode or
+    /// negative infinity for the lowest mode.
+    pub lower: f64,
+    /// Upper end of the basin of attraction, the antimode above the mode or
+    /// infinity for the highest mode.
+    pub upper: f64,
+    /// Probability mass of the estimation in the basin of attraction.
+    pub mass: f64,
+}
+
+/// Mean-shift clustering of the samples of a kernel density estimation.
+pub struct Clustering {
+    /// Modes of the estimation in increasing order of location.
+    pub modes: Vec<Mode>,
+    /// Index into modes of the mode each sample converges to, in the sorted
+    /// order of the estimation samples.
+    pub labels: Vec<usize>,
+}
+
+/// Find the modes of a kernel density estimation, in increasing order of
+/// location.
+///
+/// # Panics
+///
+/// The kernel must have g(0) > 0 for mean shift, which excludes the Uniform
+/// kernel whose derivative is zero.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::{modes, KernelDensityEstimation, NormalKernel};
+///
+/// let samples = vec!(0.0, 0.1, 0.2, 5.0, 5.1, 5.2, 5.3);
+/// let kde = KernelDensityEstimation::new(&samples, 0.5, NormalKernel);
+///
+/// let modes = modes::modes(&kde);
+/// assert_eq!(modes.len(), 2);
+/// assert!(modes[1].density > modes[0].density);
+/// ```
+pub fn modes<K: Kernel>(estimation: &KernelDensityEstimation<K>) -> Vec<Mode> {
+    mean_shift(estimation).modes
+}
+
+/// Cluster the samples of a kernel density estimation by running mean shift
+/// from each sample to a mode.
+///
+/// Each mean shift step costs O(k) for k samples within the effective kernel
+/// support, so clustering costs O(n * k) per step in total. Samples with
+/// equal values share their mean shift.
+///
+/// # Panics
+///
+/// The kernel must have g(0) > 0 for mean shift, which excludes the Uniform
+/// kernel whose derivative is zero.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::{modes, KernelDensityEstimation, EpanechnikovKernel};
+///
+/// let samples = vec!(5.1, 0.0, 5.0, 0.1, 5.2);
+/// let kde = KernelDensityEstimation::new(&samples, 1.0, EpanechnikovKernel);
+///
+/// let clustering = modes::mean_shift(&kde);
+/// assert_eq!(clustering.labels, vec!(0, 0, 1, 1, 1));
+/// ```
+pub fn mean_shift<K: Kernel>(estimation: &KernelDensityEstimation<K>) -> Clustering {
+    let kernel = estimation.kernel();
+    assert!(shadow(kernel, 0.0) > 0.0);
+
+    let bandwidth = estimation.bandwidth();
+    let samples = estimation.samples();
+    let length = samples.len();
+
+    // Samples are sorted, so equal samples are adjacent.
+    let mut ends: Vec<f64> = Vec::with_capacity(length);
+    for (i, &sample) in samples.iter().enumerate() {
+        if i > 0 && sample == samples[i - 1] {
+            let end = ends[i - 1];
+            ends.push(end);
+        } else {
+            ends.push(climb(estimation, sample));
+        }
+    }
+
+    // Group end points separated by less than the merge tolerance.
+    let mut order: Vec<usize> = (0..length).collect();
+    order.sort_by(|&i, &j| ends[i].partial_cmp(&ends[j]).unwrap());
+
+    let mut labels = vec![0; length];
+    let mut groups: Vec<f64> = vec![];
+    for (k, &i) in order.iter().enumerate() {
+        if k == 0 || ends[i] - ends[order[k - 1]] > MERGE_TOLERANCE * bandwidth {
+            groups.push(ends[i]);
+        }
+
+        labels[i] = groups.len() - 1;
+    }
+
+    // Merge neighbouring groups with no dip in the density between them,
+    // keeping the higher end point. Each group is compared with the last
+    // kept group, which changes as groups are merged into it.
+    let mut kept: Vec<f64> = vec![];
+    let mut merged: Vec<usize> = Vec::with_capacity(groups.len());
+    for &end in &groups {
+        let mut location = end;
+        while let Some(&previous) = kept.last() {
+            let lower = estimation.log_density(previous).min(estimation.log_density(location));
+            let between = estimation.log_density(antimode(estimation, previous, location));
+            if between < lower - DIP_TOLERANCE {
+                break;
+            }
+
+            if estimation.log_density(previous) >= estimation.log_density(location) {
+                location = previous;
+            }
+            kept.pop();
+        }
+
+        kept.push(location);
+        merged.push(kept.len() - 1);
+    }
+
+    // A kept group popped by a later merge reappears at the index of the
+    // later group, so the final index of each group is the smallest recorded
+    // for it or any later group.
+    for k in (1..merged.len()).rev() {
+        merged[k - 1] = merged[k - 1].min(merged[k]);
+    }
+    for label in &mut labels {
+        *label = merged[*label];
+    }
+
+    // Locate each mode between the antimodes around its end point, within
+    // the samples where the density is increasing below and decreasing above.
+    let min = samples[0];
+    let max = samples[length - 1];
+    let mut bounds = vec![min];
+    for pair in kept.windows(2) {
+        bounds.push(antimode(estimation, pair[0], pair[1]));
+    }
+    bounds.push(max);
+
+    let locations: Vec<f64> = (0..kept.len())
+        .map(|k| golden(|x| -estimation.log_density(x), bounds[k], bounds[k + 1], bandwidth))
+        .collect();
+
+    let mut antimodes = vec![f64::NEG_INFINITY];
+    for pair in locations.windows(2) {
+        antimodes.push(antimode(estimation, pair[0], pair[1]));
+    }
+    antimodes.push(f64::INFINITY);
+
+    let modes = locations.iter()
+        .enumerate()
+        .map(|(k, &location)| {
+            let lower = antimodes[k];
+            let upper = antimodes[k + 1];
+            let upper_cdf = if upper.is_finite() { estimation.cdf(upper) } else { 1.0 };
+            let lower_cdf = if lower.is_finite() { estimation.cdf(lower) } else { 0.0 };
+
+            Mode {
+                location,
+                density: estimation.density(location),
+                lower,
+                upper,
+                mass: upper_cdf - lower_cdf,
+            }
+        })
+        .collect();
+
+    Clustering { modes, labels }
+}
+
+/// Find the modes of the Normal kernel density estimation of a sample with a
+/// given bandwidth, the estimation constructed by `kde::normal`.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::modes;
+///
+/// let samples = vec!(0.0, 0.1, 0.2, 5.0, 5.1, 5.2, 5.3);
+///
+/// assert_eq!(modes::normal(&samples, 0.5).len(), 2);
+/// ```
+pub fn normal(samples: &[f64], bandwidth: f64) -> Vec<Mode> {
+    modes(&KernelDensityEstimation::new(samples, bandwidth, NormalKernel))
+}
+
+/// Find the modes of the Epanechnikov kernel density estimation of a sample
+/// with a given bandwidth, the estimation constructed by `kde::epanechnikov`.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+pub fn epanechnikov(samples: &[f64], bandwidth: f64) -> Vec<Mode> {
+    modes(&KernelDensityEstimation::new(samples, bandwidth, EpanechnikovKernel))
+}
+
+/// Cluster a sample by mean shift on its Normal kernel density estimation
+/// with a given bandwidth, the estimation constructed by `kde::normal`.
+///
+/// The labels are in the sorted order of the samples.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+pub fn normal_mean_shift(samples: &[f64], bandwidth: f64) -> Clustering {
+    mean_shift(&KernelDensityEstimation::new(samples, bandwidth, NormalKernel))
+}
+
+/// Cluster a sample by mean shift on its Epanechnikov kernel density
+/// estimation with a given bandwidth, the estimation constructed by
+/// `kde::epanechnikov`.
+///
+/// The labels are in the sorted order of the samples.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::modes;
+///
+/// let samples = vec!(5.1, 0.0, 5.0, 0.1, 5.2);
+///
+/// let clustering = modes::epanechnikov_mean_shift(&samples, 1.0);
+/// assert_eq!(clustering.labels, vec!(0, 0, 1, 1, 1));
+/// ```
+pub fn epanechnikov_mean_shift(samples: &[f64], bandwidth: f64) -> Clustering {
+    mean_shift(&KernelDensityEstimation::new(samples, bandwidth, EpanechnikovKernel))
+}
+
+/// Mean shift weight g(u) = -k'(u) / u.
+fn shadow<K: Kernel>(kernel: &K, u: f64) -> f64 {
+    let u = if u.abs() < SHADOW_LIMIT { SHADOW_LIMIT } else { u };
+    -kernel.derivative(u) / u
+}
+
+/// Run mean shift from a point to convergence.
+fn climb<K: Kernel>(estimation: &KernelDensityEstimation<K>, start: f64) -> f64 {
+    let kernel = estimation.kernel();
+    let bandwidth = estimation.bandwidth();
+    let radius = effective_support(kernel) * bandwidth;
+    let samples = estimation.samples();
+
+    let mut x = start;
+    for _ in 0..MAX_ITERATIONS {
+        let begin = samples.partition_point(|&sample| sample < x - radius);
+        let end = samples.partition_point(|&sample| sample <= x + radius);
+
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+        for (&sample, weight) in samples[begin..end].iter().zip(&estimation.weights()[begin..end]) {
+            let g = weight * shadow(kernel, (x - sample) / bandwidth);
+            numerator += g * sample;
+            denominator += g;
+        }
+
+        if denominator <= 0.0 {
+            break;
+        }
+
+        let next = numerator / denominator;
+        let step = (next - x).abs();
+        x = next;
+
+        if step < SHIFT_TOLERANCE * bandwidth {
+            break;
+        }
+    }
+
+    x
+}
+
+/// Locate the minimum of the density between two modes by golden section
+/// search.
+///
+/// The search is on the log density, which stays finite far from the samples
+/// where the density underflows.
+fn antimode<K: Kernel>(estimation: &KernelDensityEstimation<K>, low: f64, high: f64) -> f64 {
+    golden(|x| estimation.log_density(x), low, high, estimation.bandwidth())
+}
+
+/// Locate a minimum of a function between two points by golden section
+/// search, to a tolerance relative to the bandwidth.
+///
+/// Where the two probes are equal, as in a region of zero density between
+/// compact kernel supports, the bracket narrows to the probes from both
+/// sides so the minimum found is central.
+pub(crate) fn golden<F: Fn(f64) -> f64>(f: F, low: f64, high: f64, bandwidth: f64) -> f64 {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let tolerance = GOLDEN_TOLERANCE * bandwidth;
+
+    let mut low = low;
+    let mut high = high;
+    let mut left = high - ratio * (high - low);
+    let mut right = low + ratio * (high - low);
+    let mut left_value = f(left);
+    let mut right_value = f(right);
+
+    for _ in 0..GOLDEN_ITERATIONS {
+        if high - low <= tolerance {
+            break;
+        }
+
+        if left_value == right_value {
+            low = left.min(right);
+            high = left.max(right);
+            left = high - ratio * (high - low);
+            right = low + ratio * (high - low);
+            left_value = f(left);
+            right_value = f(right);
+        } else if left_value < right_value {
+            high = right;
+            right = left;
+            right_value = left_value;
+            left = high - ratio * (high - low);
+            left_value = f(left);
+        } else {
+            low = left;
+            left = right;
+            left_value = right_value;
+            right = low + ratio * (high - low);
+            right_value = f(right);
+        }
+    }
+
+    0.5 * (low + high)
+}
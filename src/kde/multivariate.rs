@@ -0,0 +1,286 @@
+//! Multivariate kernel density estimation with product kernels.
+
+use density::MultivariateDensity;
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+
+/// Bandwidth matrix for a multivariate kernel density estimation.
+///
+/// The bandwidth matrix H plays the role of the kernel covariance. The scalar
+/// and diagonal variants are given as bandwidths, the square roots of the
+/// diagonal of H, like univariate bandwidths. The full variant is H itself.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BandwidthMatrix {
+    /// A single bandwidth h for every dimension, H = h^2 * I.
+    Scalar(f64),
+    /// A bandwidth h_i for each dimension, H = diag(h_i^2).
+    Diagonal(Vec<f64>),
+    /// A symmetric positive definite matrix H, as rows.
+    Full(Vec<Vec<f64>>),
+}
+
+impl BandwidthMatrix {
+    /// Construct the full d x d matrix H.
+    fn matrix(&self, dimension: usize) -> Vec<Vec<f64>> {
+        let mut matrix = vec![vec![0.0; dimension]; dimension];
+
+        match *self {
+            BandwidthMatrix::Scalar(bandwidth) => {
+                assert!(bandwidth > 0.0);
+
+                for (i, row) in matrix.iter_mut().enumerate() {
+                    row[i] = bandwidth.powi(2);
+                }
+            }
+            BandwidthMatrix::Diagonal(ref bandwidths) => {
+                assert!(bandwidths.len() == dimension);
+                assert!(bandwidths.iter().all(|&bandwidth| bandwidth > 0.0));
+
+                for (i, row) in matrix.iter_mut().enumerate() {
+                    row[i] = bandwidths[i].powi(2);
+                }
+            }
+            BandwidthMatrix::Full(ref full) => {
+                assert!(full.len() == dimension);
+                assert!(full.iter().all(|row| row.len() == dimension));
+
+                for i in 0..dimension {
+                    for j in 0..dimension {
+                        assert!(full[i][j] == full[j][i]);
+                        matrix[i][j] = full[i][j];
+                    }
+                }
+            }
+        }
+
+        matrix
+    }
+}
+
+/// Multivariate kernel density estimation using a product kernel.
+///
+/// With the bandwidth matrix factored as H = A * A^T, A lower triangular by
+/// Cholesky decomposition, the estimation is:
+///
+/// f(x) = sum(K(A^-1 * (x - x_i))) / (n * det(A))
+///
+/// Where K(u) = k(u_1) * ... * k(u_d) is the product of univariate kernels.
+/// For the Normal kernel this is the multivariate Normal kernel with
+/// covariance H.
+pub struct MultivariateKernelDensityEstimation<K: Kernel> {
+    samples: Vec<Vec<f64>>,
+    bandwidth: BandwidthMatrix,
+    kernel: K,
+    /// Cholesky factor A of the bandwidth matrix, as rows.
+    factor: Vec<Vec<f64>>,
+    /// Determinant of the Cholesky factor.
+    determinant: f64,
+}
+
+impl<K: Kernel> MultivariateKernelDensityEstimation<K> {
+    /// Construct a new multivariate kernel density estimation for a given
+    /// sample of points, bandwidth matrix and kernel.
+    ///
+    /// # Panics
+    ///
+    /// The sample set must be non-empty and every sample must have the same
+    /// non-zero dimension.
+    ///
+    /// Scalar and diagonal bandwidths must be greater than zero and have the
+    /// sample dimension. A full bandwidth matrix must be square with the
+    /// sample dimension, symmetric and positive definite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::MultivariateDensity;
+    /// use kernel_density::kde::{BandwidthMatrix, MultivariateKernelDensityEstimation,
+    ///                           NormalKernel};
+    ///
+    /// let samples = vec!(vec!(0.0, 0.0), vec!(1.0, 2.0), vec!(2.0, 1.0));
+    /// let bandwidth = BandwidthMatrix::Full(vec!(vec!(1.0, 0.5), vec!(0.5, 1.0)));
+    /// let kde = MultivariateKernelDensityEstimation::new(&samples, bandwidth, NormalKernel);
+    ///
+    /// assert!(kde.density(&[1.0, 1.0]) > 0.0);
+    /// ```
+    pub fn new(samples: &[Vec<f64>],
+               bandwidth: BandwidthMatrix,
+               kernel: K)
+               -> MultivariateKernelDensityEstimation<K> {
+        let length = samples.len();
+        assert!(length > 0);
+
+        let dimension = samples[0].len();
+        assert!(dimension > 0);
+        assert!(samples.iter().all(|sample| sample.len() == dimension));
+
+        let factor = cholesky(&bandwidth.matrix(dimension));
+        let determinant = (0..dimension).map(|i| factor[i][i]).product();
+
+        MultivariateKernelDensityEstimation {
+            samples: samples.to_vec(),
+            bandwidth,
+            kernel,
+            factor,
+            determinant,
+        }
+    }
+
+    /// Return the sample points, in the order given.
+    pub fn samples(&self) -> &[Vec<f64>] {
+        &self.samples
+    }
+
+    /// Return the bandwidth matrix of the estimation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::{BandwidthMatrix, MultivariateKernelDensityEstimation,
+    ///                           NormalKernel};
+    ///
+    /// let samples = vec!(vec!(0.0, 0.0), vec!(1.0, 2.0));
+    /// let kde = MultivariateKernelDensityEstimation::new(&samples, BandwidthMatrix::Scalar(0.5),
+    ///                                                    NormalKernel);
+    ///
+    /// assert_eq!(kde.bandwidth(), &BandwidthMatrix::Scalar(0.5));
+    /// ```
+    pub fn bandwidth(&self) -> &BandwidthMatrix {
+        &self.bandwidth
+    }
+
+    /// Return the kernel of the estimation.
+    pub fn kernel(&self) -> &K {
+        &self.kernel
+    }
+
+    /// Construct the univariate marginal kernel density estimation of one
+    /// dimension, integrating out the other dimensions.
+    ///
+    /// The marginal uses the samples in the given dimension with bandwidth
+    /// sqrt(H_ii). This is exact for scalar and diagonal bandwidth matrices,
+    /// where the product kernel factors, and for the Normal kernel with any
+    /// bandwidth matrix, since Normal marginals are Normal. For other kernels
+    /// with a full bandwidth matrix correlating the dimension with earlier
+    /// ones, the exact marginal kernel is a sum of scaled kernels and the
+    /// marginal returned is the kernel density estimation with the same
+    /// kernel variance.
+    ///
+    /// # Panics
+    ///
+    /// The dimension must be less than the sample dimension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{BandwidthMatrix, MultivariateKernelDensityEstimation,
+    ///                           UniformKernel};
+    ///
+    /// let samples = vec!(vec!(0.0, 5.0), vec!(1.0, 6.0));
+    /// let bandwidth = BandwidthMatrix::Diagonal(vec!(0.1, 1.0));
+    /// let kde = MultivariateKernelDensityEstimation::new(&samples, bandwidth, UniformKernel);
+    ///
+    /// let marginal = kde.marginal(0);
+    /// assert_eq!(marginal.density(0.0), 2.5);
+    /// ```
+    pub fn marginal(&self, dimension: usize) -> KernelDensityEstimation<K>
+        where K: Clone
+    {
+        assert!(dimension < self.factor.len());
+
+        let samples: Vec<f64> = self.samples.iter().map(|sample| sample[dimension]).collect();
+
+        // Diagonal of H = A * A^T.
+        let variance: f64 = self.factor[dimension].iter().map(|a| a.powi(2)).sum();
+
+        KernelDensityEstimation::new(&samples, variance.sqrt(), self.kernel.clone())
+    }
+}
+
+impl<K: Kernel> MultivariateDensity for MultivariateKernelDensityEstimation<K> {
+    fn dimension(&self) -> usize {
+        self.factor.len()
+    }
+
+    /// Calculate a value of the multivariate kernel density function for a
+    /// given point.
+    ///
+    /// # Panics
+    ///
+    /// The point must have the sample dimension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::MultivariateDensity;
+    /// use kernel_density::kde::{BandwidthMatrix, MultivariateKernelDensityEstimation,
+    ///                           UniformKernel};
+    ///
+    /// let samples = vec!(vec!(0.0, 0.0), vec!(5.0, 5.0));
+    /// let kde = MultivariateKernelDensityEstimation::new(&samples,
+    ///                                                    BandwidthMatrix::Scalar(1.0),
+    ///                                                    UniformKernel);
+    ///
+    /// assert_eq!(kde.density(&[0.5, -0.5]), 0.125);
+    /// ```
+    fn density(&self, x: &[f64]) -> f64 {
+        let dimension = self.factor.len();
+        assert!(x.len() == dimension);
+
+        let mut u = vec![0.0; dimension];
+        let mut sum = 0.0;
+
+        for sample in &self.samples {
+            // Forward substitution for u = A^-1 * (x - x_i).
+            let mut product = 1.0;
+            for i in 0..dimension {
+                let row = &self.factor[i];
+                let residual = x[i] - sample[i] -
+                               row[..i].iter().zip(&u[..i]).map(|(a, v)| a * v).sum::<f64>();
+                u[i] = residual / row[i];
+
+                product *= self.kernel.density(u[i]);
+                if product == 0.0 {
+                    break;
+                }
+            }
+
+            sum += product;
+        }
+
+        sum / (self.samples.len() as f64 * self.determinant)
+    }
+}
+
+/// Cholesky decomposition of a symmetric positive definite matrix into a
+/// lower triangular factor.
+fn cholesky(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let dimension = matrix.len();
+    let mut factor = vec![vec![0.0; dimension]; dimension];
+
+    for i in 0..dimension {
+        for j in 0..(i + 1) {
+            let sum = matrix[i][j] -
+                      factor[i][..j].iter().zip(&factor[j][..j]).map(|(a, b)| a * b).sum::<f64>();
+
+            if i == j {
+                // Positive definite.
+                assert!(sum > 0.0);
+                factor[i][i] = sum.sqrt();
+            } else {
+                factor[i][j] = sum / factor[j][j];
+            }
+        }
+    }
+
+    factor
+}
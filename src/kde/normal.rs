@@ -1,66 +1,73 @@
 //! Normal kernel density estimation functions.
 
+extern crate rand;
 extern crate special_fun;
 
-use density::Density;
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+use self::rand::{Rand, Rng};
+use self::rand::distributions::normal::StandardNormal;
 use self::special_fun::FloatSpecial;
 use std::f64::consts::PI;
+use std::f64;
 
-pub struct NormalKernelDensityEstimation {
-    pub samples: Vec<f64>,
-    pub bandwidth: f64,
-}
+/// The Normal kernel.
+///
+/// k(x) = exp(-x^2 / 2) / sqrt(2 * pi)
+#[derive(Clone, Copy, Debug)]
+pub struct NormalKernel;
 
-impl Density for NormalKernelDensityEstimation {
-    /// Calculate a value of the kernel density function for a given value.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// extern crate kernel_density;
-    ///
-    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
-    /// let bandwidth = 0.1;
-    /// let kde = kernel_density::kde::normal(&samples, bandwidth);
-    ///
-    /// assert_eq!(kde.density(4.0), 0.3989422804014327);
-    /// ```
+impl Kernel for NormalKernel {
     fn density(&self, x: f64) -> f64 {
-        let length = self.samples.len();
+        (-0.5 * x.powi(2)).exp() / (2.0 * PI).sqrt()
+    }
 
-        let mut sum = 0.0;
-        for sample in &self.samples {
-            let rescaled: f64 = (x - sample) / self.bandwidth;
-            sum += (-0.5 * rescaled.powi(2)).exp()
-        }
+    fn cdf(&self, x: f64) -> f64 {
+        x.norm()
+    }
 
-        let sqrt_2pi = (2.0 * PI).sqrt();
-        sum / (sqrt_2pi * length as f64 * self.bandwidth)
+    fn log_density(&self, x: f64) -> f64 {
+        -0.5 * x.powi(2) - 0.5 * (2.0 * PI).ln()
     }
 
-    /// Calculate a value of the cumulative density function for this kernel
-    /// density estimation.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// extern crate kernel_density;
-    ///
-    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
-    /// let bandwidth = 0.1;
-    /// let kde = kernel_density::kde::normal(&samples, bandwidth);
-    ///
-    /// assert_eq!(kde.cdf(0.1), 0.08413447460685429);
-    /// ```
-    fn cdf(&self, x: f64) -> f64 {
-        let length = self.samples.len();
+    fn derivative(&self, x: f64) -> f64 {
+        -x * self.density(x)
+    }
+
+    fn support(&self) -> f64 {
+        f64::INFINITY
+    }
+
+    fn second_moment(&self) -> f64 {
+        1.0
+    }
 
-        let mut sum = 0.0;
-        for sample in &self.samples {
-            let rescaled: f64 = (x - sample) / self.bandwidth;
-            sum += rescaled.norm();
-        }
+    fn roughness(&self) -> f64 {
+        0.5 / PI.sqrt()
+    }
 
-        sum / length as f64
+    fn convolution(&self, x: f64) -> f64 {
+        (-0.25 * x.powi(2)).exp() / (4.0 * PI).sqrt()
+    }
+
+    fn sample(&self, mut rng: &mut dyn Rng) -> f64 {
+        let StandardNormal(x) = StandardNormal::rand(&mut rng);
+        x
     }
 }
+
+/// Kernel density estimation using the Normal kernel.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::normal(&samples, bandwidth);
+///
+/// assert_eq!(kde.density(4.0), 0.3989422804014327);
+/// assert_eq!(kde.cdf(0.1), 0.08413447460685429);
+/// ```
+pub type NormalKernelDensityEstimation = KernelDensityEstimation<NormalKernel>;
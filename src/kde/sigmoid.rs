@@ -0,0 +1,41 @@
+//! Sigmoid kernel density estimation functions.
+
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+use std::f64::consts::PI;
+use std::f64;
+
+/// The Sigmoid kernel.
+///
+/// k(x) = 2 / (pi * (exp(x) + exp(-x)))
+#[derive(Clone, Copy, Debug)]
+pub struct SigmoidKernel;
+
+impl Kernel for SigmoidKernel {
+    fn density(&self, x: f64) -> f64 {
+        1.0 / (PI * x.cosh())
+    }
+
+    fn log_density(&self, x: f64) -> f64 {
+        (2.0 / PI).ln() - x.abs() - (-2.0 * x.abs()).exp().ln_1p()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        2.0 * x.exp().atan() / PI
+    }
+
+    fn support(&self) -> f64 {
+        f64::INFINITY
+    }
+
+    fn second_moment(&self) -> f64 {
+        PI.powi(2) / 4.0
+    }
+
+    fn roughness(&self) -> f64 {
+        2.0 / PI.powi(2)
+    }
+}
+
+/// Kernel density estimation using the Sigmoid kernel.
+pub type SigmoidKernelDensityEstimation = KernelDensityEstimation<SigmoidKernel>;
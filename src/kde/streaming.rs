@@ -0,0 +1,322 @@
+//! Streaming kernel density estimation with bounded memory.
+
+extern crate rand;
+
+use density::Density;
+use kde::kernel::Kernel;
+use self::rand::Rng;
+
+/// A weighted kernel summarising one or more merged observations.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StreamingComponent {
+    /// Weighted mean of the merged observations.
+    pub location: f64,
+    /// Total weight of the merged observations.
+    pub weight: f64,
+    /// Weighted variance of the merged observations about the location.
+    pub variance: f64,
+    /// Bandwidth of the component kernel.
+    pub bandwidth: f64,
+}
+
+/// Kernel density estimation over a stream of observations using a bounded
+/// number of weighted kernel components.
+///
+/// Each observation is added as a component with weight one. When there are
+/// more components than the capacity, the pair of neighbouring components
+/// whose merge least increases the weighted sum of squared distances of the
+/// observations to their component locations, w_1 * w_2 * (x_1 - x_2)^2 /
+/// (w_1 + w_2), is merged into one component. Merged components keep the
+/// weighted mean and variance s^2 of their observations and use a wider
+/// kernel with bandwidth sqrt(h^2 + s^2 / m_2), where m_2 is the kernel
+/// second moment. This preserves the mean and variance of the estimation,
+/// and until the capacity is reached the estimation is exactly the kernel
+/// density estimation of the observations.
+///
+/// With forgetting factor l < 1, every component weight is multiplied by l
+/// for each new observation, so an observation k steps old has weight l^k
+/// and the estimation tracks roughly the last 1 / (1 - l) observations.
+/// Components whose weight underflows to zero are dropped.
+///
+/// Each observation costs O(c) for capacity c, as do density and cumulative
+/// density evaluations.
+///
+/// # Panics
+///
+/// Density evaluations and draws panic before the first observation.
+pub struct StreamingKernelDensityEstimation<K: Kernel> {
+    pub bandwidth: f64,
+    pub kernel: K,
+    /// Maximum number of components.
+    capacity: usize,
+    /// Factor applied to the existing weights for each new observation.
+    forgetting: f64,
+    /// Components in increasing order of location.
+    components: Vec<StreamingComponent>,
+}
+
+impl<K: Kernel> StreamingKernelDensityEstimation<K> {
+    /// Construct a new streaming kernel density estimation, without any
+    /// observations, for a given bandwidth, kernel, capacity and forgetting
+    /// factor.
+    ///
+    /// The bandwidth is fixed, so a bandwidth rule applied to an initial
+    /// window of the stream is a reasonable choice.
+    ///
+    /// # Panics
+    ///
+    /// Bandwidth must be greater than zero, capacity must be greater than
+    /// zero and the forgetting factor must be in (0, 1], with 1 for no
+    /// forgetting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{NormalKernel, StreamingKernelDensityEstimation};
+    ///
+    /// let mut kde = StreamingKernelDensityEstimation::new(0.5, NormalKernel, 100, 0.999);
+    ///
+    /// for i in 0..1000 {
+    ///     kde.observe((i % 10) as f64);
+    /// }
+    ///
+    /// assert!(kde.components().len() <= 100);
+    /// assert!(kde.density(4.5) > 0.0);
+    /// ```
+    pub fn new(bandwidth: f64,
+               kernel: K,
+               capacity: usize,
+               forgetting: f64)
+               -> StreamingKernelDensityEstimation<K> {
+        assert!(bandwidth > 0.0);
+        assert!(capacity > 0);
+        assert!(0.0 < forgetting && forgetting <= 1.0);
+
+        StreamingKernelDensityEstimation {
+            bandwidth,
+            kernel,
+            capacity,
+            forgetting,
+            components: Vec::with_capacity(capacity + 1),
+        }
+    }
+
+    /// Return the maximum number of components.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Set the maximum number of components, merging components until there
+    /// are no more than the new capacity.
+    ///
+    /// # Panics
+    ///
+    /// Capacity must be greater than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::{NormalKernel, StreamingKernelDensityEstimation};
+    ///
+    /// let mut kde = StreamingKernelDensityEstimation::new(0.5, NormalKernel, 100, 1.0);
+    /// kde.observe_all(&[0.0, 1.0, 2.0, 3.0]);
+    ///
+    /// kde.set_capacity(2);
+    /// assert_eq!(kde.components().len(), 2);
+    /// assert_eq!(kde.total_weight(), 4.0);
+    /// ```
+    pub fn set_capacity(&mut self, capacity: usize) {
+        assert!(capacity > 0);
+
+        self.capacity = capacity;
+        while self.components.len() > self.capacity {
+            self.merge_closest();
+        }
+    }
+
+    /// Return the factor applied to the existing weights for each new
+    /// observation.
+    pub fn forgetting(&self) -> f64 {
+        self.forgetting
+    }
+
+    /// Set the factor applied to the existing weights for each new
+    /// observation, from the next observation on.
+    ///
+    /// # Panics
+    ///
+    /// The forgetting factor must be in (0, 1], with 1 for no forgetting.
+    pub fn set_forgetting(&mut self, forgetting: f64) {
+        assert!(0.0 < forgetting && forgetting <= 1.0);
+
+        self.forgetting = forgetting;
+    }
+
+    /// Add an observation to the estimation.
+    ///
+    /// # Panics
+    ///
+    /// The observation must be finite.
+    pub fn observe(&mut self, x: f64) {
+        assert!(x.is_finite());
+
+        if self.forgetting < 1.0 {
+            for component in &mut self.components {
+                component.weight *= self.forgetting;
+            }
+
+            self.components.retain(|component| component.weight > 0.0);
+        }
+
+        let index = self.components.partition_point(|component| component.location <= x);
+        self.components.insert(index,
+                               StreamingComponent {
+                                   location: x,
+                                   weight: 1.0,
+                                   variance: 0.0,
+                                   bandwidth: self.bandwidth,
+                               });
+
+        if self.components.len() > self.capacity {
+            self.merge_closest();
+        }
+    }
+
+    /// Add a slice of observations to the estimation, in order.
+    pub fn observe_all(&mut self, xs: &[f64]) {
+        for &x in xs {
+            self.observe(x);
+        }
+    }
+
+    /// Return the components in increasing order of location.
+    pub fn components(&self) -> &[StreamingComponent] {
+        &self.components
+    }
+
+    /// Return the total weight of the components. Without forgetting, this
+    /// is the number of observations.
+    pub fn total_weight(&self) -> f64 {
+        self.components.iter().map(|component| component.weight).sum()
+    }
+
+    /// Merge the pair of neighbouring components with the smallest merge
+    /// cost.
+    fn merge_closest(&mut self) {
+        let mut best = 0;
+        let mut best_cost = f64::INFINITY;
+
+        for (i, pair) in self.components.windows(2).enumerate() {
+            let (a, b) = (&pair[0], &pair[1]);
+            let cost = a.weight * b.weight * (a.location - b.location).powi(2) /
+                       (a.weight + b.weight);
+
+            if cost < best_cost {
+                best = i;
+                best_cost = cost;
+            }
+        }
+
+        let b = self.components.remove(best + 1);
+        let a = &mut self.components[best];
+
+        let weight = a.weight + b.weight;
+        let location = (a.weight * a.location + b.weight * b.location) / weight;
+        let variance = (a.weight * (a.variance + (a.location - location).powi(2)) +
+                        b.weight * (b.variance + (b.location - location).powi(2))) /
+                       weight;
+
+        a.location = location;
+        a.weight = weight;
+        a.variance = variance;
+        a.bandwidth = (self.bandwidth.powi(2) + variance / self.kernel.second_moment()).sqrt();
+    }
+}
+
+impl<K: Kernel> Density for StreamingKernelDensityEstimation<K> {
+    /// Calculate a value of the streaming kernel density function for a
+    /// given value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{StreamingKernelDensityEstimation, UniformKernel};
+    ///
+    /// let mut kde = StreamingKernelDensityEstimation::new(0.1, UniformKernel, 10, 1.0);
+    /// kde.observe_all(&[9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0]);
+    ///
+    /// assert_eq!(kde.density(4.0), 0.5);
+    /// ```
+    fn density(&self, x: f64) -> f64 {
+        assert!(!self.components.is_empty());
+
+        let mut sum = 0.0;
+        let mut total_weight = 0.0;
+        for component in &self.components {
+            sum += component.weight *
+                   self.kernel.density((x - component.location) / component.bandwidth) /
+                   component.bandwidth;
+            total_weight += component.weight;
+        }
+
+        sum / total_weight
+    }
+
+    /// Calculate a value of the cumulative density function for this
+    /// streaming kernel density estimation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::density::Density;
+    /// use kernel_density::kde::{StreamingKernelDensityEstimation, UniformKernel};
+    ///
+    /// let mut kde = StreamingKernelDensityEstimation::new(0.1, UniformKernel, 10, 1.0);
+    /// kde.observe_all(&[9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0]);
+    ///
+    /// assert_eq!(kde.cdf(0.1), 0.1);
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        assert!(!self.components.is_empty());
+
+        let mut sum = 0.0;
+        let mut total_weight = 0.0;
+        for component in &self.components {
+            sum += component.weight *
+                   self.kernel.cdf((x - component.location) / component.bandwidth);
+            total_weight += component.weight;
+        }
+
+        (sum / total_weight).min(1.0)
+    }
+
+    /// Draw a random value from this streaming kernel density estimation.
+    ///
+    /// A component is chosen in proportion to its weight and kernel noise
+    /// scaled by its bandwidth is added to its location.
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        assert!(!self.components.is_empty());
+
+        let mut target = rng.next_f64() * self.total_weight();
+        for component in &self.components {
+            if target < component.weight {
+                return component.location + component.bandwidth * self.kernel.sample(rng);
+            }
+
+            target -= component.weight;
+        }
+
+        let last = &self.components[self.components.len() - 1];
+        last.location + last.bandwidth * self.kernel.sample(rng)
+    }
+}
@@ -0,0 +1,47 @@
+//! Triangular kernel density estimation functions.
+
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+
+/// The Triangular kernel.
+///
+/// k(x) = 1 - abs(x) for abs(x) <= 1 and 0 otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct TriangularKernel;
+
+impl Kernel for TriangularKernel {
+    fn density(&self, x: f64) -> f64 {
+        if x.abs() <= 1.0 {
+            1.0 - x.abs()
+        } else {
+            0.0
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x >= 1.0 {
+            1.0
+        } else if x >= 0.0 {
+            1.0 - 0.5 * (1.0 - x).powi(2)
+        } else if x > -1.0 {
+            0.5 * (1.0 + x).powi(2)
+        } else {
+            0.0
+        }
+    }
+
+    fn support(&self) -> f64 {
+        1.0
+    }
+
+    fn second_moment(&self) -> f64 {
+        1.0 / 6.0
+    }
+
+    fn roughness(&self) -> f64 {
+        2.0 / 3.0
+    }
+}
+
+/// Kernel density estimation using the Triangular kernel.
+pub type TriangularKernelDensityEstimation = KernelDensityEstimation<TriangularKernel>;
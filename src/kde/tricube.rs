@@ -0,0 +1,54 @@
+//! Tricube kernel density estimation functions.
+
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+
+/// The Tricube kernel.
+///
+/// k(x) = 70 * (1 - abs(x)^3)^3 / 81 for abs(x) <= 1 and 0 otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct TricubeKernel;
+
+impl Kernel for TricubeKernel {
+    fn density(&self, x: f64) -> f64 {
+        if x.abs() <= 1.0 {
+            70.0 * (1.0 - x.abs().powi(3)).powi(3) / 81.0
+        } else {
+            0.0
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x >= 1.0 {
+            1.0
+        } else if x > -1.0 {
+            let a = x.abs();
+            let integral = 70.0 *
+                           (a - 0.75 * a.powi(4) + 3.0 * a.powi(7) / 7.0 - 0.1 * a.powi(10)) /
+                           81.0;
+
+            if x >= 0.0 {
+                0.5 + integral
+            } else {
+                0.5 - integral
+            }
+        } else {
+            0.0
+        }
+    }
+
+    fn support(&self) -> f64 {
+        1.0
+    }
+
+    fn second_moment(&self) -> f64 {
+        35.0 / 243.0
+    }
+
+    fn roughness(&self) -> f64 {
+        175.0 / 247.0
+    }
+}
+
+/// Kernel density estimation using the Tricube kernel.
+pub type TricubeKernelDensityEstimation = KernelDensityEstimation<TricubeKernel>;
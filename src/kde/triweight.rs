@@ -0,0 +1,47 @@
+//! Triweight kernel density estimation functions.
+
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+
+/// The Triweight kernel.
+///
+/// k(x) = 35 * (1 - x^2)^3 / 32 for abs(x) <= 1 and 0 otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct TriweightKernel;
+
+impl Kernel for TriweightKernel {
+    fn density(&self, x: f64) -> f64 {
+        if x.abs() <= 1.0 {
+            35.0 * (1.0 - x.powi(2)).powi(3) / 32.0
+        } else {
+            0.0
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x >= 1.0 {
+            1.0
+        } else if x > -1.0 {
+            // Horner form keeps rounding errors from breaking monotonicity.
+            let x2 = x * x;
+            0.5 + x * (35.0 + x2 * (-35.0 + x2 * (21.0 - 5.0 * x2))) / 32.0
+        } else {
+            0.0
+        }
+    }
+
+    fn support(&self) -> f64 {
+        1.0
+    }
+
+    fn second_moment(&self) -> f64 {
+        1.0 / 9.0
+    }
+
+    fn roughness(&self) -> f64 {
+        350.0 / 429.0
+    }
+}
+
+/// Kernel density estimation using the Triweight kernel.
+pub type TriweightKernelDensityEstimation = KernelDensityEstimation<TriweightKernel>;
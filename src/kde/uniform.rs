@@ -1,66 +1,79 @@
 //! Uniform kernel density estimation functions.
 
-use density::Density;
+extern crate rand;
 
-pub struct UniformKernelDensityEstimation {
-    pub samples: Vec<f64>,
-    pub bandwidth: f64,
-}
+use kde::estimation::KernelDensityEstimation;
+use kde::kernel::Kernel;
+use self::rand::Rng;
+
+/// The Uniform kernel.
+///
+/// k(x) = 0.5 for abs(x) <= 1 and 0 otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct UniformKernel;
 
-impl Density for UniformKernelDensityEstimation {
-    /// Calculate a value of the kernel density function for a given value.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// extern crate kernel_density;
-    ///
-    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
-    /// let bandwidth = 0.1;
-    /// let kde = kernel_density::kde::uniform(&samples, bandwidth);
-    ///
-    /// assert_eq!(kde.density(4.0), 0.5);
-    /// ```
+impl Kernel for UniformKernel {
     fn density(&self, x: f64) -> f64 {
-        let length = self.samples.len();
+        if x.abs() <= 1.0 {
+            0.5
+        } else {
+            0.0
+        }
+    }
 
-        let mut sum = 0.0;
-        for sample in &self.samples {
-            if (x - sample).abs() / self.bandwidth <= 1.0 {
-                sum += 0.5
-            }
+    fn cdf(&self, x: f64) -> f64 {
+        if x >= 1.0 {
+            1.0
+        } else if x > -1.0 {
+            0.5 * (x + 1.0)
+        } else {
+            0.0
         }
+    }
 
-        sum / (length as f64 * self.bandwidth)
+    fn derivative(&self, _x: f64) -> f64 {
+        0.0
     }
 
-    /// Calculate a value of the cumulative density function for this kernel
-    /// density estimation.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// extern crate kernel_density;
-    ///
-    /// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
-    /// let bandwidth = 0.1;
-    /// let kde = kernel_density::kde::uniform(&samples, bandwidth);
-    ///
-    /// assert_eq!(kde.cdf(0.1), 0.1);
-    /// ```
-    fn cdf(&self, x: f64) -> f64 {
-        let length = self.samples.len();
+    fn support(&self) -> f64 {
+        1.0
+    }
 
-        let mut sum = 0.0;
-        for sample in &self.samples {
-            let rescaled: f64 = (x - sample) / self.bandwidth;
-            if rescaled >= 1.0 {
-                sum += 1.0;
-            } else if rescaled > -1.0 {
-                sum += 0.5 * (rescaled + 1.0);
-            }
+    fn second_moment(&self) -> f64 {
+        1.0 / 3.0
+    }
+
+    fn roughness(&self) -> f64 {
+        0.5
+    }
+
+    fn convolution(&self, x: f64) -> f64 {
+        let x = x.abs();
+
+        if x <= 2.0 {
+            0.25 * (2.0 - x)
+        } else {
+            0.0
         }
+    }
 
-        sum / length as f64
+    fn sample(&self, rng: &mut dyn Rng) -> f64 {
+        2.0 * rng.next_f64() - 1.0
     }
 }
+
+/// Kernel density estimation using the Uniform kernel.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(9.0, 8.0, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0);
+/// let bandwidth = 0.1;
+/// let kde = kernel_density::kde::uniform(&samples, bandwidth);
+///
+/// assert_eq!(kde.density(4.0), 0.5);
+/// assert_eq!(kde.cdf(0.1), 0.1);
+/// ```
+pub type UniformKernelDensityEstimation = KernelDensityEstimation<UniformKernel>;
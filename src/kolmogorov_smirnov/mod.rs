@@ -47,11 +47,11 @@ pub fn test(xs: &[f64], ys: &[f64], confidence: f64) -> TestResult {
     let is_rejected = reject_probability > confidence;
 
     TestResult {
-        is_rejected: is_rejected,
-        statistic: statistic,
-        reject_probability: reject_probability,
-        critical_value: critical_value,
-        confidence: confidence,
+        is_rejected,
+        statistic,
+        reject_probability,
+        critical_value,
+        confidence,
     }
 }
 
@@ -144,7 +144,7 @@ fn calculate_reject_probability(statistic: f64, n1: usize, n2: usize) -> f64 {
 
     let reject_probability = 1.0 - probability_kolmogorov_smirnov(term);
 
-    assert!(0.0 <= reject_probability && reject_probability <= 1.0);
+    assert!((0.0..=1.0).contains(&reject_probability));
     reject_probability
 }
 
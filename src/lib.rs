@@ -1,5 +1,8 @@
 //! Nonparametric statistics.
 
+pub mod anomaly;
 pub mod density;
 pub mod kde;
 pub mod kolmogorov_smirnov;
+pub mod multimodality;
+pub mod regression;
@@ -0,0 +1,546 @@
+//! Tests for the number of modes of a distribution.
+//!
+//! Silverman's test uses the critical bandwidth h_k, the smallest bandwidth
+//! at which the Normal kernel density estimation of the sample has at most k
+//! modes. The number of modes of a Normal kernel density estimation never
+//! increases with the bandwidth, so a large critical bandwidth is evidence of
+//! more than k modes. Its significance is assessed with a smoothed bootstrap.
+//!
+//! Hartigan's dip test uses the dip statistic, the maximum distance between
+//! the empirical distribution function and the closest unimodal distribution
+//! function. Its significance is assessed with reference quantiles of the
+//! dip of uniform samples, the least favourable unimodal distribution.
+
+extern crate rand;
+
+mod tables;
+
+use kde::{KernelDensityEstimation, NormalKernel};
+use kde::modes::golden;
+use self::rand::{Rand, Rng};
+use self::rand::distributions::normal::StandardNormal;
+
+/// Grid steps per bandwidth when counting modes.
+const GRID_RESOLUTION: f64 = 8.0;
+
+/// Samples further than this many bandwidths from a point are ignored when
+/// counting modes. Each contributes less than 1e-21 of the kernel maximum.
+const KERNEL_CUTOFF: f64 = 10.0;
+
+/// Density derivatives, times the bandwidth, smaller than this fraction of
+/// the maximum density are treated as flat when counting modes, ignoring
+/// rounding noise in the tails.
+const FLAT_TOLERANCE: f64 = 1e-12;
+
+/// Relative width of the bracket at which the critical bandwidth bisection
+/// stops.
+const BANDWIDTH_TOLERANCE: f64 = 1e-6;
+
+/// Silverman's test result.
+pub struct SilvermanTest {
+    /// Number of modes under the null hypothesis.
+    pub modes: usize,
+    /// Smallest bandwidth giving at most that number of modes.
+    pub critical_bandwidth: f64,
+    /// Smoothed bootstrap p-value for the null hypothesis of at most that
+    /// number of modes.
+    pub p_value: f64,
+}
+
+/// Hartigan's dip test result.
+pub struct DipTest {
+    /// The dip statistic.
+    pub statistic: f64,
+    /// p-value for the null hypothesis of a unimodal distribution.
+    pub p_value: f64,
+}
+
+/// Count the modes of the Normal kernel density estimation of a sample with
+/// a given bandwidth.
+///
+/// The Normal kernel is convex further than a bandwidth from its centre, so
+/// the density is convex away from the samples and every mode is within a
+/// bandwidth of a sample. The density derivative is evaluated exactly on
+/// grids of eight points per bandwidth covering these intervals around the
+/// sorted samples, and the modes are its changes of sign from positive to
+/// negative.
+///
+/// A mode that is only just forming has the derivative dip below zero over
+/// an interval much shorter than the grid step. Each local extreme of the
+/// derivative on the grid is therefore refined by golden section search and
+/// its sign is counted too, so a mode is missed only if the derivative has
+/// more than one local extreme within a grid step. The cost is linear in the
+/// number of samples however small the bandwidth is against their range.
+///
+/// # Panics
+///
+/// Bandwidth must be greater than zero and the sample set must be
+/// non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 0.1, 0.2, 5.0, 5.1, 5.2);
+///
+/// assert_eq!(kernel_density::multimodality::count_modes(&samples, 0.5), 2);
+/// assert_eq!(kernel_density::multimodality::count_modes(&samples, 5.0), 1);
+/// ```
+pub fn count_modes(samples: &[f64], bandwidth: f64) -> usize {
+    let kde = KernelDensityEstimation::new(samples, bandwidth, NormalKernel);
+    let sorted = kde.samples();
+    let length = sorted.len();
+
+    // Unnormalised density and its derivative times the bandwidth, from the
+    // samples within the kernel cutoff.
+    let cutoff = KERNEL_CUTOFF * bandwidth;
+    let evaluate = |x: f64| {
+        let low = sorted.partition_point(|&sample| sample < x - cutoff);
+        let high = sorted.partition_point(|&sample| sample <= x + cutoff);
+
+        let mut density = 0.0;
+        let mut slope = 0.0;
+        for sample in &sorted[low..high] {
+            let u = (sample - x) / bandwidth;
+            let kernel = (-0.5 * u * u).exp();
+            density += kernel;
+            slope += u * kernel;
+        }
+
+        (density, slope)
+    };
+    let slope = |x: f64| evaluate(x).1;
+
+    // Derivative at the grid points and at the refined local extremes of the
+    // derivative, over the runs of samples whose bandwidth intervals overlap.
+    let mut points: Vec<(f64, f64)> = vec![];
+    let mut maximum: f64 = 0.0;
+    let mut start = 0;
+    while start < length {
+        let mut end = start + 1;
+        while end < length && sorted[end] - sorted[end - 1] <= 2.0 * bandwidth {
+            end += 1;
+        }
+
+        let min = sorted[start] - bandwidth;
+        let max = sorted[end - 1] + bandwidth;
+        let steps = (((max - min) / bandwidth * GRID_RESOLUTION).ceil() as usize).max(1);
+        let xs: Vec<f64> = (0..(steps + 1))
+            .map(|i| min + (max - min) * i as f64 / steps as f64)
+            .collect();
+
+        let mut slopes = Vec::with_capacity(xs.len());
+        for &x in &xs {
+            let (density, slope) = evaluate(x);
+            maximum = maximum.max(density);
+            slopes.push(slope);
+            points.push((x, slope));
+        }
+
+        for j in 0..xs.len() {
+            let before = if j > 0 { j - 1 } else { j };
+            let after = if j + 1 < xs.len() { j + 1 } else { j };
+            if before == after {
+                continue;
+            }
+
+            let low = (before == j || slopes[before] > slopes[j]) &&
+                      (after == j || slopes[after] >= slopes[j]);
+            let high = (before == j || slopes[before] < slopes[j]) &&
+                       (after == j || slopes[after] <= slopes[j]);
+
+            let refined = if low {
+                golden(slope, xs[before], xs[after], bandwidth)
+            } else if high {
+                golden(|x| -slope(x), xs[before], xs[after], bandwidth)
+            } else {
+                continue;
+            };
+            points.push((refined, slope(refined)));
+        }
+
+        start = end;
+    }
+
+    points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    // Count changes from increasing to decreasing, skipping flat points. The
+    // density is convex between the grids, so the derivative increases there
+    // and cannot pass over a mode.
+    let tolerance = FLAT_TOLERANCE * maximum;
+    let mut modes = 0;
+    let mut increasing = false;
+    for &(_, slope) in &points {
+        if slope > tolerance {
+            increasing = true;
+        } else if slope < -tolerance {
+            if increasing {
+                modes += 1;
+            }
+            increasing = false;
+        }
+    }
+
+    modes
+}
+
+/// Calculate the critical bandwidth for a number of modes, the smallest
+/// bandwidth at which the Normal kernel density estimation of a sample has
+/// at most that many modes.
+///
+/// The bandwidth is located by bisection to a relative precision of 1e-6,
+/// counting modes with `count_modes`. The bracket is not narrowed below 1e-6
+/// of the smallest gap between distinct samples, where every distinct
+/// sample is a separate mode.
+///
+/// # Panics
+///
+/// The number of modes must be greater than zero and the sample must have
+/// more distinct values than the number of modes.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::multimodality::{count_modes, critical_bandwidth};
+///
+/// let samples = vec!(0.0, 0.1, 0.2, 5.0, 5.1, 5.2);
+/// let bandwidth = critical_bandwidth(&samples, 1);
+///
+/// assert_eq!(count_modes(&samples, bandwidth), 1);
+/// assert_eq!(count_modes(&samples, 0.99 * bandwidth), 2);
+/// ```
+pub fn critical_bandwidth(samples: &[f64], modes: usize) -> f64 {
+    assert!(modes > 0);
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|x_1, x_2| x_1.partial_cmp(x_2).unwrap());
+    sorted.dedup();
+    assert!(sorted.len() > modes);
+
+    let range = sorted[sorted.len() - 1] - sorted[0];
+    let smallest_gap = sorted.windows(2).map(|pair| pair[1] - pair[0]).fold(range, f64::min);
+
+    let mut upper = range;
+    while count_modes(samples, upper) > modes {
+        upper *= 2.0;
+    }
+
+    let mut lower = 0.5 * upper;
+    while lower > BANDWIDTH_TOLERANCE * smallest_gap && count_modes(samples, lower) <= modes {
+        upper = lower;
+        lower *= 0.5;
+    }
+
+    while upper - lower > BANDWIDTH_TOLERANCE * upper {
+        let middle = 0.5 * (lower + upper);
+        if count_modes(samples, middle) > modes {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+
+    upper
+}
+
+/// Perform Silverman's test of the null hypothesis that a sample comes from
+/// a distribution with at most a given number of modes.
+///
+/// Each bootstrap replicate resamples the sample with replacement, adds
+/// Normal noise with the critical bandwidth and rescales to the sample
+/// variance:
+///
+/// y_i = m + (x_i - m + h_k * e_i) / sqrt(1 + h_k^2 / s^2)
+///
+/// Where x_i are the resampled values with mean m, e_i are standard Normal
+/// values and s^2 is the sample variance. The p-value is the proportion of
+/// replicates whose Normal kernel density estimation with bandwidth h_k has
+/// more than k modes.
+///
+/// # Panics
+///
+/// The number of modes must be greater than zero, the sample must have more
+/// distinct values than the number of modes and there must be at least one
+/// replicate.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+/// extern crate rand;
+///
+/// let samples = vec!(0.0, 0.1, 0.2, 0.3, 5.0, 5.1, 5.2, 5.3);
+/// let mut rng = rand::thread_rng();
+///
+/// let test = kernel_density::multimodality::silverman(&samples, 1, 100, &mut rng);
+///
+/// if test.p_value < 0.05 {
+///     println!("More than one mode with critical bandwidth {}.",
+///              test.critical_bandwidth);
+/// }
+/// ```
+pub fn silverman(samples: &[f64], modes: usize, replicates: usize, rng: &mut dyn Rng)
+                 -> SilvermanTest {
+    assert!(replicates > 0);
+
+    let bandwidth = critical_bandwidth(samples, modes);
+
+    let length = samples.len();
+    let n = length as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let scale = 1.0 / (1.0 + bandwidth.powi(2) / variance).sqrt();
+
+    let mut rng = rng;
+    let mut exceedances = 0;
+    let mut replicate = vec![0.0; length];
+
+    for _ in 0..replicates {
+        for value in &mut replicate {
+            let index = ((rng.next_f64() * n) as usize).min(length - 1);
+            *value = samples[index];
+        }
+
+        let replicate_mean = replicate.iter().sum::<f64>() / n;
+        for value in &mut replicate {
+            let StandardNormal(e) = StandardNormal::rand(&mut rng);
+            *value = replicate_mean + (*value - replicate_mean + bandwidth * e) * scale;
+        }
+
+        if count_modes(&replicate, bandwidth) > modes {
+            exceedances += 1;
+        }
+    }
+
+    SilvermanTest {
+        modes,
+        critical_bandwidth: bandwidth,
+        p_value: exceedances as f64 / replicates as f64,
+    }
+}
+
+/// Calculate Hartigan's dip statistic of a sample, the maximum distance
+/// between the empirical distribution function and the closest unimodal
+/// distribution function.
+///
+/// This follows algorithm AS 217 of Hartigan (1985) as implemented in the R
+/// `diptest` package. The dip is between 1 / (2 * n) and 1 / 4 for more than
+/// one sample.
+///
+/// # Panics
+///
+/// The sample set must be non-empty.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 0.0, 0.0, 1.0, 1.0, 1.0);
+///
+/// assert_eq!(kernel_density::multimodality::dip(&samples), 0.25);
+/// ```
+pub fn dip(samples: &[f64]) -> f64 {
+    let n = samples.len();
+    assert!(n > 0);
+
+    // One-based indexing as in the published algorithm.
+    let mut x = Vec::with_capacity(n + 1);
+    x.push(0.0);
+    x.extend_from_slice(samples);
+    x[1..].sort_by(|x_1, x_2| x_1.partial_cmp(x_2).unwrap());
+
+    // The dip in units of 1 / (2 * n).
+    let mut dip = 1.0;
+
+    if n < 2 || x[n] == x[1] {
+        return dip / (2 * n) as f64;
+    }
+
+    // Greatest convex minorant indices from the left.
+    let mut mn = vec![0; n + 1];
+    mn[1] = 1;
+    for j in 2..(n + 1) {
+        mn[j] = j - 1;
+        loop {
+            let mnj = mn[j];
+            let mnmnj = mn[mnj];
+            if mnj == 1 ||
+               (x[j] - x[mnj]) * ((mnj - mnmnj) as f64) <
+               (x[mnj] - x[mnmnj]) * ((j - mnj) as f64) {
+                break;
+            }
+            mn[j] = mnmnj;
+        }
+    }
+
+    // Least concave majorant indices from the right.
+    let mut mj = vec![0; n + 1];
+    mj[n] = n;
+    for k in (1..n).rev() {
+        mj[k] = k + 1;
+        loop {
+            let mjk = mj[k];
+            let mjmjk = mj[mjk];
+            if mjk == n ||
+               (x[k] - x[mjk]) * (mjk as f64 - mjmjk as f64) <
+               (x[mjk] - x[mjmjk]) * (k as f64 - mjk as f64) {
+                break;
+            }
+            mj[k] = mjmjk;
+        }
+    }
+
+    let mut gcm = vec![0; n + 2];
+    let mut lcm = vec![0; n + 2];
+    let mut low = 1;
+    let mut high = n;
+
+    loop {
+        // Convex minorant change points between low and high.
+        gcm[1] = high;
+        let mut i = 1;
+        while gcm[i] > low {
+            gcm[i + 1] = mn[gcm[i]];
+            i += 1;
+        }
+        let l_gcm = i;
+        let mut ig = l_gcm;
+        let mut ix = ig - 1;
+
+        // Concave majorant change points between low and high.
+        lcm[1] = low;
+        let mut i = 1;
+        while lcm[i] < high {
+            lcm[i + 1] = mj[lcm[i]];
+            i += 1;
+        }
+        let l_lcm = i;
+        let mut ih = l_lcm;
+        let mut iv = 2;
+
+        // Largest distance between the minorant and the majorant.
+        let mut d = 0.0;
+        if l_gcm != 2 || l_lcm != 2 {
+            loop {
+                let gcmix = gcm[ix];
+                let lcmiv = lcm[iv];
+
+                if gcmix > lcmiv {
+                    let gcmi1 = gcm[ix + 1];
+                    let dx = (lcmiv as f64 - gcmi1 as f64 + 1.0) -
+                             (x[lcmiv] - x[gcmi1]) * (gcmix - gcmi1) as f64 /
+                             (x[gcmix] - x[gcmi1]);
+                    iv += 1;
+                    if dx >= d {
+                        d = dx;
+                        ig = ix + 1;
+                        ih = iv - 1;
+                    }
+                } else {
+                    let lcmiv1 = lcm[iv - 1];
+                    let dx = (x[gcmix] - x[lcmiv1]) * (lcmiv - lcmiv1) as f64 /
+                             (x[lcmiv] - x[lcmiv1]) -
+                             (gcmix as f64 - lcmiv1 as f64 - 1.0);
+                    ix -= 1;
+                    if dx >= d {
+                        d = dx;
+                        ig = ix + 1;
+                        ih = iv;
+                    }
+                }
+
+                ix = ix.max(1);
+                iv = iv.min(l_lcm);
+
+                if gcm[ix] == lcm[iv] {
+                    break;
+                }
+            }
+        } else {
+            d = 1.0;
+        }
+
+        if d < dip {
+            break;
+        }
+
+        // Dip of the convex minorant.
+        let mut dip_l: f64 = 0.0;
+        for j in ig..l_gcm {
+            let mut max_t: f64 = 1.0;
+            let (j_, jb) = (gcm[j], gcm[j + 1]);
+            if j_ - jb > 1 && x[j_] != x[jb] {
+                let c = (j_ - jb) as f64 / (x[j_] - x[jb]);
+                for jj in jb..(j_ + 1) {
+                    let t = (jj - jb + 1) as f64 - (x[jj] - x[jb]) * c;
+                    max_t = max_t.max(t);
+                }
+            }
+            dip_l = dip_l.max(max_t);
+        }
+
+        // Dip of the concave majorant.
+        let mut dip_u: f64 = 0.0;
+        for j in ih..l_lcm {
+            let mut max_t: f64 = 1.0;
+            let (jb, j_) = (lcm[j], lcm[j + 1]);
+            if j_ - jb > 1 && x[j_] != x[jb] {
+                let c = (j_ - jb) as f64 / (x[j_] - x[jb]);
+                for jj in jb..(j_ + 1) {
+                    let t = (x[jj] - x[jb]) * c - (jj as f64 - jb as f64 - 1.0);
+                    max_t = max_t.max(t);
+                }
+            }
+            dip_u = dip_u.max(max_t);
+        }
+
+        dip = dip.max(dip_l.max(dip_u));
+
+        if low == gcm[ig] && high == lcm[ih] {
+            break;
+        }
+
+        low = gcm[ig];
+        high = lcm[ih];
+    }
+
+    dip / (2 * n) as f64
+}
+
+/// Perform Hartigan's dip test of the null hypothesis that a sample comes
+/// from a unimodal distribution.
+///
+/// The p-value is interpolated from reference quantiles of the dip of
+/// uniform samples, linearly in the quantiles of sqrt(n) * dip and in log(n)
+/// between tabulated sample sizes. Sample sizes larger than the table use the
+/// largest tabulated size, where sqrt(n) * dip has nearly converged to its
+/// limit distribution. p-values outside the tabulated probabilities are
+/// reported as 1 and 0.001.
+///
+/// # Panics
+///
+/// The sample set must have more than three elements.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// let samples = vec!(0.0, 0.1, 0.2, 0.3, 0.4, 5.0, 5.1, 5.2, 5.3, 5.4);
+/// let test = kernel_density::multimodality::dip_test(&samples);
+///
+/// assert!(test.p_value < 0.05);
+/// ```
+pub fn dip_test(samples: &[f64]) -> DipTest {
+    let length = samples.len();
+    assert!(length > 3);
+
+    let statistic = dip(samples);
+    let p_value = tables::p_value(length, statistic);
+
+    DipTest { statistic, p_value }
+}
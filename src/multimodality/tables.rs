@@ -0,0 +1,107 @@
+//! Reference quantiles of the dip of uniform samples.
+//!
+//! The quantiles were estimated by simulating the dip of uniform samples,
+//! with 200000 replicates for sizes up to 1000, 100000 for 2000 and 40000
+//! for larger sizes. The quantiles are of sqrt(n) * dip, which converges in
+//! distribution as n grows, so rows change slowly for large sizes.
+//!
+//! The table is the output of `cargo run --release --bin dip_quantiles`,
+//! which simulates with a fixed seed, so it can be reproduced exactly. See
+//! `src/bin/dip_quantiles.rs`.
+
+/// Cumulative probabilities of the tabulated quantiles.
+const PROBABILITIES: [f64; 19] = [0.0, 0.01, 0.02, 0.05, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8,
+                                  0.9, 0.95, 0.98, 0.99, 0.995, 0.998, 0.999];
+
+/// Tabulated sample sizes with quantiles of sqrt(n) * dip at each
+/// probability. The zero probability quantile is the smallest simulated
+/// value, which is the smallest possible value, sqrt(n) / (2 * n), for small
+/// sizes.
+#[allow(clippy::approx_constant)]
+const QUANTILES: [(usize, [f64; 19]); 18] = [
+    (4, [0.2500, 0.2500, 0.2500, 0.2500, 0.2500, 0.2500, 0.2500, 0.2500, 0.2500,
+         0.2500, 0.2662, 0.3153, 0.3744, 0.4138, 0.4469, 0.4633, 0.4744, 0.4837, 0.4882]),
+    (5, [0.2236, 0.2236, 0.2236, 0.2236, 0.2236, 0.2236, 0.2236, 0.2434, 0.2720,
+         0.3002, 0.3290, 0.3599, 0.3951, 0.4169, 0.4330, 0.4396, 0.4433, 0.4455, 0.4464]),
+    (6, [0.2041, 0.2041, 0.2041, 0.2041, 0.2041, 0.2263, 0.2542, 0.2788, 0.3016,
+         0.3233, 0.3445, 0.3660, 0.3900, 0.4037, 0.4392, 0.4707, 0.4956, 0.5219, 0.5379]),
+    (7, [0.1890, 0.1890, 0.1890, 0.1922, 0.2165, 0.2493, 0.2736, 0.2939, 0.3119,
+         0.3289, 0.3452, 0.3616, 0.3819, 0.4234, 0.4639, 0.4870, 0.5057, 0.5243, 0.5341]),
+    (8, [0.1768, 0.1768, 0.1846, 0.2082, 0.2313, 0.2606, 0.2816, 0.2989, 0.3139,
+         0.3279, 0.3411, 0.3552, 0.4008, 0.4353, 0.4693, 0.4883, 0.5059, 0.5264, 0.5503]),
+    (9, [0.1667, 0.1840, 0.1977, 0.2202, 0.2413, 0.2671, 0.2852, 0.2999, 0.3126,
+         0.3241, 0.3377, 0.3687, 0.4092, 0.4397, 0.4709, 0.4924, 0.5192, 0.5474, 0.5657]),
+    (10, [0.1581, 0.1929, 0.2061, 0.2273, 0.2468, 0.2698, 0.2855, 0.2983, 0.3092,
+          0.3232, 0.3478, 0.3760, 0.4128, 0.4417, 0.4780, 0.5060, 0.5288, 0.5567, 0.5735]),
+    (15, [0.1490, 0.2119, 0.2216, 0.2364, 0.2490, 0.2683, 0.2888, 0.3067, 0.3238,
+          0.3413, 0.3607, 0.3857, 0.4260, 0.4595, 0.4989, 0.5269, 0.5510, 0.5800, 0.5996]),
+    (20, [0.1479, 0.2122, 0.2196, 0.2360, 0.2541, 0.2774, 0.2954, 0.3115, 0.3281,
+          0.3474, 0.3689, 0.3951, 0.4350, 0.4706, 0.5115, 0.5411, 0.5683, 0.5995, 0.6202]),
+    (30, [0.1666, 0.2167, 0.2270, 0.2437, 0.2601, 0.2832, 0.3020, 0.3194, 0.3370,
+          0.3559, 0.3779, 0.4053, 0.4466, 0.4832, 0.5260, 0.5555, 0.5821, 0.6143, 0.6358]),
+    (50, [0.1684, 0.2221, 0.2323, 0.2495, 0.2666, 0.2903, 0.3094, 0.3272, 0.3453,
+          0.3648, 0.3872, 0.4159, 0.4589, 0.4968, 0.5427, 0.5737, 0.6022, 0.6369, 0.6639]),
+    (100, [0.1547, 0.2286, 0.2392, 0.2566, 0.2743, 0.2986, 0.3180, 0.3361, 0.3547,
+           0.3747, 0.3982, 0.4272, 0.4713, 0.5104, 0.5573, 0.5902, 0.6209, 0.6590, 0.6899]),
+    (200, [0.1778, 0.2331, 0.2438, 0.2618, 0.2797, 0.3044, 0.3242, 0.3428, 0.3616,
+           0.3818, 0.4053, 0.4349, 0.4804, 0.5205, 0.5687, 0.6025, 0.6336, 0.6743, 0.7027]),
+    (500, [0.1762, 0.2386, 0.2491, 0.2673, 0.2850, 0.3099, 0.3299, 0.3490, 0.3683,
+           0.3888, 0.4124, 0.4428, 0.4889, 0.5300, 0.5798, 0.6144, 0.6474, 0.6849, 0.7157]),
+    (1000, [0.1851, 0.2403, 0.2512, 0.2691, 0.2876, 0.3124, 0.3328, 0.3518, 0.3711,
+            0.3921, 0.4163, 0.4470, 0.4932, 0.5358, 0.5857, 0.6200, 0.6537, 0.6953, 0.7211]),
+    (2000, [0.1859, 0.2428, 0.2532, 0.2715, 0.2899, 0.3147, 0.3350, 0.3544, 0.3737,
+            0.3947, 0.4193, 0.4502, 0.4969, 0.5395, 0.5887, 0.6232, 0.6536, 0.6941, 0.7219]),
+    (5000, [0.1813, 0.2445, 0.2550, 0.2738, 0.2921, 0.3169, 0.3369, 0.3560, 0.3760,
+            0.3972, 0.4216, 0.4528, 0.4990, 0.5394, 0.5911, 0.6280, 0.6608, 0.7040, 0.7423]),
+    (10000, [0.1859, 0.2452, 0.2558, 0.2745, 0.2922, 0.3174, 0.3379, 0.3571, 0.3765,
+             0.3977, 0.4227, 0.4532, 0.5000, 0.5407, 0.5910, 0.6280, 0.6600, 0.7034, 0.7382]),
+];
+
+/// Interpolate the p-value of a dip statistic for a sample size.
+///
+/// The quantile row for the sample size is interpolated linearly in log(n)
+/// between the tabulated sizes, and the cumulative probability of sqrt(n) *
+/// dip linearly between the quantiles of that row.
+pub fn p_value(length: usize, statistic: f64) -> f64 {
+    let row = quantiles(length);
+    let statistic = (length as f64).sqrt() * statistic;
+
+    if statistic <= row[0] {
+        return 1.0;
+    }
+
+    // The probability of a dip at least as large as the statistic, so an
+    // atom at a tabulated quantile counts towards the p-value.
+    for (k, pair) in row.windows(2).enumerate() {
+        if statistic <= pair[1] {
+            let fraction = (statistic - pair[0]) / (pair[1] - pair[0]);
+            let probability = PROBABILITIES[k] +
+                              fraction * (PROBABILITIES[k + 1] - PROBABILITIES[k]);
+            return 1.0 - probability;
+        }
+    }
+
+    1.0 - PROBABILITIES[PROBABILITIES.len() - 1]
+}
+
+/// Quantile row for a sample size, interpolated in log(n).
+fn quantiles(length: usize) -> [f64; 19] {
+    let (largest, last) = QUANTILES[QUANTILES.len() - 1];
+    if length >= largest {
+        return last;
+    }
+
+    let index = QUANTILES.iter().position(|&(size, _)| size > length).unwrap();
+    let (low, low_row) = QUANTILES[index - 1];
+    let (high, high_row) = QUANTILES[index];
+
+    let fraction = ((length as f64).ln() - (low as f64).ln()) /
+                   ((high as f64).ln() - (low as f64).ln());
+
+    let mut row = [0.0; 19];
+    for (value, (a, b)) in row.iter_mut().zip(low_row.iter().zip(&high_row)) {
+        *value = a + fraction * (b - a);
+    }
+
+    row
+}
@@ -0,0 +1,79 @@
+//! Cross-validation bandwidth selection for kernel regression.
+//!
+//! The objective is minimised with the same logarithmic grid and golden
+//! section search as the kernel density estimation bandwidths in
+//! `kde::cross_validation`.
+
+use kde::Kernel;
+use kde::cross_validation::{minimise, CrossValidation};
+use regression;
+use regression::Method;
+
+/// Select a kernel regression bandwidth by leave-one-out cross-validation.
+///
+/// The objective minimised is the mean squared leave-one-out prediction
+/// error:
+///
+/// CV(h) = sum((y_i - m_h,-i(x_i))^2) / n
+///
+/// Where m_h,-i is the kernel regression with sample i left out. Each
+/// evaluation costs O(n^2) kernel evaluations. Bandwidths leaving any sample
+/// without neighbours within the kernel support have infinite objective.
+///
+/// # Panics
+///
+/// The sample set must have more than one element, there must be one
+/// response for each sample and the bounds must satisfy 0 < lower < upper.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::NormalKernel;
+/// use kernel_density::regression::{cross_validation, KernelRegression, Method};
+///
+/// let xs = vec!(0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0);
+/// let ys = vec!(0.1, 0.9, 2.2, 2.8, 4.1, 5.0, 5.8, 7.2);
+/// let cv = cross_validation::least_squares(&xs, &ys, &NormalKernel, Method::LocalLinear,
+///                                          0.1, 10.0);
+///
+/// let regression = KernelRegression::new(&xs, &ys, cv.bandwidth, NormalKernel,
+///                                        Method::LocalLinear);
+/// ```
+pub fn least_squares<K: Kernel>(xs: &[f64],
+                                ys: &[f64],
+                                kernel: &K,
+                                method: Method,
+                                lower: f64,
+                                upper: f64)
+                                -> CrossValidation {
+    let length = xs.len();
+    assert!(length > 1);
+    assert!(ys.len() == length);
+
+    let objective = |bandwidth: f64| {
+        let mut sum = 0.0;
+
+        let mut other_xs = Vec::with_capacity(length - 1);
+        let mut other_ys = Vec::with_capacity(length - 1);
+
+        for (i, (&x, &y)) in xs.iter().zip(ys).enumerate() {
+            other_xs.clear();
+            other_xs.extend_from_slice(&xs[..i]);
+            other_xs.extend_from_slice(&xs[i + 1..]);
+            other_ys.clear();
+            other_ys.extend_from_slice(&ys[..i]);
+            other_ys.extend_from_slice(&ys[i + 1..]);
+
+            let weights = regression::weights(&other_xs, x, bandwidth, kernel, method);
+            let value: f64 = weights.iter().zip(&other_ys).map(|(l, y)| l * y).sum();
+
+            sum += (y - value).powi(2);
+        }
+
+        sum / length as f64
+    };
+
+    minimise(objective, lower, upper)
+}
@@ -0,0 +1,265 @@
+//! Kernel regression.
+//!
+//! Kernel regression smooths paired samples (x_i, y_i) to estimate the
+//! regression function m(x) = E(Y | X = x) using the same kernels as kernel
+//! density estimation. Both estimators here are linear smoothers, so the
+//! fitted value at x is a weighted sum of the responses:
+//!
+//! m(x) = sum(l_i(x) * y_i)
+//!
+//! Bandwidths from the rules in `kde::bandwidth` applied to the x samples are
+//! reasonable starting points. `cross_validation::least_squares` selects a
+//! bandwidth minimising the leave-one-out prediction error.
+
+pub mod cross_validation;
+
+use kde::Kernel;
+use std::f64;
+
+/// Relative weighted variance of the sample distances below which a local
+/// linear fit is treated as degenerate.
+const DEGENERATE_VARIANCE: f64 = 1e-12;
+
+/// Kernel regression estimator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    /// Locally constant fit, the kernel weighted mean of the responses:
+    ///
+    /// l_i(x) = k((x_i - x) / h) / sum(k((x_j - x) / h))
+    NadarayaWatson,
+    /// Locally linear fit by kernel weighted least squares. This removes the
+    /// design bias of the Nadaraya-Watson estimator at the edges of the
+    /// samples and where the samples are unevenly spaced.
+    LocalLinear,
+}
+
+/// Fitted value of a kernel regression.
+pub struct Fit {
+    /// Estimate of the regression function.
+    pub value: f64,
+    /// Pointwise standard error of the estimate.
+    pub standard_error: f64,
+}
+
+/// Kernel regression for paired samples, bandwidth, kernel and estimator.
+///
+/// Standard errors assume a constant residual variance sigma^2. With L the
+/// smoother matrix of weights l_j(x_i), the residual variance is estimated
+/// as:
+///
+/// sigma^2 = sum((y_i - m(x_i))^2) / (n - 2 * tr(L) + tr(L^T * L))
+///
+/// And the standard error at x is sigma * sqrt(sum(l_i(x)^2)). This is the
+/// variance of the smoother around its expectation and does not include the
+/// smoothing bias.
+pub struct KernelRegression<K: Kernel> {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    bandwidth: f64,
+    kernel: K,
+    method: Method,
+    variance: f64,
+}
+
+impl<K: Kernel> KernelRegression<K> {
+    /// Construct a new kernel regression for given paired samples, bandwidth,
+    /// kernel and estimator.
+    ///
+    /// Estimating the residual variance evaluates the fit at every sample, so
+    /// construction costs O(n^2) kernel evaluations.
+    ///
+    /// # Panics
+    ///
+    /// Bandwidth must be greater than zero, the sample set must be non-empty
+    /// and there must be one response for each sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::NormalKernel;
+    /// use kernel_density::regression::{KernelRegression, Method};
+    ///
+    /// let xs = vec!(0.0, 1.0, 2.0, 3.0, 4.0, 5.0);
+    /// let ys = vec!(0.1, 0.9, 2.2, 2.8, 4.1, 5.0);
+    /// let regression = KernelRegression::new(&xs, &ys, 1.0, NormalKernel, Method::LocalLinear);
+    /// ```
+    pub fn new(xs: &[f64],
+               ys: &[f64],
+               bandwidth: f64,
+               kernel: K,
+               method: Method)
+               -> KernelRegression<K> {
+        assert!(bandwidth > 0.0);
+
+        let length = xs.len();
+        assert!(length > 0);
+        assert!(ys.len() == length);
+
+        let mut residual_sum = 0.0;
+        let mut trace = 0.0;
+        let mut square_trace = 0.0;
+
+        for (i, (&x, &y)) in xs.iter().zip(ys).enumerate() {
+            let weights = weights(xs, x, bandwidth, &kernel, method);
+            let value: f64 = weights.iter().zip(ys).map(|(l, y)| l * y).sum();
+
+            residual_sum += (y - value).powi(2);
+            trace += weights[i];
+            square_trace += weights.iter().map(|l| l * l).sum::<f64>();
+        }
+
+        let degrees_of_freedom = length as f64 - 2.0 * trace + square_trace;
+        let variance = if degrees_of_freedom > 0.0 {
+            residual_sum / degrees_of_freedom
+        } else {
+            f64::NAN
+        };
+
+        KernelRegression {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            bandwidth,
+            kernel,
+            method,
+            variance,
+        }
+    }
+
+    /// Return the samples, in the order given.
+    pub fn xs(&self) -> &[f64] {
+        &self.xs
+    }
+
+    /// Return the responses, in the same order as `xs`.
+    pub fn ys(&self) -> &[f64] {
+        &self.ys
+    }
+
+    /// Return the bandwidth of the regression.
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Return the kernel of the regression.
+    pub fn kernel(&self) -> &K {
+        &self.kernel
+    }
+
+    /// Return the kernel regression estimator.
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    /// Return the estimated residual variance. This is NaN if there are no
+    /// residual degrees of freedom, for example when the bandwidth is small
+    /// enough for the fit to interpolate the samples.
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+
+    /// Calculate the smoother weights l_i(x) of the samples for a given
+    /// value, in sample order.
+    pub fn weights(&self, x: f64) -> Vec<f64> {
+        weights(&self.xs, x, self.bandwidth, &self.kernel, self.method)
+    }
+
+    /// Calculate the fitted value and standard error for a given value.
+    ///
+    /// The fit is NaN where no sample is within the kernel support of the
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate kernel_density;
+    ///
+    /// use kernel_density::kde::UniformKernel;
+    /// use kernel_density::regression::{KernelRegression, Method};
+    ///
+    /// let xs = vec!(0.0, 1.0, 2.0, 3.0);
+    /// let ys = vec!(1.0, 3.0, 5.0, 7.0);
+    /// let regression = KernelRegression::new(&xs, &ys, 1.5, UniformKernel,
+    ///                                        Method::NadarayaWatson);
+    ///
+    /// assert_eq!(regression.fit(0.5).value, 3.0);
+    /// ```
+    pub fn fit(&self, x: f64) -> Fit {
+        let weights = self.weights(x);
+
+        let value = weights.iter().zip(&self.ys).map(|(l, y)| l * y).sum();
+        let square_sum: f64 = weights.iter().map(|l| l * l).sum();
+
+        Fit {
+            value,
+            standard_error: (self.variance * square_sum).sqrt(),
+        }
+    }
+
+    /// Calculate the fitted values and standard errors for a slice of values.
+    pub fn fits(&self, xs: &[f64]) -> Vec<Fit> {
+        xs.iter().map(|&x| self.fit(x)).collect()
+    }
+}
+
+/// Calculate the smoother weights l_i(x) of kernel regression samples for a
+/// given value, bandwidth, kernel and estimator.
+///
+/// The weights sum to one. Where no sample is within the kernel support of
+/// the value, every weight is NaN. For local linear regression where the
+/// samples within the kernel support all share the same value, or nearly so,
+/// the local slope is undetermined and the Nadaraya-Watson weights are used
+/// instead.
+///
+/// # Examples
+///
+/// ```
+/// extern crate kernel_density;
+///
+/// use kernel_density::kde::UniformKernel;
+/// use kernel_density::regression::{weights, Method};
+///
+/// let xs = vec!(0.0, 1.0, 5.0);
+/// let weights = weights(&xs, 0.5, 1.0, &UniformKernel, Method::NadarayaWatson);
+///
+/// assert_eq!(weights, vec!(0.5, 0.5, 0.0));
+/// ```
+pub fn weights<K: Kernel>(xs: &[f64],
+                          x: f64,
+                          bandwidth: f64,
+                          kernel: &K,
+                          method: Method)
+                          -> Vec<f64> {
+    let mut weights: Vec<f64> = xs.iter()
+        .map(|x_i| kernel.density((x_i - x) / bandwidth))
+        .collect();
+
+    if method == Method::LocalLinear {
+        // Weighted moments of the rescaled distances.
+        let mut s_0 = 0.0;
+        let mut s_1 = 0.0;
+        let mut s_2 = 0.0;
+        for (x_i, k) in xs.iter().zip(&weights) {
+            let u = (x_i - x) / bandwidth;
+            s_0 += k;
+            s_1 += k * u;
+            s_2 += k * u * u;
+        }
+
+        // The weights sum to s_0 * s_2 - s_1^2, the weighted variance of the
+        // distances up to a factor, which vanishes when they are all equal.
+        if s_0 * s_2 - s_1 * s_1 > DEGENERATE_VARIANCE * s_0 * s_2 {
+            for (x_i, k) in xs.iter().zip(weights.iter_mut()) {
+                *k *= s_2 - (x_i - x) / bandwidth * s_1;
+            }
+        }
+    }
+
+    let total: f64 = weights.iter().sum();
+    for weight in &mut weights {
+        *weight /= total;
+    }
+
+    weights
+}
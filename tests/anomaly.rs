@@ -0,0 +1,116 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::anomaly::{AnomalyDetector, Method};
+use kernel_density::kde::{EpanechnikovKernel, NormalKernel};
+use common::{check, read_samples, Proportion, SamplesF64, PositiveF64};
+
+const METHODS: [Method; 4] =
+    [Method::Density, Method::LowerTail, Method::UpperTail, Method::TwoTailed];
+
+#[test]
+#[should_panic(expected="assertion failed: samples.len() > 1")]
+fn new_detector_panics_on_single_sample() {
+    AnomalyDetector::new(&[0.0], 1.0, NormalKernel, Method::Density, 0.1);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < false_alarm_rate && false_alarm_rate < 1.0")]
+fn new_detector_panics_on_zero_false_alarm_rate() {
+    AnomalyDetector::new(&[0.0, 1.0], 1.0, NormalKernel, Method::Density, 0.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < false_alarm_rate && false_alarm_rate < 1.0")]
+fn new_detector_panics_on_unit_false_alarm_rate() {
+    AnomalyDetector::new(&[0.0, 1.0], 1.0, NormalKernel, Method::UpperTail, 1.0);
+}
+
+#[test]
+fn density_flags_at_most_false_alarm_rate_of_reference() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64, rate: Proportion) -> bool {
+        // Calibration is quadratic for the Normal kernel.
+        let xs: Vec<f64> = xs.vec.into_iter().take(100).collect();
+        if xs.len() < 2 || rate.val == 0.0 || rate.val == 1.0 {
+            return true;
+        }
+
+        // Each reference sample has a higher density with its own kernel
+        // than its leave-one-out density.
+        let detector = AnomalyDetector::new(&xs, bandwidth.val, NormalKernel, Method::Density,
+                                            rate.val);
+        let flagged = xs.iter().filter(|&&x| detector.is_anomaly(x)).count();
+
+        flagged as f64 <= (rate.val * xs.len() as f64).floor()
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64, Proportion) -> bool);
+}
+
+#[test]
+fn scores_match_score() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64, x: f64) -> bool {
+        let xs: Vec<f64> = xs.vec.into_iter().take(100).collect();
+        if xs.len() < 2 {
+            return true;
+        }
+
+        METHODS.iter().all(|&method| {
+            let detector = AnomalyDetector::new(&xs, bandwidth.val, EpanechnikovKernel, method,
+                                                0.1);
+            let values = vec![x, xs[0], xs[xs.len() - 1]];
+
+            let scores: Vec<f64> = values.iter().map(|&x| detector.score(x)).collect();
+
+            detector.scores(&values) == scores
+        })
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64, f64) -> bool);
+}
+
+#[test]
+fn two_tailed_score_is_twice_smaller_tail() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let lower = AnomalyDetector::new(&xs, 0.3, NormalKernel, Method::LowerTail, 0.05);
+    let upper = AnomalyDetector::new(&xs, 0.3, NormalKernel, Method::UpperTail, 0.05);
+    let two_tailed = AnomalyDetector::new(&xs, 0.3, NormalKernel, Method::TwoTailed, 0.05);
+
+    for &x in &[-3.0, -1.0, 0.0, 0.5, 2.0] {
+        let expected = 2.0 * lower.score(x).min(upper.score(x));
+        assert!((two_tailed.score(x) - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn false_alarm_rate_holds_for_new_samples() {
+    let reference = read_samples("normal_0_1.1.tsv");
+    let xs = read_samples("normal_0_1.tsv");
+
+    for &method in &METHODS {
+        let detector = AnomalyDetector::new(&reference, 0.35, NormalKernel, method, 0.05);
+        let flagged = xs.iter().filter(|&&x| detector.is_anomaly(x)).count();
+        let rate = flagged as f64 / xs.len() as f64;
+
+        assert!(0.02 < rate && rate < 0.09);
+    }
+}
+
+#[test]
+fn outliers_are_anomalies() {
+    let xs = read_samples("http_ttime.1.tsv");
+
+    for &method in &[Method::Density, Method::UpperTail, Method::TwoTailed] {
+        let detector = AnomalyDetector::new(&xs, 50.0, NormalKernel, method, 0.01);
+
+        assert!(detector.is_anomaly(1e5));
+        assert!(detector.score(1e5) < detector.threshold());
+    }
+
+    let detector = AnomalyDetector::new(&xs, 50.0, NormalKernel, Method::Density, 0.01);
+    assert!(detector.score(1e5).is_finite());
+    assert!(!detector.is_anomaly(916.0));
+}
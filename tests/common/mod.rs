@@ -1,13 +1,18 @@
 extern crate rand;
 extern crate quickcheck;
 
-use rand::Rng;
+use kernel_density::density::Density;
+use kernel_density::kde::{Kernel, KernelDensityEstimation};
+use rand::{Rng, SeedableRng, StdRng};
 use quickcheck::{Arbitrary, Gen, QuickCheck, Testable, StdGen};
 use std::{cmp, f64};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 
 #[allow(dead_code)]
 pub const EPSILON: f64 = 1e-10;
 
+#[allow(dead_code)]
 pub fn check<A: Testable>(f: A) {
     // Adjust the size of the generator to be larger than the default size of
     // 100. The problem is this restricts generated f64 values to be in the
@@ -22,6 +27,91 @@ pub fn check<A: Testable>(f: A) {
     QuickCheck::new().gen(g).quickcheck(f);
 }
 
+/// Construct a random number generator with a fixed seed, so tests drawing
+/// random values are reproducible.
+#[allow(dead_code)]
+pub fn seeded() -> StdRng {
+    let seed: &[_] = &[1, 2, 3, 4];
+    SeedableRng::from_seed(seed)
+}
+
+/// Evaluate a weighted kernel density estimation by summing over every
+/// sample, for comparison with the sorted evaluation.
+#[allow(dead_code)]
+pub fn exhaustive<K: Kernel>(kernel: &K,
+                             xs: &[f64],
+                             weights: &[f64],
+                             bandwidth: f64,
+                             x: f64)
+                             -> (f64, f64) {
+    let mut density = 0.0;
+    let mut cdf = 0.0;
+    let mut total_weight = 0.0;
+    for (sample, weight) in xs.iter().zip(weights) {
+        density += weight * kernel.density((x - sample) / bandwidth);
+        cdf += weight * kernel.cdf((x - sample) / bandwidth);
+        total_weight += weight;
+    }
+
+    (density / (total_weight * bandwidth), cdf / total_weight)
+}
+
+/// Check the sorted evaluation of unweighted and weighted kernel density
+/// estimations of the latency sample against the exhaustive evaluation.
+#[allow(dead_code)]
+pub fn assert_sorted_evaluation_matches_exhaustive_on_http_ttime<K: Kernel + Copy>(kernel: K) {
+    let xs = read_samples("http_ttime.tsv");
+    let weights: Vec<f64> = (0..xs.len()).map(|i| (1 + i % 3) as f64).collect();
+
+    let kde = KernelDensityEstimation::new(&xs, 25.0, kernel);
+    let weighted = KernelDensityEstimation::weighted(&xs, &weights, 25.0, kernel);
+    let ones = vec![1.0; xs.len()];
+
+    // Queries across the whole sample range, including the support edges
+    // of the samples themselves.
+    let mut queries: Vec<f64> = (0..2000).map(|i| 250.0 + 2.6 * i as f64).collect();
+    queries.extend(xs.iter().take(500).map(|x| x + 25.0));
+
+    for &x in &queries {
+        let (density, cdf) = exhaustive(&kernel, &xs, &ones, 25.0, x);
+        assert!((kde.density(x) - density).abs() <= 1e-12 * density);
+        assert!((kde.cdf(x) - cdf).abs() <= 1e-12);
+
+        let (density, cdf) = exhaustive(&kernel, &xs, &weights, 25.0, x);
+        assert!((weighted.density(x) - density).abs() <= 1e-12 * density);
+        assert!((weighted.cdf(x) - cdf).abs() <= 1e-12);
+    }
+}
+
+/// Read a single-column headerless data file from the `dat` directory.
+#[allow(dead_code)]
+pub fn read_samples(name: &str) -> Vec<f64> {
+    let file = BufReader::new(File::open(format!("dat/{}", name)).unwrap());
+
+    file.lines()
+        .map(|line| line.unwrap().parse::<f64>().expect("Not a floating point number."))
+        .collect()
+}
+
+/// Read a kernel density estimation fixture from the `dat` directory as
+/// (x, density, cdf) rows.
+#[allow(dead_code)]
+pub fn read_fixture(name: &str) -> Vec<(f64, f64, f64)> {
+    let file = BufReader::new(File::open(format!("dat/{}", name)).unwrap());
+
+    file.lines()
+        .skip(1)
+        .map(|line| {
+            let line = line.unwrap();
+            let values: Vec<f64> = line.split('\t')
+                .map(|value| value.parse::<f64>().expect("Not a floating point number."))
+                .collect();
+
+            (values[0], values[1], values[2])
+        })
+        .collect()
+}
+
 /// Wrapper for generating sample data with QuickCheck.
 ///
 /// Samples must be non-empty sequences of f64 values.
@@ -65,14 +155,14 @@ impl Arbitrary for SamplesF64 {
         let size = g.gen_range(1, max);
         let vec = (0..size).map(|_| f64::arbitrary(g)).collect();
 
-        SamplesF64 { vec: vec }
+        SamplesF64 { vec }
     }
 
-    fn shrink(&self) -> Box<Iterator<Item = SamplesF64>> {
+    fn shrink(&self) -> Box<dyn Iterator<Item = SamplesF64>> {
         let vec: Vec<f64> = self.vec.clone();
-        let shrunk: Box<Iterator<Item = Vec<f64>>> = vec.shrink();
+        let shrunk: Box<dyn Iterator<Item = Vec<f64>>> = vec.shrink();
 
-        Box::new(shrunk.filter(|v| v.len() > 0).map(|v| SamplesF64 { vec: v }))
+        Box::new(shrunk.filter(|v| !v.is_empty()).map(|v| SamplesF64 { vec: v }))
     }
 }
 
@@ -124,18 +214,19 @@ impl Arbitrary for MoreThanSevenSamplesF64 {
         let size = g.gen_range(8, max);
         let vec = (0..size).map(|_| f64::arbitrary(g)).collect();
 
-        MoreThanSevenSamplesF64 { vec: vec }
+        MoreThanSevenSamplesF64 { vec }
     }
 
-    fn shrink(&self) -> Box<Iterator<Item = MoreThanSevenSamplesF64>> {
+    fn shrink(&self) -> Box<dyn Iterator<Item = MoreThanSevenSamplesF64>> {
         let vec: Vec<f64> = self.vec.clone();
-        let shrunk: Box<Iterator<Item = Vec<f64>>> = vec.shrink();
+        let shrunk: Box<dyn Iterator<Item = Vec<f64>>> = vec.shrink();
 
         Box::new(shrunk.filter(|v| v.len() > 7).map(|v| MoreThanSevenSamplesF64 { vec: v }))
     }
 }
 
 /// Wrapper for generating positive floating point numbers with QuickCheck.
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct PositiveF64 {
     pub val: f64,
@@ -150,17 +241,18 @@ impl Arbitrary for PositiveF64 {
             val = f64::arbitrary(g).abs();
         }
 
-        PositiveF64 { val: val }
+        PositiveF64 { val }
     }
 
-    fn shrink(&self) -> Box<Iterator<Item = PositiveF64>> {
-        let shrunk: Box<Iterator<Item = f64>> = self.val.shrink();
+    fn shrink(&self) -> Box<dyn Iterator<Item = PositiveF64>> {
+        let shrunk: Box<dyn Iterator<Item = f64>> = self.val.shrink();
 
         Box::new(shrunk.filter(|&v| v > 0.0).map(|v| PositiveF64 { val: v }))
     }
 }
 
 /// Wrapper for generating non-positive floating point numbers with QuickCheck.
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct NonPositiveF64 {
     pub val: f64,
@@ -170,11 +262,11 @@ impl Arbitrary for NonPositiveF64 {
     fn arbitrary<G: Gen>(g: &mut G) -> NonPositiveF64 {
         let val: f64 = -f64::arbitrary(g).abs();
 
-        NonPositiveF64 { val: val }
+        NonPositiveF64 { val }
     }
 
-    fn shrink(&self) -> Box<Iterator<Item = NonPositiveF64>> {
-        let shrunk: Box<Iterator<Item = f64>> = self.val.shrink();
+    fn shrink(&self) -> Box<dyn Iterator<Item = NonPositiveF64>> {
+        let shrunk: Box<dyn Iterator<Item = f64>> = self.val.shrink();
 
         Box::new(shrunk.filter(|&v| v <= 0.0).map(|v| NonPositiveF64 { val: v }))
     }
@@ -184,6 +276,7 @@ impl Arbitrary for NonPositiveF64 {
 ///
 /// Percentile must be f64 greater than 0.0 and less than or equal to 100.
 /// In particular, there is no 0-percentile.
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct Percentile {
     pub val: f64,
@@ -193,11 +286,11 @@ impl Arbitrary for Percentile {
     fn arbitrary<G: Gen>(g: &mut G) -> Percentile {
         let val: f64 = g.gen_range(0.0, 100.0);
 
-        Percentile { val: val }
+        Percentile { val }
     }
 
-    fn shrink(&self) -> Box<Iterator<Item = Percentile>> {
-        let shrunk: Box<Iterator<Item = f64>> = self.val.shrink();
+    fn shrink(&self) -> Box<dyn Iterator<Item = Percentile>> {
+        let shrunk: Box<dyn Iterator<Item = f64>> = self.val.shrink();
 
         Box::new(shrunk.filter(|&v| 0.0 < v && v <= 100.0).map(|v| Percentile { val: v }))
     }
@@ -207,6 +300,7 @@ impl Arbitrary for Percentile {
 ///
 /// Proportion must be f64 greater than 0.0 and less than or equal to 1.0.
 /// In particular, there is no 0-proportion.
+#[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct Proportion {
     pub val: f64,
@@ -215,11 +309,11 @@ pub struct Proportion {
 impl Arbitrary for Proportion {
     fn arbitrary<G: Gen>(g: &mut G) -> Proportion {
         let val: f64 = g.gen_range(0.0, 1.0);
-        Proportion { val: val }
+        Proportion { val }
     }
 
-    fn shrink(&self) -> Box<Iterator<Item = Proportion>> {
-        let shrunk: Box<Iterator<Item = f64>> = self.val.shrink();
+    fn shrink(&self) -> Box<dyn Iterator<Item = Proportion>> {
+        let shrunk: Box<dyn Iterator<Item = f64>> = self.val.shrink();
 
         Box::new(shrunk.filter(|&v| 0.0 < v && v <= 1.0).map(|v| Proportion { val: v }))
     }
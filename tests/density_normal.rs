@@ -5,7 +5,7 @@ extern crate rand;
 extern crate quickcheck;
 
 use kernel_density::density;
-use common::{check, PositiveF64};
+use common::{check, PositiveF64, SamplesF64};
 use std::f64;
 
 #[test]
@@ -26,7 +26,7 @@ fn normal_density_between_zero_and_one() {
         let normal = density::normal(mean, variance.val);
         let actual = normal.density(x);
 
-        0.0 <= actual && actual <= 1.0
+        (0.0..=1.0).contains(&actual)
     }
 
     check(prop as fn(f64, PositiveF64, f64) -> bool);
@@ -50,7 +50,7 @@ fn normal_density_cdf_between_zero_and_one() {
         let actual = normal.cdf(x);
 
         // Non NaN value => between 0 and 1.
-        actual.is_nan() || (0.0 <= actual && actual <= 1.0)
+        actual.is_nan() || (0.0..=1.0).contains(&actual)
     }
 
     assert!(prop(0.0, PositiveF64 { val: 2.0 }, 139327.0));
@@ -68,7 +68,7 @@ fn normal_density_cdf_is_an_increasing_function() {
         let nan_value = left.is_nan() || actual.is_nan() || right.is_nan();
 
         // Non NaN values => (left <= actual <= right).
-        nan_value || (left <= actual && actual <= right)
+        nan_value || (left..=right).contains(&actual)
     }
 
     assert!(prop(0.0, PositiveF64 { val: 1.0 }, 162237.0));
@@ -84,3 +84,115 @@ fn normal_density_cdf_is_equal_weight_around_mean() {
 
     check(prop as fn(f64, PositiveF64) -> bool);
 }
+
+#[test]
+fn normal_density_batch_matches_single_values() {
+    fn prop(mean: f64, variance: PositiveF64, xs: SamplesF64) -> bool {
+        let normal = density::normal(mean, variance.val);
+        let xs = xs.vec;
+
+        let densities = normal.densities(&xs);
+        let cdfs = normal.cdfs(&xs);
+
+        xs.iter().enumerate().all(|(i, &x)| {
+            densities[i].to_bits() == normal.density(x).to_bits() &&
+            cdfs[i].to_bits() == normal.cdf(x).to_bits()
+        })
+    }
+
+    check(prop as fn(f64, PositiveF64, SamplesF64) -> bool);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: points > 1")]
+fn linspace_panics_on_single_point() {
+    density::linspace(0.0, 1.0, 1);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: min < max")]
+fn linspace_panics_on_empty_range() {
+    density::linspace(1.0, 1.0, 10);
+}
+
+#[test]
+fn linspace_is_equally_spaced_between_min_and_max() {
+    let xs = density::linspace(-1.0, 2.0, 301);
+
+    assert_eq!(xs.len(), 301);
+    assert_eq!(xs[0], -1.0);
+    assert_eq!(xs[300], 2.0);
+
+    for pair in xs.windows(2) {
+        assert!((pair[1] - pair[0] - 0.01).abs() < 1e-12);
+    }
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < p && p < 1.0")]
+fn normal_quantile_panics_on_zero_probability() {
+    density::normal(0.0, 1.0).quantile(0.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < p && p < 1.0")]
+fn normal_quantile_panics_on_probability_one() {
+    density::normal(0.0, 1.0).quantile(1.0);
+}
+
+#[test]
+fn normal_quantile_inverts_cdf() {
+    fn prop(mean: f64, variance: PositiveF64, p: f64) -> bool {
+        let p = p.abs().fract();
+        if p == 0.0 {
+            return true;
+        }
+
+        let normal = density::normal(mean, variance.val);
+        let x = normal.quantile(p);
+
+        (normal.cdf(x) - p).abs() < 1e-9
+    }
+
+    check(prop as fn(f64, PositiveF64, f64) -> bool);
+}
+
+#[test]
+fn normal_quantile_matches_default_inversion() {
+    let normal = density::normal(3.0, 4.0);
+
+    for &p in &[1e-10, 0.01, 0.25, 0.5, 0.9, 0.99, 0.9999] {
+        let lower = normal.quantile(p) - 1.0;
+        let upper = normal.quantile(p) + 1.0;
+        let x = density::invert_cdf(&*normal, p, lower, upper);
+
+        assert!((x - normal.quantile(p)).abs() < 1e-9);
+    }
+
+    assert_eq!(normal.quantile(0.5), 3.0);
+    assert!((normal.quantile(0.975) - (3.0 + 2.0 * 1.959963984540054)).abs() < 1e-12);
+}
+
+#[test]
+fn normal_log_density_is_log_of_density() {
+    fn prop(mean: f64, variance: PositiveF64, x: f64) -> bool {
+        let normal = density::normal(mean, variance.val);
+        let density = normal.density(x);
+
+        // Compare only where the density is a normal float.
+        density < f64::MIN_POSITIVE ||
+        (normal.log_density(x) - density.ln()).abs() < 1e-9 * (1.0 + density.ln().abs())
+    }
+
+    check(prop as fn(f64, PositiveF64, f64) -> bool);
+}
+
+#[test]
+fn normal_log_likelihood() {
+    let normal = density::normal(1.0, 4.0);
+    let samples = vec![-1.0, 1.0, 3.0, 1001.0];
+
+    let expected = -2.0 * (8.0 * f64::consts::PI).ln() - 2.0 * 0.5 - 1000.0f64.powi(2) / 8.0;
+    assert!((density::log_likelihood(&*normal, &samples) - expected).abs() < 1e-9);
+    assert_eq!(density::log_likelihood(&*normal, &[]), 0.0);
+}
@@ -8,7 +8,7 @@ use kernel_density::density::{Ecdf, ecdf, percentile, p, rank};
 use common::{check, SamplesF64, Percentile, Proportion};
 
 use quickcheck::TestResult;
-use std::{cmp, usize};
+use std::cmp;
 
 #[test]
 #[should_panic(expected="assertion failed: length > 0")]
@@ -29,7 +29,7 @@ fn single_use_ecdf_between_zero_and_one() {
     fn prop(xs: SamplesF64, val: f64) -> bool {
         let actual = ecdf(&xs.vec, val);
 
-        0.0 <= actual && actual <= 1.0
+        (0.0..=1.0).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64) -> bool);
@@ -41,7 +41,7 @@ fn multiple_use_ecdf_between_zero_and_one() {
         let ecdf = Ecdf::new(&xs.vec);
         let actual = ecdf.value(val);
 
-        0.0 <= actual && actual <= 1.0
+        (0.0..=1.0).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64) -> bool);
@@ -52,7 +52,7 @@ fn single_use_ecdf_is_an_increasing_function() {
     fn prop(xs: SamplesF64, val: f64) -> bool {
         let actual = ecdf(&xs.vec, val);
 
-        ecdf(&xs.vec, val - 1.0) <= actual && actual <= ecdf(&xs.vec, val + 1.0)
+        (ecdf(&xs.vec, val - 1.0)..=ecdf(&xs.vec, val + 1.0)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64) -> bool);
@@ -64,7 +64,7 @@ fn multiple_use_ecdf_is_an_increasing_function() {
         let ecdf = Ecdf::new(&xs.vec);
         let actual = ecdf.value(val);
 
-        ecdf.value(val - 1.0) <= actual && actual <= ecdf.value(val + 1.0)
+        (ecdf.value(val - 1.0)..=ecdf.value(val + 1.0)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64) -> bool);
@@ -149,7 +149,7 @@ fn single_use_ecdf_non_sample_val_is_num_samples_leq_val_div_length() {
     fn prop(xs: SamplesF64, val: f64) -> TestResult {
         let length = xs.vec.len();
 
-        if xs.vec.iter().any(|&x| x == val) {
+        if xs.vec.contains(&val) {
             // Discard Vec containing val.
             return TestResult::discard();
         }
@@ -173,7 +173,7 @@ fn multiple_use_ecdf_non_sample_val_is_num_samples_leq_val_div_length() {
     fn prop(xs: SamplesF64, val: f64) -> TestResult {
         let length = xs.vec.len();
 
-        if xs.vec.iter().any(|&x| x == val) {
+        if xs.vec.contains(&val) {
             // Discard Vec containing val.
             return TestResult::discard();
         }
@@ -242,7 +242,7 @@ fn single_use_percentile_between_samples_min_and_max() {
     fn prop(xs: SamplesF64, p: Percentile) -> bool {
         let actual = percentile(&xs.vec, p.val);
 
-        xs.min() <= actual && actual <= xs.max()
+        (xs.min()..=xs.max()).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, Percentile) -> bool);
@@ -256,7 +256,7 @@ fn single_use_percentile_is_an_increasing_function() {
 
         let actual = percentile(&xs.vec, p.val);
 
-        percentile(&xs.vec, smaller) <= actual && actual <= percentile(&xs.vec, larger)
+        (percentile(&xs.vec, smaller)..=percentile(&xs.vec, larger)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, Percentile) -> bool);
@@ -277,7 +277,7 @@ fn multiple_use_percentile_between_samples_min_and_max() {
         let ecdf = Ecdf::new(&xs.vec);
         let actual = ecdf.percentile(p.val);
 
-        xs.min() <= actual && actual <= xs.max()
+        (xs.min()..=xs.max()).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, Percentile) -> bool);
@@ -292,7 +292,7 @@ fn multiple_use_percentile_is_an_increasing_function() {
         let ecdf = Ecdf::new(&xs.vec);
         let actual = ecdf.percentile(p.val);
 
-        ecdf.percentile(smaller) <= actual && actual <= ecdf.percentile(larger)
+        (ecdf.percentile(smaller)..=ecdf.percentile(larger)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, Percentile) -> bool);
@@ -415,7 +415,7 @@ fn single_use_p_between_samples_min_and_max() {
     fn prop(xs: SamplesF64, proportion: Proportion) -> bool {
         let actual = p(&xs.vec, proportion.val);
 
-        xs.min() <= actual && actual <= xs.max()
+        (xs.min()..=xs.max()).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, Proportion) -> bool);
@@ -431,7 +431,7 @@ fn single_use_p_is_an_increasing_function() {
 
         let actual = p(&xs.vec, proportion);
 
-        p(&xs.vec, smaller) <= actual && actual <= p(&xs.vec, larger)
+        (p(&xs.vec, smaller)..=p(&xs.vec, larger)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, Proportion) -> bool);
@@ -452,7 +452,7 @@ fn multiple_use_p_between_samples_min_and_max() {
         let ecdf = Ecdf::new(&xs.vec);
         let actual = ecdf.p(proportion.val);
 
-        xs.min() <= actual && actual <= xs.max()
+        (xs.min()..=xs.max()).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, Proportion) -> bool);
@@ -469,7 +469,7 @@ fn multiple_use_p_is_an_increasing_function() {
         let ecdf = Ecdf::new(&xs.vec);
         let actual = ecdf.p(proportion);
 
-        ecdf.p(smaller) <= actual && actual <= ecdf.p(larger)
+        (ecdf.p(smaller)..=ecdf.p(larger)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, Proportion) -> bool);
@@ -594,7 +594,7 @@ fn single_use_rank_between_samples_min_and_max() {
         let x = r % length + 1;
         let actual = rank(&xs.vec, x);
 
-        xs.min() <= actual && actual <= xs.max()
+        (xs.min()..=xs.max()).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, usize) -> bool);
@@ -611,7 +611,7 @@ fn single_use_rank_is_an_increasing_function() {
 
         let actual = rank(&xs.vec, x);
 
-        rank(&xs.vec, smaller) <= actual && actual <= rank(&xs.vec, larger)
+        (rank(&xs.vec, smaller)..=rank(&xs.vec, larger)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, usize) -> bool);
@@ -643,7 +643,7 @@ fn multiple_use_rank_between_samples_min_and_max() {
 
         let x = r % length + 1;
         let actual = ecdf.rank(x);
-        xs.min() <= actual && actual <= xs.max()
+        (xs.min()..=xs.max()).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, usize) -> bool);
@@ -661,7 +661,7 @@ fn multiple_use_rank_is_an_increasing_function() {
         let ecdf = Ecdf::new(&xs.vec);
         let actual = ecdf.rank(x);
 
-        ecdf.rank(smaller) <= actual && actual <= ecdf.rank(larger)
+        (ecdf.rank(smaller)..=ecdf.rank(larger)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, usize) -> bool);
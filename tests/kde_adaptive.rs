@@ -0,0 +1,153 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::Density;
+use kernel_density::kde;
+use kernel_density::kde::{AdaptiveKernelDensityEstimation, EpanechnikovKernel, NormalKernel};
+use common::{check, read_samples, SamplesF64, PositiveF64};
+use std::f64;
+
+#[test]
+#[should_panic(expected="assertion failed: length > 0")]
+fn new_adaptive_kde_panics_on_empty_samples_set() {
+    let xs: Vec<f64> = vec![];
+    kde::normal_adaptive(&xs, 1.0, 0.5);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: bandwidth > 0.0")]
+fn adaptive_kde_panics_on_zero_bandwidth() {
+    let xs: Vec<f64> = vec![0.0];
+    kde::normal_adaptive(&xs, 0.0, 0.5);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: (0.0..=1.0).contains(&sensitivity)")]
+fn adaptive_kde_panics_on_negative_sensitivity() {
+    let xs: Vec<f64> = vec![0.0];
+    kde::epanechnikov_adaptive(&xs, 1.0, -0.5);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: (0.0..=1.0).contains(&sensitivity)")]
+fn adaptive_kde_panics_on_sensitivity_greater_than_one() {
+    let xs: Vec<f64> = vec![0.0];
+    kde::uniform_adaptive(&xs, 1.0, 1.5);
+}
+
+#[test]
+fn adaptive_kde_cdf_between_zero_and_one() {
+    fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+        let kde = kde::uniform_adaptive(&xs.vec, bandwidth.val, 0.5);
+        let actual = kde.cdf(x);
+
+        (0.0..=1.0).contains(&actual)
+    }
+
+    check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
+}
+
+#[test]
+fn adaptive_kde_cdf_is_an_increasing_function() {
+    fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+        let kde = kde::epanechnikov_adaptive(&xs.vec, bandwidth.val, 0.5);
+        let actual = kde.cdf(x);
+
+        (kde.cdf(x - 0.01)..=kde.cdf(x + 0.01)).contains(&actual)
+    }
+
+    check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
+}
+
+#[test]
+fn adaptive_kde_cdf_f64max_is_one() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        let kde = kde::uniform_adaptive(&xs.vec, bandwidth.val, 0.5);
+        let actual = kde.cdf(f64::MAX);
+
+        actual == 1.0
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn adaptive_kde_with_zero_sensitivity_is_fixed_kde() {
+    let xs = read_samples("normal_0_1.1.tsv");
+
+    let adaptive = kde::normal_adaptive(&xs, 0.25, 0.0);
+    let fixed = kde::normal(&xs, 0.25);
+
+    for &x in &[-3.0, -1.0, -0.2, 0.0, 0.5, 1.3, 2.5] {
+        assert!((adaptive.density(x) - fixed.density(x)).abs() < 1e-12);
+        assert!((adaptive.cdf(x) - fixed.cdf(x)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn adaptive_kde_bandwidths_have_pilot_geometric_mean() {
+    let xs = read_samples("http_ttime.tsv");
+    let kde = AdaptiveKernelDensityEstimation::new(&xs, 0.05, 0.5, NormalKernel);
+
+    let log_mean = kde.bandwidths().iter().map(|h| h.ln()).sum::<f64>() / xs.len() as f64;
+    assert!((log_mean.exp() - 0.05).abs() < 1e-12);
+}
+
+#[test]
+fn adaptive_kde_bandwidths_are_finite_for_underflowing_pilot_densities() {
+    // The pilot densities underflow to zero for a huge pilot bandwidth.
+    let xs = vec![0.0, 1.0];
+    let kde = AdaptiveKernelDensityEstimation::new(&xs, f64::MAX, 0.5, NormalKernel);
+
+    assert!(kde.bandwidths().iter().all(|&h| h == f64::MAX));
+}
+
+#[test]
+fn adaptive_kde_widens_tail_bandwidths() {
+    let xs = read_samples("http_ttime.tsv");
+    let kde = AdaptiveKernelDensityEstimation::new(&xs, 0.05, 0.5, NormalKernel);
+
+    let mut pairs: Vec<(f64, f64)> = xs.iter().cloned().zip(kde.bandwidths().iter().cloned()).collect();
+    pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    // The largest latencies are isolated in the long right tail and so get
+    // wider kernels than the median latency.
+    let median = pairs[pairs.len() / 2].1;
+    let maximum = pairs[pairs.len() - 1].1;
+    assert!(maximum > median);
+    assert!(maximum > 0.05);
+}
+
+#[test]
+fn adaptive_kde_density_integrates_to_cdf() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let kde = AdaptiveKernelDensityEstimation::new(&xs, 0.25, 0.5, EpanechnikovKernel);
+
+    // Simpson's rule over a range containing all of the kernel supports.
+    let low = -10.0;
+    let high = 10.0;
+    let intervals = 20000;
+    let step = (high - low) / intervals as f64;
+
+    let mut sum = kde.density(low) + kde.density(high);
+    for i in 1..intervals {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * kde.density(low + i as f64 * step);
+    }
+
+    let integral = sum * step / 3.0;
+    assert!((integral - 1.0).abs() < 1e-6);
+
+    let mut partial = 0.0;
+    let mut x = low;
+    while x < 0.5 {
+        let mid = x + step / 2.0;
+        partial += step * (kde.density(x) + 4.0 * kde.density(mid) + kde.density(x + step)) / 6.0;
+        x += step;
+    }
+
+    assert!((partial - kde.cdf(x)).abs() < 1e-6);
+}
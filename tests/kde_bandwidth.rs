@@ -0,0 +1,221 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::kde;
+use kernel_density::kde::{EpanechnikovKernel, Kernel, NormalKernel, UniformKernel};
+use kernel_density::kde::bandwidth::{rescale, scott, sheather_jones, silverman};
+use common::{check, read_samples, SamplesF64, PositiveF64};
+use quickcheck::TestResult;
+use std::f64;
+
+#[test]
+#[should_panic(expected="assertion failed: length > 0")]
+fn silverman_panics_on_empty_samples_set() {
+    let xs: Vec<f64> = vec![];
+    silverman(&xs);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: length > 0")]
+fn scott_panics_on_empty_samples_set() {
+    let xs: Vec<f64> = vec![];
+    scott(&xs);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: bandwidth > 0.0")]
+fn rescale_panics_on_zero_bandwidth() {
+    rescale(0.0, &EpanechnikovKernel);
+}
+
+#[test]
+fn silverman_is_positive() {
+    fn prop(xs: SamplesF64) -> bool {
+        silverman(&xs.vec) > 0.0
+    }
+
+    check(prop as fn(SamplesF64) -> bool);
+}
+
+#[test]
+fn scott_is_larger_than_silverman() {
+    fn prop(xs: SamplesF64) -> bool {
+        scott(&xs.vec) > silverman(&xs.vec)
+    }
+
+    check(prop as fn(SamplesF64) -> bool);
+}
+
+#[test]
+fn silverman_of_constant_sample_is_positive() {
+    let xs = vec![0.0, 0.0, 0.0, 0.0];
+    assert_eq!(silverman(&xs), 0.9 * 4f64.powf(-0.2));
+}
+
+#[test]
+fn silverman_of_standard_normal_sample() {
+    let xs = read_samples("normal_0_1.tsv");
+    let expected = 0.9 * (xs.len() as f64).powf(-0.2);
+
+    assert!((silverman(&xs) - expected).abs() < 0.05 * expected);
+}
+
+#[test]
+fn rescale_normal_is_identity() {
+    fn prop(bandwidth: PositiveF64) -> bool {
+        rescale(bandwidth.val, &NormalKernel) == bandwidth.val
+    }
+
+    check(prop as fn(PositiveF64) -> bool);
+}
+
+#[test]
+fn rescale_preserves_canonical_ratio() {
+    let epanechnikov = rescale(1.0, &EpanechnikovKernel);
+    let uniform = rescale(1.0, &UniformKernel);
+
+    let expected = EpanechnikovKernel.canonical_bandwidth() / UniformKernel.canonical_bandwidth();
+    assert!((epanechnikov / uniform - expected).abs() < 1e-12);
+}
+
+#[test]
+fn auto_kde_cdf_spans_zero_to_one() {
+    fn prop(xs: SamplesF64) -> bool {
+        let kdes = [kde::epanechnikov_auto(&xs.vec), kde::uniform_auto(&xs.vec)];
+
+        kdes.iter().all(|kde| kde.cdf(f64::MIN) == 0.0 && kde.cdf(f64::MAX) == 1.0)
+    }
+
+    check(prop as fn(SamplesF64) -> bool);
+}
+
+#[test]
+fn auto_kde_of_latency_sample_is_finite() {
+    let xs = read_samples("http_ttime.tsv");
+    let kde = kde::normal_auto(&xs);
+
+    let midrange = 0.5 * (xs.iter().cloned().fold(f64::MAX, f64::min) +
+                        xs.iter().cloned().fold(f64::MIN, f64::max));
+    let density = kde.density(midrange);
+
+    assert!(density.is_finite() && density >= 0.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: length > 1")]
+fn sheather_jones_panics_on_single_sample() {
+    sheather_jones(&[0.0]);
+}
+
+/// Solve the Sheather-Jones equation using the exact pairwise sample
+/// differences instead of binned ones, by bisection to full precision.
+///
+/// This follows the definitions in Sheather and Jones (1991) directly, with
+/// the normal reference pilot bandwidths a = 1.24 * s * n^(-1/7) and
+/// b = 1.23 * s * n^(-1/9), where s is min(sd, IQR / 1.349). It costs O(n^2)
+/// per functional estimate.
+fn exact_sheather_jones(xs: &[f64]) -> f64 {
+    let n = xs.len() as f64;
+
+    // Sum of phi^(r)(d / h) over all ordered pairs, including the diagonal,
+    // for the fourth and sixth derivatives of the standard Normal density.
+    let functional = |h: f64, sixth: bool| {
+        let mut sum = 0.0;
+        for x_i in xs {
+            for x_j in xs {
+                let u2 = ((x_i - x_j) / h).powi(2);
+                let polynomial = if sixth {
+                    u2.powi(3) - 15.0 * u2 * u2 + 45.0 * u2 - 15.0
+                } else {
+                    u2 * u2 - 6.0 * u2 + 3.0
+                };
+                sum += (-0.5 * u2).exp() * polynomial;
+            }
+        }
+
+        let power = if sixth { 7 } else { 5 };
+        sum / (n * (n - 1.0) * h.powi(power) * (2.0 * f64::consts::PI).sqrt())
+    };
+
+    let mean = xs.iter().sum::<f64>() / n;
+    let sd = (xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+
+    let mut sorted = xs.to_vec();
+    sorted.sort_by(|x_1, x_2| x_1.partial_cmp(x_2).unwrap());
+    let quantile = |p: f64| {
+        let index = p * (n - 1.0);
+        let low = index.floor() as usize;
+        let high = index.ceil() as usize;
+        sorted[low] + (index - low as f64) * (sorted[high] - sorted[low])
+    };
+    let scale = sd.min((quantile(0.75) - quantile(0.25)) / 1.349);
+
+    let a = 1.24 * scale * n.powf(-1.0 / 7.0);
+    let b = 1.23 * scale * n.powf(-1.0 / 9.0);
+    let alpha2 = 1.357 * (functional(a, false) / -functional(b, true)).powf(1.0 / 7.0);
+    let c1 = 1.0 / (2.0 * f64::consts::PI.sqrt() * n);
+
+    let equation = |h: f64| (c1 / functional(alpha2 * h.powf(5.0 / 7.0), false)).powf(0.2) - h;
+
+    let mut low = 1e-3 * scale;
+    let mut high = 10.0 * scale;
+    assert!(equation(low) > 0.0 && equation(high) < 0.0);
+
+    for _ in 0..100 {
+        let mid = 0.5 * (low + high);
+        if equation(mid) > 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    0.5 * (low + high)
+}
+
+#[test]
+fn sheather_jones_of_standard_normal_subsample() {
+    // The reference solves the same equation without binning the pairwise
+    // differences, so the binning accounts for the whole difference, a
+    // relative 0.11% for this sample.
+    let xs = read_samples("normal_0_1.1.tsv");
+    let expected = exact_sheather_jones(&xs);
+
+    assert!((expected - 0.32804552852679447).abs() < 1e-12);
+    assert!((sheather_jones(&xs) - expected).abs() < 0.002 * expected);
+}
+
+#[test]
+fn sheather_jones_of_normal_samples_is_near_amise_optimal() {
+    // The AMISE optimal bandwidth for normal data with standard deviation s
+    // is (4 / 3n)^(1/5) * s.
+    for &(name, sd) in &[("normal_0_1.tsv", 1.0), ("normal_0_2.tsv", 2f64.sqrt())] {
+        let xs = read_samples(name);
+        let expected = (4.0 / (3.0 * xs.len() as f64)).powf(0.2) * sd;
+
+        assert!((sheather_jones(&xs) - expected).abs() < 0.1 * expected);
+    }
+}
+
+#[test]
+fn sheather_jones_undersmooths_silverman_for_latency_sample() {
+    let xs = read_samples("http_ttime.tsv");
+    assert!(sheather_jones(&xs) < silverman(&xs));
+}
+
+#[test]
+fn sheather_jones_is_positive() {
+    fn prop(xs: SamplesF64) -> TestResult {
+        // Samples without spread are degenerate for the pilot estimates.
+        if xs.vec.len() < 2 || xs.vec.iter().all(|&x| x == xs.vec[0]) {
+            return TestResult::discard();
+        }
+
+        TestResult::from_bool(sheather_jones(&xs.vec) > 0.0)
+    }
+
+    check(prop as fn(SamplesF64) -> TestResult);
+}
@@ -0,0 +1,73 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::{self, Density};
+use kernel_density::kde::{Kernel, KernelDensityEstimation, EpanechnikovKernel, NormalKernel,
+                          TriangularKernel, UniformKernel};
+use common::{check, read_samples, SamplesF64, PositiveF64};
+
+/// Check batch evaluation is identical to evaluation one value at a time.
+fn batch_matches_single_values<K: Kernel>(kde: &KernelDensityEstimation<K>, xs: &[f64]) -> bool {
+    let densities = kde.densities(xs);
+    let cdfs = kde.cdfs(xs);
+
+    densities.len() == xs.len() && cdfs.len() == xs.len() &&
+    xs.iter().enumerate().all(|(i, &x)| {
+        densities[i].to_bits() == kde.density(x).to_bits() &&
+        cdfs[i].to_bits() == kde.cdf(x).to_bits()
+    })
+}
+
+#[test]
+fn kde_batch_matches_single_values_for_sorted_values() {
+    fn prop(samples: SamplesF64, xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        let mut xs = xs.vec;
+        xs.sort_by(|x_1, x_2| x_1.partial_cmp(x_2).unwrap());
+
+        batch_matches_single_values(&KernelDensityEstimation::new(&samples.vec,
+                                                                  bandwidth.val,
+                                                                  UniformKernel),
+                                    &xs) &&
+        batch_matches_single_values(&KernelDensityEstimation::new(&samples.vec,
+                                                                  bandwidth.val,
+                                                                  EpanechnikovKernel),
+                                    &xs)
+    }
+
+    check(prop as fn(SamplesF64, SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn kde_batch_matches_single_values_for_unsorted_values() {
+    fn prop(samples: SamplesF64, xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        let xs = xs.vec;
+
+        batch_matches_single_values(&KernelDensityEstimation::new(&samples.vec,
+                                                                  bandwidth.val,
+                                                                  TriangularKernel),
+                                    &xs) &&
+        batch_matches_single_values(&KernelDensityEstimation::new(&samples.vec,
+                                                                  bandwidth.val,
+                                                                  NormalKernel),
+                                    &xs)
+    }
+
+    check(prop as fn(SamplesF64, SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn kde_batch_sweeps_linspace_over_http_ttime() {
+    let samples = read_samples("http_ttime.tsv");
+    let weights: Vec<f64> = (0..samples.len()).map(|i| (1 + i % 2) as f64).collect();
+    let kde = KernelDensityEstimation::weighted(&samples, &weights, 15.0, EpanechnikovKernel);
+
+    let xs = density::linspace(0.0, 6000.0, 60001);
+    assert!(batch_matches_single_values(&kde, &xs));
+
+    // Plotting through a boxed density uses the same batch methods.
+    let boxed: Box<dyn Density> = Box::new(kde);
+    assert_eq!(boxed.cdfs(&xs)[60000], 1.0);
+}
@@ -0,0 +1,166 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::Density;
+use kernel_density::kde;
+use kernel_density::kde::{Boundary, BoundedKernelDensityEstimation, Kernel, EpanechnikovKernel,
+                          NormalKernel, UniformKernel};
+use common::{check, read_samples, SamplesF64, PositiveF64};
+use std::f64;
+
+const BOUNDARIES: [Boundary; 3] =
+    [Boundary::Reflection, Boundary::Renormalisation, Boundary::Transformation];
+
+/// Integrate a density over [low, high] using composite Simpson's rule.
+fn integrate(density: &dyn Density, low: f64, high: f64) -> f64 {
+    let intervals = 20000;
+    let step = (high - low) / intervals as f64;
+
+    let mut sum = density.density(low) + density.density(high);
+    for i in 1..intervals {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * density.density(low + i as f64 * step);
+    }
+
+    sum * step / 3.0
+}
+
+/// Check a bounded estimation is zero outside its bounds and that its density
+/// integrates to its cumulative density inside them.
+fn assert_bounded<K: Kernel>(kde: &BoundedKernelDensityEstimation<K>, low: f64, high: f64) {
+    assert_eq!(kde.cdf(kde.lower()), 0.0);
+    assert_eq!(kde.cdf(kde.upper()), 1.0);
+    assert_eq!(kde.density(kde.lower() - 0.1), 0.0);
+    assert_eq!(kde.density(kde.upper() + 0.1), 0.0);
+
+    let integral = integrate(kde, low, high);
+    assert!((integral - (kde.cdf(high) - kde.cdf(low))).abs() < 1e-6,
+            "{:?} integral {} cdf {}",
+            kde.boundary(),
+            integral,
+            kde.cdf(high) - kde.cdf(low));
+}
+
+#[test]
+#[should_panic(expected="assertion failed: lower < upper")]
+fn bounded_kde_panics_on_empty_bounds() {
+    kde::normal_bounded(&[1.0], 1.0, 1.0, 1.0, Boundary::Reflection);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: samples.iter().all(|&x| (lower..=upper).contains(&x))")]
+fn bounded_kde_panics_on_sample_outside_bounds() {
+    kde::epanechnikov_bounded(&[-1.0, 1.0], 1.0, 0.0, f64::INFINITY, Boundary::Renormalisation);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: samples.iter().all(|&x| lower < x && x < upper)")]
+fn transformation_kde_panics_on_sample_at_bound() {
+    kde::uniform_bounded(&[0.0, 1.0], 1.0, 0.0, f64::INFINITY, Boundary::Transformation);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: length > 0")]
+fn bounded_kde_panics_on_empty_samples_set() {
+    kde::normal_bounded(&[], 1.0, 0.0, 1.0, Boundary::Reflection);
+}
+
+#[test]
+fn bounded_kde_cdf_between_zero_and_one() {
+    fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+        let lower = xs.min();
+        let upper = xs.max() + 1.0;
+
+        [Boundary::Reflection, Boundary::Renormalisation].iter().all(|&boundary| {
+            let kde = kde::uniform_bounded(&xs.vec, bandwidth.val, lower, upper, boundary);
+            let actual = kde.cdf(x);
+
+            (0.0..=1.0).contains(&actual) && kde.cdf(lower) == 0.0
+        })
+    }
+
+    check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
+}
+
+#[test]
+fn lower_bounded_kde_integrates_to_one() {
+    // Half normal samples.
+    let xs: Vec<f64> = read_samples("normal_0_1.1.tsv").iter().map(|x| x.abs()).collect();
+
+    // Normal kernel mass spills below zero for the samples near zero.
+    let unbounded = kde::normal(&xs, 0.25);
+    assert!(unbounded.cdf(0.0) > 0.01);
+
+    for &boundary in &BOUNDARIES {
+        let kde = BoundedKernelDensityEstimation::new(&xs, 0.25, NormalKernel,
+                                                      0.0, f64::INFINITY, boundary);
+        assert_bounded(&kde, 0.0, 1.0);
+        assert!((kde.cdf(50.0) - 1.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn upper_bounded_kde_integrates_to_one() {
+    let xs: Vec<f64> = read_samples("normal_0_1.1.tsv").iter().map(|x| -x.abs()).collect();
+
+    for &boundary in &BOUNDARIES {
+        let kde = BoundedKernelDensityEstimation::new(&xs, 0.25, EpanechnikovKernel,
+                                                      f64::NEG_INFINITY, 0.0, boundary);
+        assert_bounded(&kde, -1.0, 0.0);
+        assert!(kde.cdf(-50.0) < 1e-6);
+    }
+}
+
+#[test]
+fn doubly_bounded_kde_integrates_to_one() {
+    // Map the standard normal samples into (0, 1) with the normal cdf.
+    let xs: Vec<f64> = read_samples("normal_0_1.1.tsv")
+        .iter()
+        .map(|&x| kde::NormalKernel.cdf(x))
+        .collect();
+
+    for &boundary in &BOUNDARIES {
+        let kde = BoundedKernelDensityEstimation::new(&xs, 0.1, NormalKernel, 0.0, 1.0, boundary);
+        assert_bounded(&kde, 0.0, 1.0);
+    }
+}
+
+#[test]
+fn reflection_folds_wide_kernels_between_bounds() {
+    // Bandwidth much wider than the bounds needs many reflections.
+    let xs = vec![0.1, 0.2, 0.9];
+    let kde = BoundedKernelDensityEstimation::new(&xs, 5.0, NormalKernel,
+                                                  0.0, 1.0, Boundary::Reflection);
+
+    assert_bounded(&kde, 0.0, 1.0);
+
+    // Almost uniform after folding.
+    assert!((kde.density(0.5) - 1.0).abs() < 0.01);
+}
+
+#[test]
+fn reflection_doubles_density_at_bound() {
+    let xs = vec![0.0];
+    let kde = BoundedKernelDensityEstimation::new(&xs, 1.0, UniformKernel,
+                                                  0.0, f64::INFINITY, Boundary::Reflection);
+    assert_eq!(kde.density(0.5), 1.0);
+    assert_eq!(kde.cdf(0.5), 0.5);
+}
+
+#[test]
+fn unbounded_kde_is_unchanged() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let unbounded = kde::normal(&xs, 0.25);
+
+    for &boundary in &BOUNDARIES {
+        let kde = kde::normal_bounded(&xs, 0.25, f64::NEG_INFINITY, f64::INFINITY, boundary);
+
+        for &x in &[-2.0, -0.5, 0.0, 0.3, 1.7] {
+            assert!((kde.density(x) - unbounded.density(x)).abs() < 1e-12);
+            assert!((kde.cdf(x) - unbounded.cdf(x)).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,247 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::{linspace, Density};
+use kernel_density::kde::circular;
+use kernel_density::kde::circular::{CircularKernel, CircularKernelDensityEstimation};
+use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+use common::{check, read_samples, seeded, SamplesF64, PositiveF64};
+use std::f64::consts::PI;
+
+const KERNELS: [CircularKernel; 2] = [CircularKernel::VonMises, CircularKernel::WrappedNormal];
+
+/// Integrate a density over a full period with the trapezoid rule, which is
+/// very accurate for smooth periodic functions.
+fn integrate(kde: &CircularKernelDensityEstimation, from: f64, to: f64, points: usize) -> f64 {
+    let xs = linspace(from, to, points + 1);
+    let densities = kde.densities(&xs);
+    let step = (to - from) / points as f64;
+
+    step * (densities.iter().sum::<f64>() - 0.5 * (densities[0] + densities[points]))
+}
+
+#[test]
+#[should_panic(expected="assertion failed: period > 0.0 && period.is_finite()")]
+fn new_circular_kde_panics_on_zero_period() {
+    CircularKernelDensityEstimation::new(&[0.0], 0.0, 1.0, CircularKernel::VonMises);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: concentration > 0.0 && concentration.is_finite()")]
+fn new_circular_kde_panics_on_zero_concentration() {
+    CircularKernelDensityEstimation::new(&[0.0], 24.0, 0.0, CircularKernel::VonMises);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: length > 0")]
+fn new_circular_kde_panics_on_empty_samples() {
+    CircularKernelDensityEstimation::new(&[], 24.0, 1.0, CircularKernel::WrappedNormal);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: resultant_length < 1.0")]
+fn rule_of_thumb_panics_on_equal_samples() {
+    circular::rule_of_thumb(&[3.0, 3.0, 27.0], 24.0);
+}
+
+#[test]
+fn density_is_periodic() {
+    fn prop(xs: SamplesF64, concentration: PositiveF64, x: f64) -> bool {
+        let period = 24.0;
+        let concentration = concentration.val.min(1e4);
+        let x = x % 1e6;
+        let xs: Vec<f64> = xs.vec.iter().take(100).cloned().collect();
+        let shifted: Vec<f64> = xs.iter().map(|x| x - 3.0 * period).collect();
+
+        KERNELS.iter().all(|&kernel| {
+            let kde = CircularKernelDensityEstimation::new(&xs, period, concentration, kernel);
+            let other = CircularKernelDensityEstimation::new(&shifted, period, concentration,
+                                                             kernel);
+
+            let density = kde.density(x);
+            let tolerance = 1e-8 * (1.0 + density);
+
+            (kde.density(x + period) - density).abs() < tolerance &&
+            (kde.density(x - 2.0 * period) - density).abs() < tolerance &&
+            (other.density(x) - density).abs() < tolerance
+        })
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64, f64) -> bool);
+}
+
+#[test]
+fn density_integrates_to_one() {
+    let samples = [0.5, 1.0, 2.0, 12.0, 13.5, 22.0, 23.9];
+
+    for &kernel in &KERNELS {
+        for &concentration in &[0.01, 1.0, 10.0, 100.0, 1e4] {
+            for &period in &[24.0, 360.0, 2.0 * PI] {
+                let xs: Vec<f64> = samples.iter().map(|x| x * period / 24.0).collect();
+                let kde = CircularKernelDensityEstimation::new(&xs, period, concentration,
+                                                               kernel);
+
+                let integral = integrate(&kde, 0.0, period, 4000);
+                assert!((integral - 1.0).abs() < 1e-9);
+
+                let shifted = integrate(&kde, -period / 3.0, 2.0 * period / 3.0, 4000);
+                assert!((shifted - 1.0).abs() < 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn cdf_integrates_density_from_origin() {
+    let samples = vec![350.0, 10.0, 20.0, 100.0, 200.0];
+    let weights = vec![1.0, 2.0, 1.0, 0.5, 3.0];
+
+    for &kernel in &KERNELS {
+        let mut kde = CircularKernelDensityEstimation::weighted(&samples, &weights, 360.0, 5.0,
+                                                                kernel);
+
+        for &origin in &[0.0, 90.0, -45.0] {
+            kde.origin = origin;
+            assert_eq!(kde.cdf(origin), 0.0);
+            assert!(kde.cdf(origin - 1e-9) > 1.0 - 1e-9);
+
+            // Cumulative trapezoid rule integral of the density from the
+            // origin, at every hundredth grid point.
+            let xs = linspace(origin, origin + 360.0, 36001);
+            let densities = kde.densities(&xs);
+
+            let mut integral = 0.0;
+            let mut previous = 0.0;
+            for i in 1..xs.len() {
+                integral += 0.005 * (densities[i - 1] + densities[i]);
+
+                if i % 100 == 0 && i < 36000 {
+                    let cdf = kde.cdf(xs[i]);
+                    assert!((cdf - integral).abs() < 1e-8);
+                    assert!((cdf - kde.cdf(xs[i] + 360.0)).abs() < 1e-12);
+                    assert!(cdf >= previous);
+                    previous = cdf;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn quantile_inverts_cdf() {
+    let samples = vec![22.0, 23.0, 0.5, 1.0, 8.0];
+
+    for &kernel in &KERNELS {
+        let mut kde = CircularKernelDensityEstimation::new(&samples, 24.0, 20.0, kernel);
+        kde.origin = 12.0;
+
+        for &p in &[0.01, 0.25, 0.5, 0.75, 0.99] {
+            let x = kde.quantile(p);
+            assert!((12.0..36.0).contains(&x));
+            assert!((kde.cdf(x) - p).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn large_concentration_matches_normal_kde() {
+    // Away from the wrap point, the circular estimation with concentration
+    // kappa is close to the Normal kernel estimation with bandwidth period /
+    // (2 * pi * sqrt(kappa)).
+    let xs: Vec<f64> = read_samples("normal_0_1.1.tsv").iter().map(|x| x + 12.0).collect();
+    let period = 24.0;
+    let concentration: f64 = 1e4;
+    let bandwidth = period / (2.0 * PI * concentration.sqrt());
+    let kde = KernelDensityEstimation::new(&xs, bandwidth, NormalKernel);
+
+    for &kernel in &KERNELS {
+        let circular = CircularKernelDensityEstimation::new(&xs, period, concentration, kernel);
+
+        for x in linspace(9.0, 15.0, 13) {
+            let density = kde.density(x);
+            assert!((circular.density(x) - density).abs() < 1e-3 * (1.0 + density));
+            assert!((circular.cdf(x) - kde.cdf(x)).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn circular_mean_and_mode_wrap() {
+    let samples = vec![23.0, 23.5, 23.75, 0.25, 0.5, 1.0];
+
+    for &kernel in &KERNELS {
+        let kde = CircularKernelDensityEstimation::new(&samples, 24.0, 30.0, kernel);
+
+        let mean = kde.mean();
+        assert!(mean.min(24.0 - mean) < 1e-9);
+
+        let mode = kde.mode();
+        assert!(mode.min(24.0 - mode) < 1e-6);
+
+        let (densest, _) = linspace(0.0, 24.0, 2401)
+            .into_iter()
+            .map(|x| (x, kde.density(x)))
+            .fold((0.0, 0.0), |best, point| if point.1 > best.1 { point } else { best });
+        assert!(kde.density(mode) > kde.density(densest) - 1e-12);
+
+        let resultant_length = kde.mean_resultant_length();
+        assert!(0.0 < resultant_length && resultant_length < 1.0);
+    }
+
+    let opposite = CircularKernelDensityEstimation::new(&[6.0, 18.0], 24.0, 30.0,
+                                                        CircularKernel::VonMises);
+    assert!(opposite.mean_resultant_length() < 1e-12);
+}
+
+#[test]
+fn draws_follow_cdf() {
+    let samples = vec![350.0, 10.0, 20.0, 100.0, 200.0];
+    let draws = 20000;
+
+    for &kernel in &KERNELS {
+        for &concentration in &[0.5, 8.0, 200.0] {
+            let kde = CircularKernelDensityEstimation::new(&samples, 360.0, concentration,
+                                                           kernel);
+
+            let mut rng = seeded();
+            let xs: Vec<f64> = (0..draws).map(|_| kde.sample(&mut rng)).collect();
+            assert!(xs.iter().all(|x| (0.0..360.0).contains(x)));
+
+            for x in linspace(15.0, 345.0, 12) {
+                let proportion = xs.iter().filter(|&&draw| draw <= x).count() as f64 /
+                                 draws as f64;
+                assert!((proportion - kde.cdf(x)).abs() < 0.015);
+            }
+        }
+    }
+}
+
+#[test]
+fn concentration_selection_tracks_spread() {
+    // Samples scaled to a quarter of the period are spread more widely and
+    // need lower concentrations.
+    let samples: Vec<f64> = read_samples("normal_0_1.1.tsv").into_iter().take(100).collect();
+    let narrow: Vec<f64> = samples.iter().map(|x| 0.5 * x).collect();
+    let wide: Vec<f64> = samples.iter().map(|x| 2.0 * x).collect();
+    let period = 24.0;
+
+    let narrow_rule = circular::rule_of_thumb(&narrow, period);
+    let wide_rule = circular::rule_of_thumb(&wide, period);
+    assert!(narrow_rule > wide_rule && wide_rule > 0.0);
+
+    // For nearly Normal samples, the rule of thumb is close to Silverman's
+    // rule on the line.
+    let bandwidth = period / (2.0 * PI * narrow_rule.sqrt());
+    let silverman = kernel_density::kde::bandwidth::silverman(&narrow);
+    assert!((bandwidth / silverman - 1.0).abs() < 0.25);
+
+    for &kernel in &KERNELS {
+        let narrow_cv = circular::likelihood(&narrow, period, kernel, 0.1, 1e5);
+        let wide_cv = circular::likelihood(&wide, period, kernel, 0.1, 1e5);
+        assert!(narrow_cv > wide_cv);
+        assert!(0.1 <= wide_cv && narrow_cv <= 1e5);
+    }
+}
@@ -0,0 +1,149 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::{Density, MultivariateDensity};
+use kernel_density::kde;
+use kernel_density::kde::{BandwidthMatrix, ConditionalKernelDensityEstimation,
+                          EpanechnikovKernel, KernelDensityEstimation,
+                          MultivariateKernelDensityEstimation, NormalKernel, UniformKernel};
+use kernel_density::kde::bandwidth::conditional_scott;
+use common::{check, SamplesF64, PositiveF64};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// Read the (ctime, ttime) columns of dat/http.tsv.
+fn read_http() -> (Vec<f64>, Vec<f64>) {
+    let file = BufReader::new(File::open("dat/http.tsv").unwrap());
+
+    file.lines()
+        .skip(1)
+        .map(|line| {
+            let values: Vec<f64> = line.unwrap()
+                .split('\t')
+                .map(|value| value.parse::<f64>().expect("Not a floating point number."))
+                .collect();
+            (values[0], values[2])
+        })
+        .unzip()
+}
+
+#[test]
+#[should_panic(expected="assertion failed: responses.len() == length")]
+fn conditional_kde_panics_on_mismatched_responses() {
+    ConditionalKernelDensityEstimation::new(&[0.0, 1.0], &[0.0], 1.0, 1.0, NormalKernel);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: condition_bandwidth > 0.0")]
+fn conditional_kde_panics_on_zero_condition_bandwidth() {
+    ConditionalKernelDensityEstimation::new(&[0.0], &[0.0], 0.0, 1.0, NormalKernel);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: total_weight > 0.0")]
+fn conditional_kde_panics_on_distant_condition() {
+    let kde = ConditionalKernelDensityEstimation::new(&[0.0], &[0.0], 1.0, 1.0, UniformKernel);
+    kde.given(2.0);
+}
+
+#[test]
+fn conditional_kde_is_joint_over_marginal() {
+    fn prop(xs: SamplesF64, x: f64, y: f64, bandwidth: PositiveF64) -> bool {
+        let conditions = xs.vec.clone();
+        let responses: Vec<f64> = xs.vec.iter().rev().map(|x| 2.0 * x).collect();
+        let (h_x, h_y) = (bandwidth.val, 2.0 * bandwidth.val);
+
+        let kde = ConditionalKernelDensityEstimation::new(&conditions, &responses,
+                                                          h_x, h_y, NormalKernel);
+        let marginal = kde::normal(&conditions, h_x).density(x);
+        if marginal < 1e-100 {
+            return true;
+        }
+
+        let points: Vec<Vec<f64>> = conditions.iter()
+            .zip(&responses)
+            .map(|(&x, &y)| vec![x, y])
+            .collect();
+        let joint = MultivariateKernelDensityEstimation::new(&points,
+                                                             BandwidthMatrix::Diagonal(vec![h_x, h_y]),
+                                                             NormalKernel);
+
+        let expected = joint.density(&[x, y]) / marginal;
+        let actual = kde.given(x).density(y);
+
+        (actual - expected).abs() <= 1e-9 * expected.max(1e-300)
+    }
+
+    check(prop as fn(SamplesF64, f64, f64, PositiveF64) -> bool);
+}
+
+#[test]
+fn constant_weights_give_unconditional_kde() {
+    fn prop(xs: SamplesF64, x: f64, y: f64) -> bool {
+        let responses: Vec<f64> = xs.vec.iter().map(|x| x.sin()).collect();
+        let conditions = vec![0.0; responses.len()];
+
+        let kde = ConditionalKernelDensityEstimation::new(&conditions, &responses,
+                                                          1.0, 0.5, EpanechnikovKernel);
+        let unconditional = KernelDensityEstimation::new(&responses, 0.5, EpanechnikovKernel);
+
+        let x = x.sin() / 2.0;
+        let expected = unconditional.density(y);
+        (kde.given(x).density(y) - expected).abs() <= 1e-12 * expected.max(1.0)
+    }
+
+    check(prop as fn(SamplesF64, f64, f64) -> bool);
+}
+
+#[test]
+fn compact_kernel_selects_nearby_conditions() {
+    let conditions = vec![0.0, 0.1, 0.2, 10.0, 10.1];
+    let responses = vec![1.0, 2.0, 3.0, 100.0, 200.0];
+    let kde = ConditionalKernelDensityEstimation::new(&conditions, &responses,
+                                                      1.0, 0.1, UniformKernel);
+
+    let weights = kde.weights(0.1);
+    assert_eq!(weights, vec![0.5, 0.5, 0.5, 0.0, 0.0]);
+
+    let conditional = kde.given(0.1);
+    assert_eq!(conditional.cdf(3.5), 1.0);
+    assert_eq!(conditional.density(100.0), 0.0);
+
+    let conditional = kde.given(10.0);
+    assert_eq!(conditional.cdf(3.5), 0.0);
+    assert_eq!(conditional.cdf(150.0), 0.5);
+}
+
+#[test]
+fn http_total_time_increases_with_connect_time() {
+    let (ctime, ttime) = read_http();
+    let (h_x, h_y) = conditional_scott(&ctime, &ttime);
+
+    let kde = ConditionalKernelDensityEstimation::new(&ctime, &ttime, h_x, h_y, NormalKernel);
+
+    let fast = kde.given(430.0);
+    let slow = kde.given(470.0);
+
+    // Connect time is part of total time, so a longer connect shifts the
+    // conditional distribution of total time up.
+    assert!(slow.quantile(0.5) > fast.quantile(0.5));
+
+    // Each conditional density integrates to one.
+    let step = 1.0;
+    let integral: f64 = (0..20000).map(|i| fast.density(i as f64 * step) * step).sum();
+    assert!((integral - 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn conditional_scott_is_bivariate_scott_diagonal() {
+    let (ctime, ttime) = read_http();
+    let (h_x, h_y) = conditional_scott(&ctime, &ttime);
+
+    let n = ctime.len() as f64;
+    let scale = n.powf(-1.0 / 6.0);
+    assert!((h_x - kde::bandwidth::scott(&ctime) / 1.06 * n.powf(0.2) * scale).abs() < 1e-9);
+    assert!((h_y - kde::bandwidth::scott(&ttime) / 1.06 * n.powf(0.2) * scale).abs() < 1e-9);
+}
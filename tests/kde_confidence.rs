@@ -0,0 +1,108 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::{linspace, normal, Density};
+use kernel_density::kde::{confidence, EpanechnikovKernel, KernelDensityEstimation,
+                          NormalKernel};
+use common::{check, read_samples, seeded, Proportion, SamplesF64, PositiveF64};
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < level && level < 1.0")]
+fn pointwise_panics_on_unit_level() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    confidence::pointwise(&kde, &[0.0], 1.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < level && level < 1.0")]
+fn simultaneous_panics_on_zero_level() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    confidence::simultaneous(&kde, &[0.0], 0.0, 10, &mut seeded());
+}
+
+#[test]
+#[should_panic(expected="assertion failed: replicates > 0")]
+fn simultaneous_panics_on_zero_replicates() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    confidence::simultaneous(&kde, &[0.0], 0.5, 0, &mut seeded());
+}
+
+#[test]
+fn pointwise_band_contains_estimate() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64, level: Proportion) -> bool {
+        let level = level.val.clamp(1e-3, 0.999);
+        let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, EpanechnikovKernel);
+        let band = confidence::pointwise(&kde, &xs.vec, level);
+
+        band.xs == xs.vec && band.estimate == kde.densities(&xs.vec) &&
+        (0..xs.vec.len()).all(|i| {
+            0.0 <= band.lower[i] && band.lower[i] <= band.estimate[i] &&
+            band.estimate[i] <= band.upper[i]
+        })
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64, Proportion) -> bool);
+}
+
+#[test]
+fn pointwise_band_narrows_with_sample_size() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let mut doubled = xs.clone();
+    doubled.extend(&xs);
+
+    let points = linspace(-1.0, 1.0, 21);
+    let band = confidence::pointwise(&KernelDensityEstimation::new(&xs, 0.3, NormalKernel),
+                                     &points,
+                                     0.95);
+    let narrower = confidence::pointwise(&KernelDensityEstimation::new(&doubled, 0.3,
+                                                                       NormalKernel),
+                                         &points,
+                                         0.95);
+
+    for i in 0..points.len() {
+        let ratio = (narrower.upper[i] - narrower.estimate[i]) /
+                    (band.upper[i] - band.estimate[i]);
+        assert!((ratio - 0.5f64.sqrt()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn simultaneous_band_contains_pointwise_band() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let kde = KernelDensityEstimation::new(&xs, 0.3, NormalKernel);
+    let points = linspace(-2.0, 2.0, 81);
+
+    let pointwise = confidence::pointwise(&kde, &points, 0.95);
+    let simultaneous = confidence::simultaneous(&kde, &points, 0.95, 200, &mut seeded());
+
+    assert_eq!(simultaneous.estimate, pointwise.estimate);
+    for i in 0..points.len() {
+        assert!(simultaneous.lower[i] <= pointwise.lower[i]);
+        assert!(simultaneous.upper[i] >= pointwise.upper[i]);
+    }
+}
+
+#[test]
+fn bands_cover_smoothed_normal_density() {
+    // The Normal kernel density estimation of Normal(0, 1) samples estimates
+    // the Normal(0, 1 + h^2) density.
+    let xs = read_samples("normal_0_1.tsv");
+    let bandwidth = 0.3;
+    let kde = KernelDensityEstimation::new(&xs, bandwidth, NormalKernel);
+    let smoothed = normal(0.0, 1.0 + bandwidth * bandwidth);
+    let points = linspace(-2.5, 2.5, 51);
+
+    let simultaneous = confidence::simultaneous(&kde, &points, 0.99, 200, &mut seeded());
+    let pointwise = confidence::pointwise(&kde, &points, 0.99);
+
+    let covered = |band: &confidence::Band, i: usize| {
+        let f = smoothed.density(points[i]);
+        band.lower[i] <= f && f <= band.upper[i]
+    };
+
+    assert!((0..points.len()).all(|i| covered(&simultaneous, i)));
+    assert!((0..points.len()).filter(|&i| covered(&pointwise, i)).count() >= 45);
+}
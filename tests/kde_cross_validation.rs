@@ -0,0 +1,123 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::Density;
+use kernel_density::kde::{EpanechnikovKernel, Kernel, KernelDensityEstimation, NormalKernel,
+                          UniformKernel};
+use kernel_density::kde::bandwidth::silverman;
+use kernel_density::kde::cross_validation::{least_squares, likelihood};
+use common::read_samples;
+
+/// Standard normal sample and the same shifted by 6, the second half of the
+/// sample is a separate mode.
+fn bimodal_samples() -> Vec<f64> {
+    let mut xs = read_samples("normal_0_1.1.tsv");
+    xs.extend(read_samples("normal_0_1.2.tsv").iter().map(|x| x + 6.0));
+    xs
+}
+
+#[test]
+#[should_panic(expected="assertion failed: length > 1")]
+fn least_squares_panics_on_single_sample() {
+    least_squares(&[0.0], &NormalKernel, 0.1, 1.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: length > 1")]
+fn likelihood_panics_on_single_sample() {
+    likelihood(&[0.0], &NormalKernel, 0.1, 1.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < lower && lower < upper")]
+fn least_squares_panics_on_inverted_bounds() {
+    least_squares(&[0.0, 1.0], &NormalKernel, 1.0, 0.1);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < lower && lower < upper")]
+fn likelihood_panics_on_zero_lower_bound() {
+    likelihood(&[0.0, 1.0], &NormalKernel, 0.0, 1.0);
+}
+
+fn assert_within_bounds<K: Kernel>(kernel: K) {
+    let xs = read_samples("normal_0_1.1.tsv");
+
+    for cv in &[least_squares(&xs, &kernel, 0.05, 2.0), likelihood(&xs, &kernel, 0.05, 2.0)] {
+        assert!((0.05..=2.0).contains(&cv.bandwidth));
+        assert_eq!(cv.curve.len(), 50);
+        assert!(cv.curve.iter().all(|&(_, objective)| objective >= cv.objective));
+    }
+}
+
+#[test]
+fn cross_validation_bandwidth_is_within_bounds() {
+    assert_within_bounds(NormalKernel);
+    assert_within_bounds(EpanechnikovKernel);
+    assert_within_bounds(UniformKernel);
+}
+
+fn assert_least_squares_matches_numeric_integration<K: Kernel + Copy>(kernel: K) {
+    let xs = vec![-1.3, -0.2, 0.0, 0.4, 1.9, 2.2];
+    let n = xs.len() as f64;
+
+    let cv = least_squares(&xs, &kernel, 0.5, 1.5);
+    let (bandwidth, objective) = cv.curve[25];
+
+    let kde = KernelDensityEstimation::new(&xs, bandwidth, kernel);
+
+    // Midpoint rule integral of the squared density.
+    let step = 1e-4;
+    let integrated_square: f64 = (0..140_000)
+        .map(|i| -7.0 + (i as f64 + 0.5) * step)
+        .map(|x| kde.density(x).powi(2) * step)
+        .sum();
+
+    // Leave-one-out density at each sample from the full density.
+    let k_0 = kernel.density(0.0);
+    let leave_one_out: f64 = xs.iter()
+        .map(|&x| (n * kde.density(x) - k_0 / bandwidth) / (n - 1.0))
+        .sum();
+
+    let expected = integrated_square - 2.0 * leave_one_out / n;
+    assert!((objective - expected).abs() < 1e-4);
+}
+
+#[test]
+fn least_squares_objective_matches_numeric_integration() {
+    assert_least_squares_matches_numeric_integration(NormalKernel);
+    assert_least_squares_matches_numeric_integration(EpanechnikovKernel);
+    assert_least_squares_matches_numeric_integration(UniformKernel);
+}
+
+#[test]
+fn least_squares_undersmooths_silverman_for_bimodal_sample() {
+    let xs = bimodal_samples();
+    let reference = silverman(&xs);
+
+    let cv = least_squares(&xs, &NormalKernel, 0.05 * reference, 2.0 * reference);
+    assert!(cv.bandwidth < reference);
+}
+
+#[test]
+fn likelihood_undersmooths_silverman_for_bimodal_sample() {
+    let xs = bimodal_samples();
+    let reference = silverman(&xs);
+
+    let cv = likelihood(&xs, &NormalKernel, 0.05 * reference, 2.0 * reference);
+    assert!(cv.bandwidth < reference);
+}
+
+#[test]
+fn likelihood_of_compact_kernel_avoids_isolated_samples() {
+    let xs = vec![0.0, 0.1, 0.2, 5.0, 5.1];
+
+    let cv = likelihood(&xs, &EpanechnikovKernel, 0.01, 1.0);
+    assert!(cv.bandwidth >= 0.1 && cv.objective.is_finite());
+
+    let cv = likelihood(&xs, &UniformKernel, 0.01, 1.0);
+    assert!(cv.bandwidth >= 0.1 && cv.objective.is_finite());
+}
@@ -5,7 +5,9 @@ extern crate rand;
 extern crate quickcheck;
 
 use kernel_density::kde;
-use common::{check, SamplesF64, PositiveF64};
+use kernel_density::kde::EpanechnikovKernel;
+use common::{assert_sorted_evaluation_matches_exhaustive_on_http_ttime, check, exhaustive,
+             SamplesF64, PositiveF64};
 use std::f64;
 
 #[test]
@@ -28,7 +30,7 @@ fn epanechnikov_kde_between_zero_and_one() {
         let kde = kde::epanechnikov(&xs.vec, bandwidth.val);
         let actual = kde.density(x);
 
-        0.0 <= actual && actual <= 1.0
+        (0.0..=1.0).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
@@ -40,7 +42,7 @@ fn epanechnikov_kde_cdf_between_zero_and_one() {
         let kde = kde::epanechnikov(&xs.vec, bandwidth.val);
         let actual = kde.cdf(x);
 
-        0.0 <= actual && actual <= 1.0
+        (0.0..=1.0).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
@@ -52,7 +54,7 @@ fn epanechnikov_kde_cdf_is_an_increasing_function() {
         let kde = kde::epanechnikov(&xs.vec, bandwidth.val);
         let actual = kde.cdf(x);
 
-        kde.cdf(x - 0.01) <= actual && actual <= kde.cdf(x + 0.01)
+        (kde.cdf(x - 0.01)..=kde.cdf(x + 0.01)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
@@ -81,3 +83,22 @@ fn epanechnikov_kde_cdf_f64min_is_zero() {
 
     check(prop as fn(SamplesF64, PositiveF64) -> bool);
 }
+
+#[test]
+fn epanechnikov_kde_sorted_evaluation_matches_exhaustive() {
+    fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+        let weights = vec![1.0; xs.vec.len()];
+        let kde = kde::epanechnikov(&xs.vec, bandwidth.val);
+        let (density, cdf) = exhaustive(&EpanechnikovKernel, &xs.vec, &weights, bandwidth.val, x);
+
+        (kde.density(x) - density).abs() <= 1e-12 * density &&
+        (kde.cdf(x) - cdf).abs() <= 1e-12
+    }
+
+    check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
+}
+
+#[test]
+fn epanechnikov_kde_sorted_evaluation_matches_exhaustive_on_http_ttime() {
+    assert_sorted_evaluation_matches_exhaustive_on_http_ttime(EpanechnikovKernel);
+}
@@ -0,0 +1,110 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::Density;
+use kernel_density::kde::{fast_gauss, KernelDensityEstimation, NormalKernel};
+use kernel_density::kde::fast_gauss::FastGaussTransform;
+use common::{check, read_samples, SamplesF64, PositiveF64};
+
+/// Check the fast Gauss transform is within the error bound of the exact
+/// Normal kernel density estimation at a set of points.
+fn assert_within_error(kde: &KernelDensityEstimation<NormalKernel>, xs: &[f64], error: f64) {
+    let densities = fast_gauss::densities(kde, xs, error);
+
+    for (&x, &density) in xs.iter().zip(&densities) {
+        let exact = kde.density(x);
+        assert!((density - exact).abs() <= error,
+                "x {} fast {} exact {} error {}",
+                x,
+                density,
+                exact,
+                error);
+    }
+}
+
+#[test]
+#[should_panic(expected="assertion failed: error > 0.0")]
+fn fast_gauss_transform_panics_on_zero_error() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    FastGaussTransform::new(&kde, 0.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: error > 0.0")]
+fn relative_fast_gauss_transform_panics_on_zero_error() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    FastGaussTransform::relative(&kde, 0.0);
+}
+
+#[test]
+fn fast_gauss_transform_is_within_error_bound() {
+    fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+        let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, NormalKernel);
+        let transform = FastGaussTransform::new(&kde, 1e-9);
+
+        (transform.density(x) - kde.density(x)).abs() <= 1e-9
+    }
+
+    check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
+}
+
+#[test]
+fn fast_gauss_transform_matches_normal_samples() {
+    let xs = read_samples("normal_0_1.tsv");
+    let points: Vec<f64> = (0..1001).map(|i| -5.0 + 0.01 * i as f64).collect();
+
+    for &bandwidth in &[0.05, 0.25, 1.0] {
+        let kde = KernelDensityEstimation::new(&xs, bandwidth, NormalKernel);
+
+        for &error in &[1e-3, 1e-6, 1e-10] {
+            assert_within_error(&kde, &points, error);
+        }
+    }
+}
+
+#[test]
+fn fast_gauss_transform_matches_weighted_http_ttime() {
+    let xs = read_samples("http_ttime.tsv");
+    let weights: Vec<f64> = (0..xs.len()).map(|i| (i % 4) as f64).collect();
+    let kde = KernelDensityEstimation::weighted(&xs, &weights, 20.0, NormalKernel);
+
+    let points: Vec<f64> = (0..1000).map(|i| 200.0 + 5.5 * i as f64).collect();
+    assert_within_error(&kde, &points, 1e-8);
+}
+
+#[test]
+fn relative_fast_gauss_transform_is_within_error_of_maximum() {
+    let xs = read_samples("http_ttime.tsv");
+    let points: Vec<f64> = (0..1000).map(|i| 200.0 + 5.5 * i as f64).collect();
+
+    for &bandwidth in &[2.0, 20.0, 200.0] {
+        let kde = KernelDensityEstimation::new(&xs, bandwidth, NormalKernel);
+        let exact = kde.densities(&points);
+        let maximum = exact.iter().cloned().fold(0.0, f64::max);
+
+        for &error in &[1e-3, 1e-6, 1e-10] {
+            let transform = FastGaussTransform::relative(&kde, error);
+
+            for (&x, &density) in points.iter().zip(&exact) {
+                assert!((transform.density(x) - density).abs() <= error * maximum);
+            }
+        }
+    }
+}
+
+#[test]
+fn fast_gauss_transform_batch_matches_single_points() {
+    let xs = read_samples("normal_0_2.1.tsv");
+    let kde = KernelDensityEstimation::new(&xs, 0.3, NormalKernel);
+    let transform = FastGaussTransform::new(&kde, 1e-6);
+
+    let points = vec![-3.0, -0.25, 0.0, 1.5, 4.0];
+    let densities = transform.densities(&points);
+
+    for (&x, &density) in points.iter().zip(&densities) {
+        assert_eq!(density, transform.density(x));
+    }
+}
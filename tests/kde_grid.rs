@@ -0,0 +1,147 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::Density;
+use kernel_density::kde::{grid, KernelDensityEstimation, EpanechnikovKernel, NormalKernel,
+                          UniformKernel};
+use common::{check, read_samples, SamplesF64, PositiveF64};
+use std::f64::consts::PI;
+
+#[test]
+#[should_panic(expected="assertion failed: min < max")]
+fn grid_panics_on_empty_range() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    grid::evaluate(&kde, 1.0, 1.0, 10);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: points > 1")]
+fn grid_panics_on_single_point() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    grid::evaluate(&kde, 0.0, 1.0, 1);
+}
+
+#[test]
+fn grid_has_requested_points() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, EpanechnikovKernel);
+        let grid = grid::evaluate(&kde, -1.0, 1.0, 101);
+
+        grid.xs.len() == 101 && grid.density.len() == 101 && grid.cdf.len() == 101 &&
+        grid.xs[0] == -1.0 && grid.xs[100] == 1.0
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn normal_grid_is_within_binning_error_bound() {
+    let xs = read_samples("normal_0_1.tsv");
+    let bandwidth = 0.25;
+    let kde = KernelDensityEstimation::new(&xs, bandwidth, NormalKernel);
+
+    let grid = grid::evaluate(&kde, -2.0, 2.0, 401);
+    let step: f64 = 0.01;
+
+    let density_bound = step.powi(2) / ((2.0 * PI).sqrt() * 8.0 * bandwidth.powi(3));
+    let cdf_bound = step.powi(2) * (-0.5f64).exp() / ((2.0 * PI).sqrt() * 8.0 * bandwidth.powi(2));
+
+    for i in 0..grid.xs.len() {
+        let x = grid.xs[i];
+        assert!((grid.density[i] - kde.density(x)).abs() <= density_bound + 1e-12);
+        assert!((grid.cdf[i] - kde.cdf(x)).abs() <= cdf_bound + 1e-12);
+    }
+}
+
+#[test]
+fn epanechnikov_grid_is_close_to_exact() {
+    let xs = read_samples("normal_0_2.tsv");
+    let bandwidth = 0.5;
+    let kde = KernelDensityEstimation::new(&xs, bandwidth, EpanechnikovKernel);
+
+    // Grid range narrower than the samples.
+    let grid = grid::evaluate(&kde, -1.0, 1.5, 1001);
+
+    for i in 0..grid.xs.len() {
+        let x = grid.xs[i];
+        assert!((grid.density[i] - kde.density(x)).abs() < 1e-4);
+        assert!((grid.cdf[i] - kde.cdf(x)).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn weighted_grid_is_close_to_exact() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let weights: Vec<f64> = (0..xs.len()).map(|i| (i % 5) as f64).collect();
+    let kde = KernelDensityEstimation::weighted(&xs, &weights, 0.5, NormalKernel);
+
+    let grid = grid::evaluate(&kde, -4.0, 4.0, 801);
+
+    for i in 0..grid.xs.len() {
+        let x = grid.xs[i];
+        assert!((grid.density[i] - kde.density(x)).abs() < 1e-4);
+        assert!((grid.cdf[i] - kde.cdf(x)).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn grid_cdf_is_increasing_and_between_zero_and_one() {
+    let xs = read_samples("http_ttime.tsv");
+    let kde = KernelDensityEstimation::new(&xs, 10.0, UniformKernel);
+
+    let grid = grid::evaluate(&kde, 0.0, 6000.0, 6001);
+
+    assert!(grid.cdf[0] < 1e-12);
+    assert!((grid.cdf[6000] - 1.0).abs() < 1e-12);
+
+    for i in 1..grid.cdf.len() {
+        assert!(grid.cdf[i - 1] <= grid.cdf[i] + 1e-12);
+        assert!((0.0..=1.0).contains(&grid.cdf[i]));
+        assert!(grid.density[i] >= 0.0);
+    }
+}
+
+#[test]
+fn grid_counts_distant_samples_in_cdf() {
+    let xs = vec![-100.0, 0.0, 100.0, 200.0];
+    let kde = KernelDensityEstimation::new(&xs, 1.0, EpanechnikovKernel);
+
+    let grid = grid::evaluate(&kde, -5.0, 5.0, 101);
+
+    assert!((grid.cdf[0] - 0.25).abs() < 1e-12);
+    assert!((grid.cdf[100] - 0.5).abs() < 1e-12);
+    assert!((grid.density[50] - 0.75 / 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn grid_bins_nearby_samples_beyond_grid_width() {
+    // Wide kernels reach samples further away than the grid width.
+    let xs = vec![-30.0, 0.0, 0.5, 40.0];
+    let kde = KernelDensityEstimation::new(&xs, 20.0, NormalKernel);
+
+    let grid = grid::evaluate(&kde, 0.0, 1.0, 101);
+
+    for i in 0..grid.xs.len() {
+        let x = grid.xs[i];
+        assert!((grid.density[i] - kde.density(x)).abs() < 1e-9);
+        assert!((grid.cdf[i] - kde.cdf(x)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn grid_evaluates_samples_beyond_bin_extension_exactly() {
+    // The kernel support is far wider than the bins extend beyond the grid.
+    let xs = vec![-500.0, 0.0, 0.5, 300.0];
+    let kde = KernelDensityEstimation::new(&xs, 200.0, NormalKernel);
+
+    let grid = grid::evaluate(&kde, 0.0, 1.0, 101);
+
+    for i in 0..grid.xs.len() {
+        let x = grid.xs[i];
+        assert!((grid.density[i] - kde.density(x)).abs() < 1e-12);
+        assert!((grid.cdf[i] - kde.cdf(x)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,360 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::Density;
+use kernel_density::kde;
+use kernel_density::kde::{BiweightKernel, CosineKernel, EpanechnikovKernel, Kernel,
+                          KernelDensityEstimation, LogisticKernel, NormalKernel, SigmoidKernel,
+                          TriangularKernel, TricubeKernel, TriweightKernel, UniformKernel};
+use common::{check, read_fixture, read_samples, SamplesF64, PositiveF64};
+use std::f64;
+
+/// Triangular kernel defined outside the crate.
+struct CustomKernel;
+
+impl Kernel for CustomKernel {
+    fn density(&self, x: f64) -> f64 {
+        (1.0 - x.abs()).max(0.0)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x >= 1.0 {
+            1.0
+        } else if x >= 0.0 {
+            1.0 - 0.5 * (1.0 - x).powi(2)
+        } else if x > -1.0 {
+            0.5 * (1.0 + x).powi(2)
+        } else {
+            0.0
+        }
+    }
+
+    fn support(&self) -> f64 {
+        1.0
+    }
+
+    fn second_moment(&self) -> f64 {
+        1.0 / 6.0
+    }
+
+    fn roughness(&self) -> f64 {
+        2.0 / 3.0
+    }
+}
+
+/// Wrapper using the default convolution, derivative and log density
+/// implementations of a kernel.
+struct DefaultConvolution<K: Kernel>(K);
+
+impl<K: Kernel> Kernel for DefaultConvolution<K> {
+    fn density(&self, x: f64) -> f64 {
+        self.0.density(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        self.0.cdf(x)
+    }
+
+    fn support(&self) -> f64 {
+        self.0.support()
+    }
+
+    fn second_moment(&self) -> f64 {
+        self.0.second_moment()
+    }
+
+    fn roughness(&self) -> f64 {
+        self.0.roughness()
+    }
+}
+
+/// Midpoint rule integral over [-radius, radius].
+fn integrate<F: Fn(f64) -> f64>(f: F, radius: f64) -> f64 {
+    let steps = 200_000;
+    let step = 2.0 * radius / steps as f64;
+
+    (0..steps).map(|i| f(-radius + (i as f64 + 0.5) * step) * step).sum()
+}
+
+fn assert_kernel_constants<K: Kernel>(kernel: K) {
+    let radius = kernel.support().min(40.0);
+
+    assert!((integrate(|x| kernel.density(x), radius) - 1.0).abs() < 1e-6);
+    assert!((integrate(|x| x * x * kernel.density(x), radius) - kernel.second_moment()).abs() <
+            1e-6);
+    assert!((integrate(|x| kernel.density(x).powi(2), radius) - kernel.roughness()).abs() < 1e-6);
+    assert!((kernel.convolution(0.0) - kernel.roughness()).abs() < 1e-9);
+
+    assert!(kernel.cdf(-radius - 1.0) < 1e-15);
+    assert!(kernel.cdf(radius + 1.0) > 1.0 - 1e-15);
+    assert!((kernel.cdf(0.0) - 0.5).abs() < 1e-12);
+
+    for &x in &[-0.9, -0.5, 0.25, 0.75] {
+        let integral = integrate(|t| if t <= x { kernel.density(t) } else { 0.0 }, radius);
+        assert!((kernel.cdf(x) - integral).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn builtin_kernel_constants_are_consistent() {
+    assert_kernel_constants(NormalKernel);
+    assert_kernel_constants(EpanechnikovKernel);
+    assert_kernel_constants(UniformKernel);
+    assert_kernel_constants(TriangularKernel);
+    assert_kernel_constants(BiweightKernel);
+    assert_kernel_constants(TriweightKernel);
+    assert_kernel_constants(TricubeKernel);
+    assert_kernel_constants(CosineKernel);
+    assert_kernel_constants(LogisticKernel);
+    assert_kernel_constants(SigmoidKernel);
+}
+
+#[test]
+fn custom_kernel_constants_are_consistent() {
+    assert_kernel_constants(CustomKernel);
+}
+
+fn assert_default_convolution_matches<K: Kernel>(kernel: K) {
+    let default = DefaultConvolution(kernel);
+
+    for &x in &[0.0, 0.3, -0.7, 1.0, 1.5, 2.5] {
+        assert!((default.convolution(x) - default.0.convolution(x)).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn default_convolution_matches_closed_forms() {
+    assert_default_convolution_matches(NormalKernel);
+    assert_default_convolution_matches(EpanechnikovKernel);
+    assert_default_convolution_matches(UniformKernel);
+}
+
+fn assert_default_derivative_matches<K: Kernel>(kernel: K) {
+    let default = DefaultConvolution(kernel);
+
+    for &x in &[0.0, 0.3, -0.7, 0.99, 1.5, -2.5] {
+        assert!((default.derivative(x) - default.0.derivative(x)).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn default_derivative_matches_closed_forms() {
+    assert_default_derivative_matches(NormalKernel);
+    assert_default_derivative_matches(EpanechnikovKernel);
+    assert_default_derivative_matches(UniformKernel);
+}
+
+fn assert_default_log_density_matches<K: Kernel>(kernel: K) {
+    let default = DefaultConvolution(kernel);
+
+    for &x in &[0.0, 0.3, -0.7, 1.5, -2.5, 10.0, -30.0] {
+        assert!((default.log_density(x) - default.0.log_density(x)).abs() < 1e-12);
+    }
+
+    // The closed forms stay finite where the density underflows.
+    assert_eq!(default.0.density(2000.0), 0.0);
+    assert!(default.0.log_density(2000.0).is_finite());
+}
+
+#[test]
+fn default_log_density_matches_closed_forms() {
+    assert_default_log_density_matches(NormalKernel);
+    assert_default_log_density_matches(LogisticKernel);
+    assert_default_log_density_matches(SigmoidKernel);
+}
+
+#[test]
+fn canonical_bandwidths() {
+    assert!((NormalKernel.canonical_bandwidth() - 0.7763883571).abs() < 1e-9);
+    assert!((EpanechnikovKernel.canonical_bandwidth() - 15f64.powf(0.2)).abs() < 1e-12);
+    assert!((UniformKernel.canonical_bandwidth() - 4.5f64.powf(0.2)).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: length > 0")]
+fn new_custom_kde_panics_on_empty_samples_set() {
+    let xs: Vec<f64> = vec![];
+    KernelDensityEstimation::new(&xs, 1.0, CustomKernel);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: bandwidth > 0.0")]
+fn custom_kde_panics_on_zero_bandwidth() {
+    let xs: Vec<f64> = vec![0.0];
+    KernelDensityEstimation::new(&xs, 0.0, CustomKernel);
+}
+
+#[test]
+fn custom_kde_density() {
+    let xs = vec![0.0, 1.0, 3.0];
+    let kde = KernelDensityEstimation::new(&xs, 2.0, CustomKernel);
+
+    // Contributions (1 - 0.25) and (1 - 0.25) from 0 and 1, none from 3.
+    assert_eq!(kde.density(0.5), 1.5 / 6.0);
+}
+
+#[test]
+fn custom_kde_cdf_between_zero_and_one() {
+    fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+        let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, CustomKernel);
+        let actual = kde.cdf(x);
+
+        (0.0..=1.0).contains(&actual)
+    }
+
+    check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
+}
+
+#[test]
+fn custom_kde_cdf_is_an_increasing_function() {
+    fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+        let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, CustomKernel);
+        let actual = kde.cdf(x);
+
+        (kde.cdf(x - 0.01)..=kde.cdf(x + 0.01)).contains(&actual)
+    }
+
+    check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
+}
+
+/// Kernels with a single value, so generic quickcheck properties can
+/// construct them.
+trait UnitKernel: Kernel {
+    const KERNEL: Self;
+}
+
+impl UnitKernel for TriangularKernel {
+    const KERNEL: TriangularKernel = TriangularKernel;
+}
+
+impl UnitKernel for BiweightKernel {
+    const KERNEL: BiweightKernel = BiweightKernel;
+}
+
+impl UnitKernel for TriweightKernel {
+    const KERNEL: TriweightKernel = TriweightKernel;
+}
+
+impl UnitKernel for TricubeKernel {
+    const KERNEL: TricubeKernel = TricubeKernel;
+}
+
+impl UnitKernel for CosineKernel {
+    const KERNEL: CosineKernel = CosineKernel;
+}
+
+impl UnitKernel for LogisticKernel {
+    const KERNEL: LogisticKernel = LogisticKernel;
+}
+
+impl UnitKernel for SigmoidKernel {
+    const KERNEL: SigmoidKernel = SigmoidKernel;
+}
+
+fn kde_between_zero_and_one<K: UnitKernel>(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+    let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, K::KERNEL);
+    let actual = kde.density(x);
+
+    (0.0..=1.0).contains(&actual)
+}
+
+fn kde_cdf_between_zero_and_one<K: UnitKernel>(xs: SamplesF64,
+                                               x: f64,
+                                               bandwidth: PositiveF64)
+                                               -> bool {
+    let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, K::KERNEL);
+    let actual = kde.cdf(x);
+
+    (0.0..=1.0).contains(&actual)
+}
+
+fn kde_cdf_is_an_increasing_function<K: UnitKernel>(xs: SamplesF64,
+                                                    x: f64,
+                                                    bandwidth: PositiveF64)
+                                                    -> bool {
+    let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, K::KERNEL);
+    let actual = kde.cdf(x);
+
+    (kde.cdf(x - 0.01)..=kde.cdf(x + 0.01)).contains(&actual)
+}
+
+fn kde_cdf_f64max_is_one<K: UnitKernel>(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+    let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, K::KERNEL);
+    kde.cdf(f64::MAX) == 1.0
+}
+
+fn kde_cdf_f64min_is_zero<K: UnitKernel>(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+    let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, K::KERNEL);
+    kde.cdf(f64::MIN) == 0.0
+}
+
+fn assert_kde_properties<K: UnitKernel>() {
+    check(kde_between_zero_and_one::<K> as fn(SamplesF64, f64, PositiveF64) -> bool);
+    check(kde_cdf_between_zero_and_one::<K> as fn(SamplesF64, f64, PositiveF64) -> bool);
+    check(kde_cdf_is_an_increasing_function::<K> as fn(SamplesF64, f64, PositiveF64) -> bool);
+    check(kde_cdf_f64max_is_one::<K> as fn(SamplesF64, PositiveF64) -> bool);
+    check(kde_cdf_f64min_is_zero::<K> as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn builtin_kde_properties() {
+    assert_kde_properties::<TriangularKernel>();
+    assert_kde_properties::<BiweightKernel>();
+    assert_kde_properties::<TriweightKernel>();
+    assert_kde_properties::<TricubeKernel>();
+    assert_kde_properties::<CosineKernel>();
+    assert_kde_properties::<LogisticKernel>();
+    assert_kde_properties::<SigmoidKernel>();
+}
+
+fn assert_matches_fixture(kde: Box<dyn Density>, name: &str) {
+    for (x, density, cdf) in read_fixture(name) {
+        assert!((kde.density(x) - density).abs() < 1e-12);
+        assert!((kde.cdf(x) - cdf).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn triangular_kde_matches_fixture() {
+    let xs = read_samples("normal_0_1.tsv");
+    assert_matches_fixture(kde::triangular(&xs, 0.25), "kde_normal_0_1_triangular_0.25.tsv");
+}
+
+#[test]
+fn biweight_kde_matches_fixture() {
+    let xs = read_samples("normal_0_1.tsv");
+    assert_matches_fixture(kde::biweight(&xs, 0.25), "kde_normal_0_1_biweight_0.25.tsv");
+}
+
+#[test]
+fn triweight_kde_matches_fixture() {
+    let xs = read_samples("normal_0_1.tsv");
+    assert_matches_fixture(kde::triweight(&xs, 0.25), "kde_normal_0_1_triweight_0.25.tsv");
+}
+
+#[test]
+fn tricube_kde_matches_fixture() {
+    let xs = read_samples("normal_0_1.tsv");
+    assert_matches_fixture(kde::tricube(&xs, 0.25), "kde_normal_0_1_tricube_0.25.tsv");
+}
+
+#[test]
+fn cosine_kde_matches_fixture() {
+    let xs = read_samples("normal_0_1.tsv");
+    assert_matches_fixture(kde::cosine(&xs, 0.25), "kde_normal_0_1_cosine_0.25.tsv");
+}
+
+#[test]
+fn logistic_kde_matches_fixture() {
+    let xs = read_samples("normal_0_1.tsv");
+    assert_matches_fixture(kde::logistic(&xs, 0.25), "kde_normal_0_1_logistic_0.25.tsv");
+}
+
+#[test]
+fn sigmoid_kde_matches_fixture() {
+    let xs = read_samples("normal_0_1.tsv");
+    assert_matches_fixture(kde::sigmoid(&xs, 0.25), "kde_normal_0_1_sigmoid_0.25.tsv");
+}
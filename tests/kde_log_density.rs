@@ -0,0 +1,81 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::{log_likelihood, Density};
+use kernel_density::kde::{EpanechnikovKernel, KernelDensityEstimation, LogisticKernel,
+                          NormalKernel};
+use common::{check, read_samples, SamplesF64, PositiveF64};
+use std::f64;
+
+#[test]
+fn log_density_is_log_of_density() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64, x: f64) -> bool {
+        let normal = KernelDensityEstimation::new(&xs.vec, bandwidth.val, NormalKernel);
+        let logistic = KernelDensityEstimation::new(&xs.vec, bandwidth.val, LogisticKernel);
+        let epanechnikov = KernelDensityEstimation::new(&xs.vec,
+                                                        bandwidth.val,
+                                                        EpanechnikovKernel);
+
+        let densities: [&dyn Density; 3] = [&normal, &logistic, &epanechnikov];
+        densities.iter().all(|kde| {
+            let density = kde.density(x);
+
+            // Compare only where the density is a normal float.
+            density < f64::MIN_POSITIVE ||
+            (kde.log_density(x) - density.ln()).abs() < 1e-9 * (1.0 + density.ln().abs())
+        })
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64, f64) -> bool);
+}
+
+#[test]
+fn normal_log_density_does_not_underflow() {
+    let xs = vec![-1.0, 0.0, 2.0];
+    let kde = KernelDensityEstimation::new(&xs, 0.1, NormalKernel);
+
+    // The nearest sample dominates far from the samples.
+    let x = 50.0;
+    let expected = -0.5 * ((x - 2.0) / 0.1f64).powi(2) - 0.5 * (2.0 * f64::consts::PI).ln() -
+                   (3.0 * 0.1f64).ln();
+
+    assert_eq!(kde.density(x), 0.0);
+    assert!((kde.log_density(x) - expected).abs() < 1e-9 * expected.abs());
+}
+
+#[test]
+fn weighted_log_density_ignores_zero_weights() {
+    let kde = KernelDensityEstimation::weighted(&[0.0, 100.0], &[1.0, 0.0], 1.0, NormalKernel);
+    let single = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+
+    for &x in &[-50.0, 0.0, 50.0, 100.0] {
+        assert_eq!(kde.log_density(x), single.log_density(x));
+    }
+}
+
+#[test]
+fn compact_kernel_log_density_is_negative_infinity_outside_support() {
+    let kde = KernelDensityEstimation::new(&[0.0, 1.0], 0.5, EpanechnikovKernel);
+
+    assert_eq!(kde.log_density(2.0), f64::NEG_INFINITY);
+    assert_eq!(log_likelihood(&kde, &[0.5, 2.0]), f64::NEG_INFINITY);
+}
+
+#[test]
+fn held_out_log_likelihood_prefers_better_bandwidth() {
+    let train = read_samples("normal_0_1.1.tsv");
+    let test = read_samples("normal_0_1.2.tsv");
+
+    let log_likelihood_with = |bandwidth: f64| {
+        let kde = KernelDensityEstimation::new(&train, bandwidth, NormalKernel);
+        log_likelihood(&kde, &test)
+    };
+
+    // Silverman's rule gives about 0.35 for 256 standard Normal samples.
+    assert!(log_likelihood_with(0.35) > log_likelihood_with(0.01));
+    assert!(log_likelihood_with(0.35) > log_likelihood_with(5.0));
+    assert!(log_likelihood_with(0.01).is_finite());
+}
@@ -0,0 +1,131 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::{linspace, Density};
+use kernel_density::kde::{EpanechnikovKernel, KernelDensityEstimation,
+                          MixtureKernelDensityEstimation, NormalKernel, UniformKernel};
+use common::{check, read_samples, seeded, SamplesF64, PositiveF64, EPSILON};
+
+#[test]
+#[should_panic(expected="assertion failed: length > 0")]
+fn new_mixture_panics_on_no_components() {
+    let components: Vec<KernelDensityEstimation<NormalKernel>> = vec![];
+    MixtureKernelDensityEstimation::new(components, &[]);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: weights.len() == length")]
+fn new_mixture_panics_on_mismatched_weights() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    MixtureKernelDensityEstimation::new(vec![kde], &[1.0, 1.0]);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: total_weight > 0.0")]
+fn new_mixture_panics_on_zero_weights() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    MixtureKernelDensityEstimation::new(vec![kde], &[0.0]);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: total_weight > 0.0")]
+fn remove_panics_on_last_weighted_component() {
+    let kde = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    let mut mixture = MixtureKernelDensityEstimation::pooled(vec![kde]);
+    mixture.remove(0);
+}
+
+#[test]
+#[should_panic(expected="estimation.bandwidth == bandwidth")]
+fn concatenate_panics_on_different_bandwidths() {
+    let first = KernelDensityEstimation::new(&[0.0], 1.0, NormalKernel);
+    let second = KernelDensityEstimation::new(&[0.0], 2.0, NormalKernel);
+    KernelDensityEstimation::concatenate(&[&first, &second]);
+}
+
+#[test]
+fn pooled_shards_match_concatenated_samples() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64, x: f64) -> bool {
+        let middle = xs.vec.len().div_ceil(2);
+        if middle == xs.vec.len() {
+            return true;
+        }
+
+        let shards: Vec<&[f64]> = vec![&xs.vec[..middle], &xs.vec[middle..]];
+        let estimations: Vec<KernelDensityEstimation<EpanechnikovKernel>> = shards.iter()
+            .map(|shard| KernelDensityEstimation::new(shard, bandwidth.val, EpanechnikovKernel))
+            .collect();
+
+        let full = KernelDensityEstimation::new(&xs.vec, bandwidth.val, EpanechnikovKernel);
+        let concatenated = KernelDensityEstimation::concatenate(&[&estimations[0],
+                                                                  &estimations[1]]);
+        let mixture = MixtureKernelDensityEstimation::pooled(estimations);
+
+        let density = full.density(x);
+        let cdf = full.cdf(x);
+
+        concatenated.samples() == full.samples() && concatenated.density(x) == density &&
+        concatenated.cdf(x) == cdf &&
+        (mixture.density(x) - density).abs() < EPSILON * (1.0 + density) &&
+        (mixture.cdf(x) - cdf).abs() < EPSILON
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64, f64) -> bool);
+}
+
+#[test]
+fn mixture_weights_combine_components() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let narrow = KernelDensityEstimation::new(&xs, 0.2, NormalKernel);
+    let wide = KernelDensityEstimation::new(&xs, 0.8, NormalKernel);
+    let values = linspace(-4.0, 4.0, 41);
+
+    let mixture = MixtureKernelDensityEstimation::new(vec![narrow, wide], &[1.0, 3.0]);
+    let densities = mixture.densities(&values);
+    let cdfs = mixture.cdfs(&values);
+
+    for (i, &x) in values.iter().enumerate() {
+        let density = 0.25 * mixture.components()[0].density(x) +
+                      0.75 * mixture.components()[1].density(x);
+        let cdf = 0.25 * mixture.components()[0].cdf(x) + 0.75 * mixture.components()[1].cdf(x);
+
+        assert!((mixture.density(x) - density).abs() < EPSILON);
+        assert!((mixture.cdf(x) - cdf).abs() < EPSILON);
+        assert!((densities[i] - density).abs() < EPSILON);
+        assert!((cdfs[i] - cdf).abs() < EPSILON);
+    }
+
+    assert!(mixture.cdf(-20.0) < 1e-12);
+    assert!(mixture.cdf(20.0) > 1.0 - 1e-12);
+}
+
+#[test]
+fn push_and_remove_shards() {
+    let low = KernelDensityEstimation::new(&[0.0, 0.5], 1.0, UniformKernel);
+    let high = KernelDensityEstimation::new(&[10.0], 1.0, UniformKernel);
+
+    let mut mixture = MixtureKernelDensityEstimation::pooled(vec![low]);
+    mixture.push(high, 1.0);
+    assert!((mixture.cdf(5.0) - 2.0 / 3.0).abs() < EPSILON);
+
+    let removed = mixture.remove(0);
+    assert_eq!(removed.samples(), &[0.0, 0.5]);
+    assert_eq!(mixture.cdf(5.0), 0.0);
+    assert_eq!(mixture.density(10.0), 0.5);
+}
+
+#[test]
+fn mixture_draws_follow_weights() {
+    let low = KernelDensityEstimation::new(&[0.0], 1.0, UniformKernel);
+    let high = KernelDensityEstimation::new(&[10.0], 1.0, UniformKernel);
+    let mixture = MixtureKernelDensityEstimation::new(vec![low, high], &[1.0, 3.0]);
+
+    let mut rng = seeded();
+    let draws = 10000;
+    let high_draws = (0..draws).filter(|_| mixture.sample(&mut rng) > 5.0).count();
+
+    assert!((high_draws as f64 / draws as f64 - 0.75).abs() < 0.02);
+}
@@ -0,0 +1,143 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::Density;
+use kernel_density::kde::{modes, EpanechnikovKernel, KernelDensityEstimation, NormalKernel,
+                          UniformKernel};
+use kernel_density::kde::bandwidth::rescale;
+use common::{check, read_samples, SamplesF64, PositiveF64};
+
+#[test]
+#[should_panic(expected="assertion failed: shadow(kernel, 0.0) > 0.0")]
+fn mean_shift_panics_on_uniform_kernel() {
+    let kde = KernelDensityEstimation::new(&[0.0, 1.0], 1.0, UniformKernel);
+    modes::mean_shift(&kde);
+}
+
+#[test]
+fn single_sample_has_single_mode() {
+    let kde = KernelDensityEstimation::new(&[3.0], 1.0, EpanechnikovKernel);
+    let clustering = modes::mean_shift(&kde);
+
+    assert_eq!(clustering.modes.len(), 1);
+    assert_eq!(clustering.labels, vec![0]);
+
+    let mode = &clustering.modes[0];
+    assert_eq!(mode.location, 3.0);
+    assert_eq!(mode.density, 0.75);
+    assert_eq!(mode.lower, f64::NEG_INFINITY);
+    assert_eq!(mode.upper, f64::INFINITY);
+    assert_eq!(mode.mass, 1.0);
+}
+
+#[test]
+fn normal_sample_is_unimodal() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let kde = KernelDensityEstimation::new(&xs, 0.5, NormalKernel);
+
+    let modes = modes::modes(&kde);
+    assert_eq!(modes.len(), 1);
+    assert!(modes[0].location.abs() < 0.25);
+    assert!((modes[0].mass - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn normal_mixture_is_bimodal() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let mut mixture = xs.clone();
+    mixture.extend(xs.iter().map(|x| x + 10.0));
+
+    // Wide enough that no sample in the tails is isolated.
+    for &bandwidth in &[0.8, 1.0] {
+        let normal = KernelDensityEstimation::new(&mixture, bandwidth, NormalKernel);
+        let epanechnikov = KernelDensityEstimation::new(&mixture,
+                                                        rescale(bandwidth, &EpanechnikovKernel),
+                                                        EpanechnikovKernel);
+
+        for clustering in &[modes::mean_shift(&normal), modes::mean_shift(&epanechnikov)] {
+            let modes = &clustering.modes;
+            assert_eq!(modes.len(), 2);
+
+            assert!(modes[0].location.abs() < 0.5);
+            assert!((modes[1].location - 10.0).abs() < 0.5);
+            assert!((modes[0].upper - 5.0).abs() < 1.0);
+            assert_eq!(modes[0].upper, modes[1].lower);
+            assert!((modes[0].mass - 0.5).abs() < 1e-3);
+            assert!((modes[1].mass - 0.5).abs() < 1e-3);
+
+            // Samples are sorted, so the first half belong to the first mode.
+            let half = xs.len();
+            assert!(clustering.labels[..half].iter().all(|&label| label == 0));
+            assert!(clustering.labels[half..].iter().all(|&label| label == 1));
+        }
+    }
+}
+
+#[test]
+fn modes_are_local_maxima() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        // Mean shift from every sample is quadratic for wide bandwidths.
+        let xs: Vec<f64> = xs.vec.into_iter().take(100).collect();
+        let kde = KernelDensityEstimation::new(&xs, bandwidth.val, NormalKernel);
+        let clustering = modes::mean_shift(&kde);
+        let modes = &clustering.modes;
+
+        let delta = 1e-2 * bandwidth.val;
+        let maxima = modes.iter().all(|mode| {
+            mode.lower < mode.location && mode.location < mode.upper &&
+            kde.density(mode.location - delta) <= mode.density &&
+            kde.density(mode.location + delta) <= mode.density
+        });
+
+        let sorted = modes.windows(2).all(|pair| pair[0].upper == pair[1].lower);
+        let total_mass: f64 = modes.iter().map(|mode| mode.mass).sum();
+
+        let labelled = clustering.labels.iter().zip(kde.samples()).all(|(&label, &x)| {
+            label < modes.len() && modes[label].lower <= x && x <= modes[label].upper
+        });
+
+        maxima && sorted && (total_mass - 1.0).abs() < 1e-9 && labelled
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn distant_modes_have_central_antimodes() {
+    let xs = vec![-815865200993.0, 0.0, 0.0, 778567939430.165];
+    let kde = KernelDensityEstimation::new(&xs, 17143731.946899414, NormalKernel);
+
+    let modes = modes::modes(&kde);
+    assert_eq!(modes.len(), 3);
+    assert!(modes.iter().all(|mode| mode.lower < mode.location && mode.location < mode.upper));
+    assert!((modes[0].upper + 407932600496.5).abs() < 1e3);
+    assert!((modes[1].upper - 389283969715.0825).abs() < 1e3);
+}
+
+#[test]
+fn weighted_samples_shift_modes() {
+    let xs = vec![0.0, 1.0];
+    let kde = KernelDensityEstimation::weighted(&xs, &[1.0, 3.0], 1.0, NormalKernel);
+
+    let modes = modes::modes(&kde);
+    assert_eq!(modes.len(), 1);
+    assert!(modes[0].location > 0.5 && modes[0].location < 1.0);
+}
+
+#[test]
+fn sample_entry_points_match_estimation_modes() {
+    let xs = read_samples("normal_0_1.1.tsv");
+
+    let normal = KernelDensityEstimation::new(&xs, 0.3, NormalKernel);
+    assert_eq!(modes::normal(&xs, 0.3), modes::modes(&normal));
+    assert_eq!(modes::normal_mean_shift(&xs, 0.3).labels,
+               modes::mean_shift(&normal).labels);
+
+    let epanechnikov = KernelDensityEstimation::new(&xs, 0.5, EpanechnikovKernel);
+    assert_eq!(modes::epanechnikov(&xs, 0.5), modes::modes(&epanechnikov));
+    assert_eq!(modes::epanechnikov_mean_shift(&xs, 0.5).labels,
+               modes::mean_shift(&epanechnikov).labels);
+}
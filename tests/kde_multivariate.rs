@@ -0,0 +1,233 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::{Density, MultivariateDensity};
+use kernel_density::kde;
+use kernel_density::kde::{bandwidth, BandwidthMatrix, MultivariateKernelDensityEstimation,
+                          EpanechnikovKernel, NormalKernel, UniformKernel};
+use common::{check, SamplesF64, PositiveF64};
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// Read the (ctime, dtime) columns of the first rows of dat/http.tsv.
+fn read_http(rows: usize) -> Vec<Vec<f64>> {
+    let file = BufReader::new(File::open("dat/http.tsv").unwrap());
+
+    file.lines()
+        .skip(1)
+        .take(rows)
+        .map(|line| {
+            line.unwrap()
+                .split('\t')
+                .take(2)
+                .map(|value| value.parse::<f64>().expect("Not a floating point number."))
+                .collect()
+        })
+        .collect()
+}
+
+/// Integrate a bivariate density over a rectangle using the midpoint rule.
+fn integrate(kde: &dyn MultivariateDensity, x: (f64, f64), y: (f64, f64), steps: usize) -> f64 {
+    let dx = (x.1 - x.0) / steps as f64;
+    let dy = (y.1 - y.0) / steps as f64;
+
+    let mut sum = 0.0;
+    for i in 0..steps {
+        for j in 0..steps {
+            let point = [x.0 + (i as f64 + 0.5) * dx, y.0 + (j as f64 + 0.5) * dy];
+            sum += kde.density(&point);
+        }
+    }
+
+    sum * dx * dy
+}
+
+#[test]
+#[should_panic(expected="assertion failed: length > 0")]
+fn multivariate_kde_panics_on_empty_samples_set() {
+    kde::normal_multivariate(&[], BandwidthMatrix::Scalar(1.0));
+}
+
+#[test]
+#[should_panic(expected="assertion failed: samples.iter().all(|sample| sample.len() == dimension)")]
+fn multivariate_kde_panics_on_mixed_dimensions() {
+    kde::normal_multivariate(&[vec![0.0, 1.0], vec![0.0]], BandwidthMatrix::Scalar(1.0));
+}
+
+#[test]
+#[should_panic(expected="assertion failed: bandwidths.len() == dimension")]
+fn multivariate_kde_panics_on_wrong_bandwidth_dimension() {
+    kde::normal_multivariate(&[vec![0.0, 1.0]], BandwidthMatrix::Diagonal(vec![1.0]));
+}
+
+#[test]
+#[should_panic(expected="assertion failed: sum > 0.0")]
+fn multivariate_kde_panics_on_indefinite_bandwidth_matrix() {
+    let bandwidth = BandwidthMatrix::Full(vec![vec![1.0, 2.0], vec![2.0, 1.0]]);
+    kde::normal_multivariate(&[vec![0.0, 1.0]], bandwidth);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: full[i][j] == full[j][i]")]
+fn multivariate_kde_panics_on_asymmetric_bandwidth_matrix() {
+    let bandwidth = BandwidthMatrix::Full(vec![vec![1.0, 0.5], vec![0.0, 1.0]]);
+    kde::normal_multivariate(&[vec![0.0, 1.0]], bandwidth);
+}
+
+#[test]
+fn one_dimensional_kde_is_univariate_kde() {
+    fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+        let points: Vec<Vec<f64>> = xs.vec.iter().map(|&x| vec![x]).collect();
+        let multivariate = MultivariateKernelDensityEstimation::new(&points,
+                                                                    BandwidthMatrix::Scalar(bandwidth.val),
+                                                                    EpanechnikovKernel);
+        let univariate = kde::epanechnikov(&xs.vec, bandwidth.val);
+
+        let expected = univariate.density(x);
+        (multivariate.density(&[x]) - expected).abs() <= 1e-12 * expected
+    }
+
+    check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
+}
+
+#[test]
+fn equivalent_bandwidth_matrices_are_equal() {
+    let samples = read_http(200);
+
+    let scalar = kde::normal_multivariate(&samples, BandwidthMatrix::Scalar(10.0));
+    let diagonal = kde::normal_multivariate(&samples, BandwidthMatrix::Diagonal(vec![10.0, 10.0]));
+    let full = kde::normal_multivariate(&samples,
+                                        BandwidthMatrix::Full(vec![vec![100.0, 0.0],
+                                                                   vec![0.0, 100.0]]));
+
+    for point in &[[450.0, 450.0], [470.0, 440.0], [500.0, 520.0]] {
+        let expected = scalar.density(point);
+        assert!((diagonal.density(point) - expected).abs() <= 1e-15 * expected.max(1.0));
+        assert!((full.density(point) - expected).abs() <= 1e-15 * expected.max(1.0));
+    }
+}
+
+#[test]
+fn normal_product_kernel_is_bivariate_normal() {
+    let samples = vec![vec![0.0, 0.0], vec![1.0, -1.0]];
+    let (a, b, c) = (2.0, 0.6, 0.5);
+    let bandwidth = BandwidthMatrix::Full(vec![vec![a, b], vec![b, c]]);
+    let kde = kde::normal_multivariate(&samples, bandwidth);
+
+    // Bivariate normal density with covariance [[a, b], [b, c]].
+    let determinant: f64 = a * c - b * b;
+    let normal = |x: f64, y: f64| {
+        let exponent = (c * x * x - 2.0 * b * x * y + a * y * y) / determinant;
+        (-0.5 * exponent).exp() / (2.0 * PI * determinant.sqrt())
+    };
+
+    for &(x, y) in &[(0.0, 0.0), (0.5, -0.2), (2.0, 1.0), (-1.0, -3.0)] {
+        let expected = 0.5 * (normal(x, y) + normal(x - 1.0, y + 1.0));
+        assert!((kde.density(&[x, y]) - expected).abs() < 1e-15);
+    }
+}
+
+#[test]
+fn multivariate_kde_integrates_to_one() {
+    let samples = vec![vec![0.0, 0.0], vec![1.0, 0.5], vec![0.5, 2.0]];
+    let bandwidth = BandwidthMatrix::Full(vec![vec![1.0, 0.3], vec![0.3, 0.5]]);
+    let kde = MultivariateKernelDensityEstimation::new(&samples, bandwidth, EpanechnikovKernel);
+
+    let integral = integrate(&kde, (-2.0, 3.0), (-2.0, 4.0), 400);
+    assert!((integral - 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn diagonal_marginal_integrates_out_other_dimension() {
+    let samples = read_http(50);
+    let bandwidth = BandwidthMatrix::Diagonal(vec![8.0, 12.0]);
+    let kde = MultivariateKernelDensityEstimation::new(&samples, bandwidth, UniformKernel);
+
+    let marginal = kde.marginal(1);
+    let expected = kde::uniform(&samples.iter().map(|sample| sample[1]).collect::<Vec<f64>>(),
+                                12.0);
+
+    for &y in &[430.0, 450.0, 475.0] {
+        assert_eq!(marginal.density(y), expected.density(y));
+    }
+}
+
+#[test]
+fn normal_marginal_of_full_bandwidth_matrix_is_exact() {
+    let samples = vec![vec![0.0, 0.0], vec![1.0, -1.0]];
+    let bandwidth = BandwidthMatrix::Full(vec![vec![2.0, 0.6], vec![0.6, 0.5]]);
+    let kde = MultivariateKernelDensityEstimation::new(&samples, bandwidth, NormalKernel);
+
+    let marginal = kde.marginal(1);
+    assert!((marginal.bandwidth() - 0.5f64.sqrt()).abs() < 1e-15);
+
+    // Integrate the joint density over the first dimension.
+    for &y in &[-2.0, -0.5, 0.0, 1.0] {
+        let steps = 4000;
+        let dx = 20.0 / steps as f64;
+        let integral: f64 = (0..steps)
+            .map(|i| kde.density(&[-10.0 + (i as f64 + 0.5) * dx, y]) * dx)
+            .sum();
+
+        assert!((integral - marginal.density(y)).abs() < 1e-9);
+    }
+}
+
+#[test]
+#[should_panic(expected="assertion failed: dimension < self.factor.len()")]
+fn marginal_panics_on_missing_dimension() {
+    let kde = MultivariateKernelDensityEstimation::new(&[vec![0.0, 1.0]],
+                                                       BandwidthMatrix::Scalar(1.0),
+                                                       NormalKernel);
+    kde.marginal(2);
+}
+
+#[test]
+fn multivariate_rules_scale_sample_covariance() {
+    let samples = read_http(8192);
+    let n = samples.len() as f64;
+
+    let mean = |i: usize| samples.iter().map(|sample| sample[i]).sum::<f64>() / n;
+    let (mean_x, mean_y) = (mean(0), mean(1));
+    let covariance = samples.iter().map(|s| (s[0] - mean_x) * (s[1] - mean_y)).sum::<f64>() /
+                     (n - 1.0);
+
+    let scott = bandwidth::multivariate_scott(&samples);
+    match scott {
+        BandwidthMatrix::Full(ref matrix) => {
+            assert!((matrix[0][1] - covariance * n.powf(-1.0 / 3.0)).abs() <
+                    1e-9 * covariance.abs());
+            assert_eq!(matrix[0][1], matrix[1][0]);
+        }
+        _ => panic!("Expected a full bandwidth matrix"),
+    }
+
+    // Identical in two dimensions.
+    assert_eq!(bandwidth::multivariate_silverman(&samples), scott);
+
+    let kde = kde::normal_multivariate(&samples, scott);
+    assert_eq!(kde.dimension(), 2);
+    assert!(kde.density(&[mean_x, mean_y]) > 0.0);
+}
+
+#[test]
+fn multivariate_silverman_differs_from_scott_in_three_dimensions() {
+    let samples = vec![vec![0.0, 1.0, 0.0], vec![1.0, 3.0, 1.0], vec![2.0, 2.0, 5.0],
+                       vec![3.0, 5.0, 2.0]];
+
+    let factor = (4.0f64 / 5.0).powf(2.0 / 7.0);
+    match (bandwidth::multivariate_scott(&samples), bandwidth::multivariate_silverman(&samples)) {
+        (BandwidthMatrix::Full(scott), BandwidthMatrix::Full(silverman)) => {
+            for i in 0..3 {
+                for j in 0..3 {
+                    assert!((silverman[i][j] - factor * scott[i][j]).abs() < 1e-12);
+                }
+            }
+        }
+        _ => panic!("Expected full bandwidth matrices"),
+    }
+}
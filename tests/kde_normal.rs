@@ -22,13 +22,20 @@ fn normal_kde_panics_on_zero_bandwidth() {
     kde::normal(&xs, 0.0);
 }
 
+#[test]
+#[should_panic(expected="assertion failed: samples.iter().all(|sample| !sample.is_nan())")]
+fn normal_kde_panics_on_nan_sample() {
+    let xs: Vec<f64> = vec![0.0, f64::NAN];
+    kde::normal(&xs, 1.0);
+}
+
 #[test]
 fn normal_kde_between_zero_and_one() {
     fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
         let kde = kde::normal(&xs.vec, bandwidth.val);
         let actual = kde.density(x);
 
-        0.0 <= actual && actual <= 1.0
+        (0.0..=1.0).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
@@ -40,7 +47,7 @@ fn normal_kde_cdf_between_zero_and_one() {
         let kde = kde::normal(&xs.vec, bandwidth.val);
         let actual = kde.cdf(x);
 
-        0.0 <= actual && actual <= 1.0
+        (0.0..=1.0).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
@@ -52,7 +59,7 @@ fn normal_kde_cdf_is_an_increasing_function() {
         let kde = kde::normal(&xs.vec, bandwidth.val);
         let actual = kde.cdf(x);
 
-        kde.cdf(x - 0.01) <= actual && actual <= kde.cdf(x + 0.01)
+        (kde.cdf(x - 0.01)..=kde.cdf(x + 0.01)).contains(&actual)
     }
 
     check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
@@ -0,0 +1,108 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::Density;
+use kernel_density::kde;
+use common::{check, read_samples, SamplesF64, PositiveF64};
+use std::f64;
+
+/// The logistic distribution, using the default quantile implementation.
+struct LogisticDensity;
+
+impl Density for LogisticDensity {
+    fn cdf(&self, x: f64) -> f64 {
+        1.0 / (1.0 + (-x).exp())
+    }
+
+    fn density(&self, x: f64) -> f64 {
+        let cdf = self.cdf(x);
+        cdf * (1.0 - cdf)
+    }
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < p && p < 1.0")]
+fn default_quantile_panics_on_probability_one() {
+    LogisticDensity.quantile(1.0);
+}
+
+#[test]
+fn default_quantile_inverts_cdf() {
+    for &p in &[1e-12f64, 0.001, 0.1, 0.5, 0.75, 0.999] {
+        let expected = (p / (1.0 - p)).ln();
+        assert!((LogisticDensity.quantile(p) - expected).abs() < 1e-9 * expected.abs().max(1.0));
+    }
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < p && p < 1.0")]
+fn kde_quantile_panics_on_zero_probability() {
+    kde::normal(&[0.0], 1.0).quantile(0.0);
+}
+
+#[test]
+fn kde_quantile_inverts_cdf() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64, p: f64) -> bool {
+        let p = p.abs().fract();
+        if p == 0.0 {
+            return true;
+        }
+
+        let kdes = [kde::epanechnikov(&xs.vec, bandwidth.val),
+                    kde::triangular(&xs.vec, bandwidth.val),
+                    kde::logistic(&xs.vec, bandwidth.val)];
+
+        kdes.iter().all(|kde| {
+            let x = kde.quantile(p);
+            (kde.cdf(x) - p).abs() < 1e-9
+        })
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64, f64) -> bool);
+}
+
+#[test]
+fn kde_quantile_is_increasing() {
+    let xs = read_samples("http_ttime.tsv");
+    let kde = kde::normal(&xs, 20.0);
+
+    let mut previous = f64::NEG_INFINITY;
+    for i in 1..100 {
+        let quantile = kde.quantile(i as f64 / 100.0);
+        assert!(quantile > previous);
+        previous = quantile;
+    }
+}
+
+#[test]
+fn kde_quantile_of_latency_tail() {
+    let xs = read_samples("http_ttime.tsv");
+    let kde = kde::epanechnikov(&xs, 20.0);
+
+    let p99 = kde.quantile(0.99);
+    assert!((kde.cdf(p99) - 0.99).abs() < 1e-12);
+
+    let mut sorted = xs.clone();
+    sorted.sort_by(|x_1, x_2| x_1.partial_cmp(x_2).unwrap());
+    let empirical = sorted[(0.99 * xs.len() as f64) as usize];
+    assert!((p99 - empirical).abs() < 50.0);
+}
+
+#[test]
+fn kde_quantile_is_left_end_of_gap() {
+    let kde = kde::uniform(&[0.0, 10.0], 1.0);
+
+    // The cumulative density is 0.5 between the two kernels.
+    assert!((kde.quantile(0.5) - 1.0).abs() < 1e-12);
+    assert!((kde.quantile(0.25) - 0.0).abs() < 1e-12);
+}
+
+#[test]
+fn normal_kde_quantile_of_extreme_probability() {
+    let kde = kde::normal(&[0.0], 1.0);
+
+    assert!((kde.quantile(1e-20) - (-9.262340089798408)).abs() < 1e-9);
+}
@@ -0,0 +1,158 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density::{linspace, Density};
+use kernel_density::kde::{EpanechnikovKernel, Kernel, KernelDensityEstimation, NormalKernel,
+                          StreamingKernelDensityEstimation};
+use common::{check, read_samples, SamplesF64, PositiveF64, EPSILON};
+
+#[test]
+#[should_panic(expected="assertion failed: bandwidth > 0.0")]
+fn new_streaming_kde_panics_on_zero_bandwidth() {
+    StreamingKernelDensityEstimation::new(0.0, NormalKernel, 10, 1.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: capacity > 0")]
+fn new_streaming_kde_panics_on_zero_capacity() {
+    StreamingKernelDensityEstimation::new(1.0, NormalKernel, 0, 1.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < forgetting && forgetting <= 1.0")]
+fn new_streaming_kde_panics_on_forgetting_above_one() {
+    StreamingKernelDensityEstimation::new(1.0, NormalKernel, 10, 1.5);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: x.is_finite()")]
+fn streaming_kde_observe_panics_on_nan() {
+    let mut kde = StreamingKernelDensityEstimation::new(1.0, NormalKernel, 10, 1.0);
+    kde.observe(f64::NAN);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: capacity > 0")]
+fn set_capacity_panics_on_zero_capacity() {
+    let mut kde = StreamingKernelDensityEstimation::new(1.0, NormalKernel, 10, 1.0);
+    kde.set_capacity(0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: 0.0 < forgetting && forgetting <= 1.0")]
+fn set_forgetting_panics_on_zero_forgetting() {
+    let mut kde = StreamingKernelDensityEstimation::new(1.0, NormalKernel, 10, 1.0);
+    kde.set_forgetting(0.0);
+}
+
+#[test]
+fn lowering_capacity_merges_down_to_new_capacity() {
+    let mut kde = StreamingKernelDensityEstimation::new(1.0, NormalKernel, 10, 1.0);
+    kde.observe_all(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+    assert_eq!(kde.components().len(), 10);
+
+    kde.set_capacity(3);
+    assert_eq!(kde.components().len(), 3);
+
+    kde.observe(10.0);
+
+    assert_eq!(kde.components().len(), 3);
+    assert!((kde.total_weight() - 11.0).abs() < EPSILON);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: !self.components.is_empty()")]
+fn streaming_kde_density_panics_before_observations() {
+    let kde = StreamingKernelDensityEstimation::new(1.0, NormalKernel, 10, 1.0);
+    kde.density(0.0);
+}
+
+#[test]
+fn streaming_kde_within_capacity_is_exact() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64, x: f64) -> bool {
+        let mut streaming = StreamingKernelDensityEstimation::new(bandwidth.val,
+                                                                  EpanechnikovKernel,
+                                                                  xs.vec.len(),
+                                                                  1.0);
+        streaming.observe_all(&xs.vec);
+        let kde = KernelDensityEstimation::new(&xs.vec, bandwidth.val, EpanechnikovKernel);
+
+        (streaming.density(x) - kde.density(x)).abs() < EPSILON * (1.0 + kde.density(x)) &&
+        (streaming.cdf(x) - kde.cdf(x)).abs() < EPSILON
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64, f64) -> bool);
+}
+
+#[test]
+fn merging_preserves_weight_mean_and_variance() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        let xs: Vec<f64> = xs.vec.into_iter().map(|x| x % 1e3).collect();
+        let h = bandwidth.val % 1e3 + 1e-3;
+
+        let mut kde = StreamingKernelDensityEstimation::new(h, NormalKernel, 8, 1.0);
+        kde.observe_all(&xs);
+
+        let n = xs.len() as f64;
+        let mean = xs.iter().sum::<f64>() / n;
+        let variance = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        // Mixture moments of the components.
+        let components = kde.components();
+        let total_weight = kde.total_weight();
+        let kde_mean = components.iter().map(|c| c.weight * c.location).sum::<f64>() / total_weight;
+        let second_moment = NormalKernel.second_moment();
+        let kde_variance = components.iter()
+            .map(|c| {
+                let spread = (c.location - kde_mean).powi(2);
+                c.weight * (spread + c.bandwidth.powi(2) * second_moment)
+            })
+            .sum::<f64>() / total_weight;
+
+        let scale = 1.0 + variance + h * h;
+
+        components.len() <= 8 && (total_weight - n).abs() < 1e-9 &&
+        components.windows(2).all(|pair| pair[0].location <= pair[1].location) &&
+        (kde_mean - mean).abs() < 1e-9 * (1.0 + mean.abs()) &&
+        (kde_variance - (variance + h * h)).abs() < 1e-6 * scale
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn compressed_estimation_approximates_full_estimation() {
+    let xs = read_samples("normal_0_1.tsv");
+    let bandwidth = 0.3;
+
+    let mut streaming = StreamingKernelDensityEstimation::new(bandwidth, NormalKernel, 64, 1.0);
+    streaming.observe_all(&xs);
+    let kde = KernelDensityEstimation::new(&xs, bandwidth, NormalKernel);
+
+    assert_eq!(streaming.components().len(), 64);
+    for x in linspace(-4.0, 4.0, 81) {
+        assert!((streaming.density(x) - kde.density(x)).abs() < 5e-3);
+        assert!((streaming.cdf(x) - kde.cdf(x)).abs() < 5e-3);
+    }
+}
+
+#[test]
+fn forgetting_tracks_recent_observations() {
+    let xs = read_samples("normal_0_1.tsv");
+    let forgetting = 0.99;
+
+    let mut kde = StreamingKernelDensityEstimation::new(0.3, NormalKernel, 64, forgetting);
+    kde.observe_all(&xs[..4096]);
+    let shifted: Vec<f64> = xs[4096..].iter().map(|x| x + 10.0).collect();
+    kde.observe_all(&shifted);
+
+    // A geometric series of weights over the observations.
+    let expected = (1.0 - forgetting.powi(xs.len() as i32)) / (1.0 - forgetting);
+    assert!((kde.total_weight() - expected).abs() < 1e-9 * expected);
+
+    assert!(kde.cdf(5.0) < 1e-6);
+    assert!((kde.quantile(0.5) - 10.0).abs() < 0.5);
+}
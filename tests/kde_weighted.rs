@@ -0,0 +1,186 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::kde;
+use kernel_density::kde::{KernelDensityEstimation, NormalKernel};
+use kernel_density::kde::bandwidth::{effective_sample_size, frequency_scott, frequency_silverman,
+                                     scott, silverman, weighted_scott, weighted_silverman};
+use common::{check, read_samples, SamplesF64, PositiveF64};
+use std::f64;
+
+/// Generate deterministic small integer counts for a sample.
+fn counts(length: usize) -> Vec<f64> {
+    (0..length).map(|i| (1 + i % 4) as f64).collect()
+}
+
+/// Replicate each sample by its count.
+fn replicate(xs: &[f64], counts: &[f64]) -> Vec<f64> {
+    let mut replicated = vec![];
+    for (&x, &count) in xs.iter().zip(counts) {
+        for _ in 0..count as usize {
+            replicated.push(x);
+        }
+    }
+    replicated
+}
+
+#[test]
+#[should_panic(expected="assertion failed: weights.len() == length")]
+fn weighted_kde_panics_on_mismatched_weights() {
+    kde::normal_weighted(&[0.0, 1.0], &[1.0], 1.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: weights.iter().all(|&weight| weight >= 0.0 && weight.is_finite())")]
+fn weighted_kde_panics_on_negative_weight() {
+    kde::epanechnikov_weighted(&[0.0, 1.0], &[1.0, -1.0], 1.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: total_weight > 0.0")]
+fn weighted_kde_panics_on_zero_total_weight() {
+    kde::uniform_weighted(&[0.0, 1.0], &[0.0, 0.0], 1.0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: bandwidth > 0.0")]
+fn weighted_kde_panics_on_zero_bandwidth() {
+    kde::normal_weighted(&[0.0], &[1.0], 0.0);
+}
+
+#[test]
+fn weighted_kde_with_counts_matches_replicated_samples() {
+    fn prop(xs: SamplesF64, x: f64, bandwidth: PositiveF64) -> bool {
+        let counts = counts(xs.vec.len());
+        let replicated = replicate(&xs.vec, &counts);
+
+        let pairs = [(kde::normal_weighted(&xs.vec, &counts, bandwidth.val),
+                          kde::normal(&replicated, bandwidth.val)),
+                         (kde::epanechnikov_weighted(&xs.vec, &counts, bandwidth.val),
+                          kde::epanechnikov(&replicated, bandwidth.val)),
+                         (kde::uniform_weighted(&xs.vec, &counts, bandwidth.val),
+                          kde::uniform(&replicated, bandwidth.val))];
+
+        pairs.iter().all(|(weighted, replicated)| {
+            (weighted.density(x) - replicated.density(x)).abs() < 1e-12 &&
+            (weighted.cdf(x) - replicated.cdf(x)).abs() < 1e-12
+        })
+    }
+
+    check(prop as fn(SamplesF64, f64, PositiveF64) -> bool);
+}
+
+#[test]
+fn weighted_kde_is_invariant_to_weight_scale() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let weights = counts(xs.len());
+    let scaled: Vec<f64> = weights.iter().map(|w| 0.001 * w).collect();
+
+    let kde = kde::normal_weighted(&xs, &weights, 0.25);
+    let scaled_kde = kde::normal_weighted(&xs, &scaled, 0.25);
+
+    for &x in &[-2.0, -0.5, 0.0, 0.3, 1.7] {
+        assert!((kde.density(x) - scaled_kde.density(x)).abs() < 1e-12);
+        assert!((kde.cdf(x) - scaled_kde.cdf(x)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn weighted_kde_ignores_zero_weight_samples() {
+    let xs = vec![0.0, 1.0, 2.0, 100.0];
+    let weights = vec![1.0, 1.0, 1.0, 0.0];
+
+    let weighted = kde::epanechnikov_weighted(&xs, &weights, 0.5);
+    let unweighted = kde::epanechnikov(&xs[..3], 0.5);
+
+    for &x in &[-0.25, 0.0, 0.75, 1.5, 100.0] {
+        assert_eq!(weighted.density(x), unweighted.density(x));
+        assert_eq!(weighted.cdf(x), unweighted.cdf(x));
+    }
+}
+
+#[test]
+fn weighted_kde_cdf_f64max_is_one() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        let counts = counts(xs.vec.len());
+        let kde = kde::uniform_weighted(&xs.vec, &counts, bandwidth.val);
+
+        kde.cdf(f64::MAX) == 1.0
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn unweighted_effective_sample_size_is_length() {
+    fn prop(xs: SamplesF64) -> bool {
+        let kde = KernelDensityEstimation::new(&xs.vec, 1.0, NormalKernel);
+        kde.effective_sample_size() == xs.vec.len() as f64
+    }
+
+    check(prop as fn(SamplesF64) -> bool);
+}
+
+#[test]
+fn effective_sample_size_of_unequal_weights() {
+    assert_eq!(effective_sample_size(&[3.0, 1.0]), 16.0 / 10.0);
+    assert_eq!(effective_sample_size(&[5.0, 0.0, 0.0]), 1.0);
+}
+
+#[test]
+fn weighted_silverman_of_equal_weights_is_near_silverman() {
+    let xs = read_samples("normal_0_1.tsv");
+    let weights = vec![1.0; xs.len()];
+
+    let expected = silverman(&xs);
+    assert!((weighted_silverman(&xs, &weights) - expected).abs() < 1e-3 * expected);
+}
+
+#[test]
+fn importance_weights_ignore_scale_but_counts_do_not() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let weights = vec![1.0; xs.len()];
+    let scaled = vec![7.0; xs.len()];
+
+    assert_eq!(weighted_silverman(&xs, &weights), weighted_silverman(&xs, &scaled));
+    assert!(frequency_silverman(&xs, &scaled) < frequency_silverman(&xs, &weights));
+}
+
+#[test]
+fn frequency_rules_match_replicated_sample() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let counts = counts(xs.len());
+    let replicated = replicate(&xs, &counts);
+
+    let expected = silverman(&replicated);
+    assert!((frequency_silverman(&xs, &counts) - expected).abs() < 1e-12);
+
+    let expected = scott(&replicated);
+    assert!((frequency_scott(&xs, &counts) - expected).abs() < 1e-12);
+}
+
+#[test]
+fn frequency_rules_use_total_count() {
+    let xs = vec![0.0, 1.0, 2.0, 3.0];
+    let counts = vec![10.0, 30.0, 30.0, 10.0];
+    let replicated = replicate(&xs, &counts);
+
+    // The effective sample size of these weights is only 3.2.
+    let expected = silverman(&replicated);
+    assert!((frequency_silverman(&xs, &counts) - expected).abs() < 1e-12);
+    assert!(weighted_silverman(&xs, &counts) > 1.5 * expected);
+}
+
+#[test]
+fn weighted_rules_are_positive() {
+    fn prop(xs: SamplesF64) -> bool {
+        let counts = counts(xs.vec.len());
+        weighted_silverman(&xs.vec, &counts) > 0.0 &&
+        weighted_scott(&xs.vec, &counts) > weighted_silverman(&xs.vec, &counts)
+    }
+
+    check(prop as fn(SamplesF64) -> bool);
+}
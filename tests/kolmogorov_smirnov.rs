@@ -46,7 +46,7 @@ fn test_panics_on_confidence_geq_one() {
 /// Kolmogorov-Smirnov test. This simple implementation is used as a
 /// verification check against actual calculation used.
 fn calculate_statistic_alt(xs: &[f64], ys: &[f64]) -> f64 {
-    assert!(xs.len() > 0 && ys.len() > 0);
+    assert!(!xs.is_empty() && !ys.is_empty());
 
     let ecdf_xs = Ecdf::new(xs);
     let ecdf_ys = Ecdf::new(ys);
@@ -54,14 +54,14 @@ fn calculate_statistic_alt(xs: &[f64], ys: &[f64]) -> f64 {
     let mut statistic = 0.0;
 
     for x in xs.iter() {
-        let diff = (ecdf_xs.value(x.clone()) - ecdf_ys.value(x.clone())).abs();
+        let diff = (ecdf_xs.value(*x) - ecdf_ys.value(*x)).abs();
         if diff > statistic {
             statistic = diff;
         }
     }
 
     for y in ys.iter() {
-        let diff = (ecdf_xs.value(y.clone()) - ecdf_ys.value(y.clone())).abs();
+        let diff = (ecdf_xs.value(*y) - ecdf_ys.value(*y)).abs();
         if diff > statistic {
             statistic = diff;
         }
@@ -89,7 +89,7 @@ fn test_statistic_is_between_zero_and_one() {
         let result = test(&xs.vec, &ys.vec, 0.95);
         let actual = result.statistic;
 
-        0.0 <= actual && actual <= 1.0
+        (0.0..=1.0).contains(&actual)
     }
 
     check(prop as fn(MoreThanSevenSamplesF64, MoreThanSevenSamplesF64) -> bool);
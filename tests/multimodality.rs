@@ -0,0 +1,204 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::multimodality::{count_modes, critical_bandwidth, dip, dip_test, silverman};
+use common::{check, read_samples, seeded, MoreThanSevenSamplesF64, PositiveF64, SamplesF64};
+
+fn bimodal() -> Vec<f64> {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let mut mixture = xs.clone();
+    mixture.extend(xs.iter().map(|x| x + 6.0));
+    mixture
+}
+
+#[test]
+#[should_panic(expected="assertion failed: n > 0")]
+fn dip_panics_on_empty_samples() {
+    dip(&[]);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: length > 3")]
+fn dip_test_panics_on_three_samples() {
+    dip_test(&[0.0, 1.0, 2.0]);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: modes > 0")]
+fn critical_bandwidth_panics_on_zero_modes() {
+    critical_bandwidth(&[0.0, 1.0], 0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: sorted.len() > modes")]
+fn critical_bandwidth_panics_on_too_few_distinct_samples() {
+    critical_bandwidth(&[0.0, 0.0, 1.0, 1.0], 2);
+}
+
+#[test]
+fn evenly_spaced_dip_is_minimal() {
+    for &n in &[1, 2, 5, 10, 100] {
+        let xs: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        assert!((dip(&xs) - 0.5 / n as f64).abs() < 1e-15);
+    }
+}
+
+#[test]
+fn two_clusters_have_maximal_dip() {
+    let xs = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+    assert_eq!(dip(&xs), 0.25);
+}
+
+#[test]
+fn two_atom_dip_is_half_the_smaller_mass() {
+    // The closest unimodal distribution puts the mode at the larger atom and
+    // spreads the smaller atom linearly, missing it by half its mass.
+    let n = 10;
+    for k in 1..n {
+        let xs: Vec<f64> = (0..n).map(|i| if i < k { 0.0 } else { 1.0 }).collect();
+        let expected = k.min(n - k) as f64 / (2 * n) as f64;
+        assert!((dip(&xs) - expected).abs() < 1e-15);
+    }
+}
+
+#[test]
+fn dip_is_bounded_and_affine_invariant() {
+    fn prop(xs: SamplesF64, scale: PositiveF64) -> bool {
+        let n = xs.vec.len() as f64;
+        let statistic = dip(&xs.vec);
+
+        let transformed: Vec<f64> = xs.vec.iter().map(|x| 0.5 - x * scale.val).collect();
+
+        0.5 / n - 1e-12 <= statistic && statistic <= (0.5 / n).max(0.25) + 1e-12 &&
+        (dip(&transformed) - statistic).abs() < 1e-9
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn count_modes_decreases_with_bandwidth() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        let xs: Vec<f64> = xs.vec.into_iter().take(100).collect();
+        count_modes(&xs, bandwidth.val) >= count_modes(&xs, 2.0 * bandwidth.val)
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn critical_bandwidth_separates_mode_counts() {
+    fn prop(xs: MoreThanSevenSamplesF64) -> bool {
+        let xs: Vec<f64> = xs.vec.into_iter().take(100).collect();
+        let mut distinct = xs.clone();
+        distinct.sort_by(|x_1, x_2| x_1.partial_cmp(x_2).unwrap());
+        distinct.dedup();
+
+        (1..3).filter(|&modes| distinct.len() > modes).all(|modes| {
+            let bandwidth = critical_bandwidth(&xs, modes);
+            count_modes(&xs, bandwidth) <= modes && count_modes(&xs, 0.99 * bandwidth) > modes
+        })
+    }
+
+    check(prop as fn(MoreThanSevenSamplesF64) -> bool);
+}
+
+/// Count the local maxima of the Normal kernel density estimation of a sample
+/// on a dense grid, independently of `count_modes`.
+fn dense_count_modes(xs: &[f64], bandwidth: f64) -> usize {
+    let min = xs.iter().cloned().fold(f64::MAX, f64::min) - bandwidth;
+    let max = xs.iter().cloned().fold(f64::MIN, f64::max) + bandwidth;
+    let steps = 200000;
+
+    let density: Vec<f64> = (0..(steps + 1))
+        .map(|i| {
+            let x = min + (max - min) * i as f64 / steps as f64;
+            xs.iter().map(|sample| (-0.5 * ((x - sample) / bandwidth).powi(2)).exp()).sum()
+        })
+        .collect();
+
+    density.windows(3).filter(|triple| triple[0] < triple[1] && triple[1] >= triple[2]).count()
+}
+
+#[test]
+fn critical_bandwidth_resolves_emerging_mode() {
+    // A second mode forms between grid points just below the critical
+    // bandwidth.
+    let xs = vec![0.0, 0.3, 0.5, 1.0, 1.1, 2.9, 3.0, 3.2, 3.5, 4.1];
+    let bandwidth = critical_bandwidth(&xs, 1);
+
+    assert_eq!(count_modes(&xs, bandwidth), 1);
+    assert_eq!(count_modes(&xs, (1.0 - 1e-5) * bandwidth), 2);
+    assert_eq!(dense_count_modes(&xs, 1.001 * bandwidth), 1);
+    assert_eq!(dense_count_modes(&xs, 0.999 * bandwidth), 2);
+}
+
+#[test]
+fn count_modes_resolves_near_duplicate_samples() {
+    assert_eq!(count_modes(&[0.0, 1e-6, 1.0, 2.0], 1e-7), 4);
+    assert_eq!(count_modes(&[0.0, 1e-6, 1.0, 2.0], 1e-5), 3);
+}
+
+#[test]
+fn critical_bandwidth_of_near_duplicate_samples() {
+    let xs = vec![0.0, 1e-9, 1.0, 2.0];
+    let bandwidth = critical_bandwidth(&xs, 3);
+
+    assert!(bandwidth < 1e-9);
+    assert_eq!(count_modes(&xs, bandwidth), 3);
+    assert_eq!(count_modes(&xs, 0.99 * bandwidth), 4);
+}
+
+#[test]
+fn silverman_rejects_unimodality_of_mixture() {
+    let mut rng = seeded();
+
+    let test = silverman(&bimodal(), 1, 100, &mut rng);
+    assert_eq!(test.modes, 1);
+    assert!(test.p_value < 0.05);
+
+    let test = silverman(&bimodal(), 2, 100, &mut rng);
+    assert!(test.p_value > 0.05);
+}
+
+#[test]
+fn silverman_accepts_unimodality_of_normal() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let mut rng = seeded();
+
+    let test = silverman(&xs, 1, 100, &mut rng);
+    assert!(test.p_value > 0.05);
+}
+
+#[test]
+fn dip_test_rejects_unimodality_of_mixture() {
+    let test = dip_test(&bimodal());
+    assert!(test.p_value < 0.01);
+}
+
+#[test]
+fn dip_test_accepts_unimodality_of_normal() {
+    let test = dip_test(&read_samples("normal_0_1.tsv"));
+    assert!(test.p_value > 0.05);
+}
+
+#[test]
+fn dip_test_p_value_is_a_probability() {
+    fn prop(xs: MoreThanSevenSamplesF64) -> bool {
+        let test = dip_test(&xs.vec);
+        0.001 <= test.p_value && test.p_value <= 1.0
+    }
+
+    check(prop as fn(MoreThanSevenSamplesF64) -> bool);
+}
+
+#[test]
+fn evenly_spaced_dip_test_accepts_unimodality() {
+    for &n in &[4, 12, 100, 20000] {
+        let xs: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        assert_eq!(dip_test(&xs).p_value, 1.0);
+    }
+}
@@ -0,0 +1,144 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::kde::{EpanechnikovKernel, NormalKernel, UniformKernel};
+use kernel_density::regression::{cross_validation, weights, KernelRegression, Method};
+use common::{check, SamplesF64, PositiveF64};
+
+#[test]
+#[should_panic(expected="assertion failed: ys.len() == length")]
+fn regression_panics_on_mismatched_responses() {
+    KernelRegression::new(&[0.0, 1.0], &[0.0], 1.0, NormalKernel, Method::NadarayaWatson);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: bandwidth > 0.0")]
+fn regression_panics_on_zero_bandwidth() {
+    KernelRegression::new(&[0.0], &[0.0], 0.0, NormalKernel, Method::LocalLinear);
+}
+
+#[test]
+fn weights_sum_to_one() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        let x = xs.vec[0];
+
+        [Method::NadarayaWatson, Method::LocalLinear].iter().all(|&method| {
+            let total: f64 = weights(&xs.vec, x, bandwidth.val, &NormalKernel, method)
+                .iter()
+                .sum();
+            (total - 1.0).abs() < 1e-9
+        })
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn local_linear_reproduces_lines() {
+    fn prop(xs: SamplesF64, bandwidth: PositiveF64) -> bool {
+        // Construction is quadratic in the number of samples.
+        let xs: Vec<f64> = xs.vec.into_iter().take(100).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 3.0 - 0.5 * x).collect();
+        let regression = KernelRegression::new(&xs, &ys, bandwidth.val, NormalKernel,
+                                               Method::LocalLinear);
+
+        xs.iter().zip(&ys).all(|(&x, &y)| {
+            (regression.fit(x).value - y).abs() <= 1e-6 * (1.0 + y.abs() + x.abs())
+        })
+    }
+
+    check(prop as fn(SamplesF64, PositiveF64) -> bool);
+}
+
+#[test]
+fn nadaraya_watson_has_boundary_bias_on_lines() {
+    let xs: Vec<f64> = (0..11).map(|i| i as f64).collect();
+    let ys = xs.clone();
+
+    let nadaraya_watson = KernelRegression::new(&xs, &ys, 2.0, EpanechnikovKernel,
+                                                Method::NadarayaWatson);
+    let local_linear = KernelRegression::new(&xs, &ys, 2.0, EpanechnikovKernel,
+                                             Method::LocalLinear);
+
+    // The locally constant fit is pulled towards the interior at the edges.
+    assert!(nadaraya_watson.fit(0.0).value > 0.4);
+    assert!(local_linear.fit(0.0).value.abs() < 1e-12);
+
+    // In the interior of evenly spaced samples both are exact.
+    assert!((nadaraya_watson.fit(5.0).value - 5.0).abs() < 1e-12);
+    assert!((local_linear.fit(5.0).value - 5.0).abs() < 1e-12);
+}
+
+#[test]
+fn oversmoothed_fit_is_sample_mean() {
+    let xs = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+    let ys = vec![1.0, 4.0, 2.0, 8.0, 5.0];
+
+    // The uniform kernel over all samples gives equal weights.
+    let regression = KernelRegression::new(&xs, &ys, 100.0, UniformKernel,
+                                           Method::NadarayaWatson);
+
+    let n = ys.len() as f64;
+    let mean = ys.iter().sum::<f64>() / n;
+    let variance = ys.iter().map(|y| (y - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    let fit = regression.fit(2.5);
+    assert!((fit.value - mean).abs() < 1e-12);
+    assert!((regression.variance() - variance).abs() < 1e-12);
+    assert!((fit.standard_error - (variance / n).sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn interpolating_fit_has_undefined_standard_error() {
+    let regression = KernelRegression::new(&[0.0, 1.0], &[0.0, 1.0], 0.1, UniformKernel,
+                                           Method::LocalLinear);
+
+    assert_eq!(regression.fit(0.0).value, 0.0);
+    assert!(regression.variance().is_nan());
+    assert!(regression.fit(0.0).standard_error.is_nan());
+}
+
+#[test]
+fn fit_is_nan_outside_support() {
+    let regression = KernelRegression::new(&[0.0, 1.0, 2.0], &[0.0, 1.0, 0.0], 1.0,
+                                           UniformKernel, Method::NadarayaWatson);
+
+    assert!(regression.fit(10.0).value.is_nan());
+}
+
+#[test]
+fn cross_validation_objective_is_leave_one_out_error() {
+    let xs: Vec<f64> = (0..30).map(|i| (i as f64 * 0.37).sin() * 5.0).collect();
+    let ys: Vec<f64> = xs.iter().enumerate().map(|(i, x)| x * x + (i as f64).cos()).collect();
+
+    for &method in &[Method::NadarayaWatson, Method::LocalLinear] {
+        let cv = cross_validation::least_squares(&xs, &ys, &NormalKernel, method, 0.1, 5.0);
+
+        for &(bandwidth, objective) in cv.curve.iter().step_by(7) {
+            if objective.is_infinite() {
+                continue;
+            }
+
+            let mut sum = 0.0;
+            for i in 0..xs.len() {
+                let mut left_xs = xs.clone();
+                let mut left_ys = ys.clone();
+                left_xs.remove(i);
+                left_ys.remove(i);
+
+                let regression = KernelRegression::new(&left_xs, &left_ys, bandwidth,
+                                                       NormalKernel, method);
+                sum += (ys[i] - regression.fit(xs[i]).value).powi(2);
+            }
+
+            let expected = sum / xs.len() as f64;
+            assert!((objective - expected).abs() <= 1e-9 * expected);
+        }
+
+        assert!(cv.objective <= cv.curve.iter().map(|&(_, o)| o).fold(f64::INFINITY, f64::min));
+        assert!(0.1 <= cv.bandwidth && cv.bandwidth <= 5.0);
+    }
+}
@@ -0,0 +1,163 @@
+mod common;
+
+extern crate kernel_density;
+extern crate rand;
+extern crate quickcheck;
+
+use kernel_density::density;
+use kernel_density::density::Density;
+use kernel_density::kde;
+use kernel_density::kde::{Boundary, BoundedKernelDensityEstimation, Kernel, BiweightKernel,
+                          CosineKernel, EpanechnikovKernel, LogisticKernel, NormalKernel,
+                          SigmoidKernel, TriangularKernel, TricubeKernel, TriweightKernel,
+                          UniformKernel};
+use common::{read_samples, seeded};
+use rand::Rng;
+use std::f64;
+
+const DRAWS: usize = 20000;
+
+/// Check the sample mean and variance of kernel draws against the kernel
+/// second moment, and that draws are within the kernel support.
+fn assert_kernel_moments<K: Kernel>(kernel: K) {
+    let mut rng = seeded();
+    let draws: Vec<f64> = (0..DRAWS).map(|_| kernel.sample(&mut rng)).collect();
+
+    let mean = draws.iter().sum::<f64>() / DRAWS as f64;
+    let second_moment = draws.iter().map(|x| x * x).sum::<f64>() / DRAWS as f64;
+
+    assert!(mean.abs() < 0.05, "mean {}", mean);
+    assert!((second_moment / kernel.second_moment() - 1.0).abs() < 0.05,
+            "second moment {} expected {}",
+            second_moment,
+            kernel.second_moment());
+    assert!(draws.iter().all(|x| x.abs() <= kernel.support()));
+}
+
+/// Check the empirical cumulative density of draws against a density at its
+/// deciles, within a Kolmogorov-Smirnov style tolerance.
+fn assert_draws_follow_cdf(density: &dyn Density, draws: &[f64]) {
+    for i in 1..10 {
+        let p = i as f64 / 10.0;
+        let quantile = density.quantile(p);
+        let below = draws.iter().filter(|&&x| x <= quantile).count() as f64 / draws.len() as f64;
+
+        assert!((below - p).abs() < 2.0 / (draws.len() as f64).sqrt(),
+                "p {} empirical {}",
+                p,
+                below);
+    }
+}
+
+#[test]
+fn kernel_samples_have_kernel_moments() {
+    assert_kernel_moments(BiweightKernel);
+    assert_kernel_moments(CosineKernel);
+    assert_kernel_moments(EpanechnikovKernel);
+    assert_kernel_moments(LogisticKernel);
+    assert_kernel_moments(NormalKernel);
+    assert_kernel_moments(SigmoidKernel);
+    assert_kernel_moments(TriangularKernel);
+    assert_kernel_moments(TricubeKernel);
+    assert_kernel_moments(TriweightKernel);
+    assert_kernel_moments(UniformKernel);
+}
+
+#[test]
+fn normal_density_samples_follow_cdf() {
+    let normal = density::normal(3.0, 4.0);
+
+    let mut rng = seeded();
+    let draws: Vec<f64> = (0..DRAWS).map(|_| normal.sample(&mut rng)).collect();
+
+    assert_draws_follow_cdf(&*normal, &draws);
+}
+
+#[test]
+fn seeded_samples_are_reproducible() {
+    let xs = read_samples("normal_0_1.1.tsv");
+    let kde = kde::epanechnikov(&xs, 0.25);
+
+    let mut rng = seeded();
+    let first: Vec<f64> = (0..100).map(|_| kde.sample(&mut rng)).collect();
+
+    let mut rng = seeded();
+    let second: Vec<f64> = (0..100).map(|_| kde.sample(&mut rng)).collect();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn kde_samples_follow_cdf() {
+    let xs = read_samples("http_ttime.tsv");
+    let kdes = [kde::normal(&xs, 20.0),
+                kde::epanechnikov(&xs, 20.0),
+                kde::uniform(&xs, 20.0),
+                kde::triangular(&xs, 20.0),
+                kde::normal_adaptive(&xs, 20.0, 0.5)];
+
+    let mut rng = seeded();
+    for kde in &kdes {
+        let draws: Vec<f64> = (0..DRAWS).map(|_| kde.sample(&mut rng)).collect();
+        assert_draws_follow_cdf(&**kde, &draws);
+    }
+}
+
+#[test]
+fn weighted_kde_samples_never_draw_zero_weight_samples() {
+    let xs = vec![0.0, 100.0, 200.0];
+    let weights = vec![1.0, 0.0, 1.0];
+    let kde = kde::uniform_weighted(&xs, &weights, 1.0);
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        let x = kde.sample(&mut rng);
+        assert!((x - 100.0).abs() > 50.0);
+    }
+}
+
+#[test]
+fn bounded_kde_samples_are_within_bounds_and_follow_cdf() {
+    let xs: Vec<f64> = read_samples("normal_0_1.1.tsv")
+        .iter()
+        .map(|&x| NormalKernel.cdf(x))
+        .collect();
+
+    let mut rng = seeded();
+    for &boundary in &[Boundary::Reflection, Boundary::Renormalisation, Boundary::Transformation] {
+        let kde = BoundedKernelDensityEstimation::new(&xs, 0.2, NormalKernel, 0.0, 1.0, boundary);
+        let draws: Vec<f64> = (0..DRAWS).map(|_| kde.sample(&mut rng)).collect();
+
+        assert!(draws.iter().all(|x| (0.0..=1.0).contains(x)));
+        assert_draws_follow_cdf(&kde, &draws);
+    }
+}
+
+#[test]
+fn default_sample_inverts_cdf() {
+    /// The standard exponential distribution.
+    struct ExponentialDensity;
+
+    impl Density for ExponentialDensity {
+        fn cdf(&self, x: f64) -> f64 {
+            if x > 0.0 { 1.0 - (-x).exp() } else { 0.0 }
+        }
+
+        fn density(&self, x: f64) -> f64 {
+            if x > 0.0 { (-x).exp() } else { 0.0 }
+        }
+    }
+
+    let mut rng = seeded();
+    let draws: Vec<f64> = (0..DRAWS).map(|_| ExponentialDensity.sample(&mut rng)).collect();
+
+    assert!(draws.iter().all(|&x| x >= 0.0));
+    assert_draws_follow_cdf(&ExponentialDensity, &draws);
+
+    let mean = draws.iter().sum::<f64>() / DRAWS as f64;
+    assert!((mean - 1.0).abs() < 0.05);
+
+    // Draws through a trait object use the same generator.
+    let mut boxed: Box<dyn Rng> = Box::new(seeded());
+    assert_eq!(ExponentialDensity.sample(&mut *boxed), draws[0]);
+}